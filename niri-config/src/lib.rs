@@ -47,6 +47,8 @@ pub struct Config {
     pub environment: Environment,
     #[knuffel(children(name = "window-rule"))]
     pub window_rules: Vec<WindowRule>,
+    #[knuffel(children(name = "workspace"))]
+    pub workspaces: Vec<Workspace>,
     #[knuffel(child, default)]
     pub binds: Binds,
     #[knuffel(child, default)]
@@ -250,6 +252,8 @@ pub struct Output {
     pub position: Option<Position>,
     #[knuffel(child, unwrap(argument, str))]
     pub mode: Option<Mode>,
+    #[knuffel(child)]
+    pub layout: Option<LayoutOverride>,
 }
 
 impl Default for Output {
@@ -261,6 +265,7 @@ impl Default for Output {
             transform: Transform::Normal,
             position: None,
             mode: None,
+            layout: None,
         }
     }
 }
@@ -287,9 +292,11 @@ pub struct Layout {
     #[knuffel(child, default)]
     pub border: Border,
     #[knuffel(child, unwrap(children), default)]
-    pub preset_column_widths: Vec<PresetWidth>,
+    pub preset_column_widths: Vec<PresetSize>,
     #[knuffel(child)]
     pub default_column_width: Option<DefaultColumnWidth>,
+    #[knuffel(child, unwrap(children), default)]
+    pub preset_window_heights: Vec<PresetSize>,
     #[knuffel(child, unwrap(argument), default)]
     pub center_focused_column: CenterFocusedColumn,
     #[knuffel(child, unwrap(argument), default = Self::default().gaps)]
@@ -305,6 +312,7 @@ impl Default for Layout {
             border: Default::default(),
             preset_column_widths: Default::default(),
             default_column_width: Default::default(),
+            preset_window_heights: Default::default(),
             center_focused_column: Default::default(),
             gaps: 16,
             struts: Default::default(),
@@ -312,6 +320,30 @@ impl Default for Layout {
     }
 }
 
+/// Partial layout settings that take precedence over the global `layout` section.
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
+pub struct LayoutOverride {
+    #[knuffel(child, unwrap(children))]
+    pub preset_column_widths: Option<Vec<PresetSize>>,
+    #[knuffel(child)]
+    pub default_column_width: Option<DefaultColumnWidth>,
+    #[knuffel(child, unwrap(children))]
+    pub preset_window_heights: Option<Vec<PresetSize>>,
+    #[knuffel(child, unwrap(argument))]
+    pub center_focused_column: Option<CenterFocusedColumn>,
+    #[knuffel(child, unwrap(argument))]
+    pub gaps: Option<u16>,
+    #[knuffel(child)]
+    pub struts: Option<Struts>,
+}
+
+impl LayoutOverride {
+    /// Parses the contents of a `layout` block, e.g. when it's set at runtime.
+    pub fn parse(text: &str) -> Result<Self, knuffel::Error> {
+        knuffel::parse("layout", text)
+    }
+}
+
 #[derive(knuffel::Decode, Debug, Clone, PartialEq, Eq)]
 pub struct SpawnAtStartup {
     #[knuffel(arguments)]
@@ -447,13 +479,13 @@ impl Default for Cursor {
 }
 
 #[derive(knuffel::Decode, Debug, Clone, Copy, PartialEq)]
-pub enum PresetWidth {
+pub enum PresetSize {
     Proportion(#[knuffel(argument)] f64),
     Fixed(#[knuffel(argument)] i32),
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct DefaultColumnWidth(pub Option<PresetWidth>);
+pub struct DefaultColumnWidth(pub Option<PresetSize>);
 
 #[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct Struts {
@@ -485,8 +517,12 @@ pub struct Animations {
     pub horizontal_view_movement: Animation,
     #[knuffel(child, default = Animation::default_window_open())]
     pub window_open: Animation,
+    #[knuffel(child, default = Animation::default_window_movement())]
+    pub window_movement: Animation,
     #[knuffel(child, default = Animation::default_config_notification_open_close())]
     pub config_notification_open_close: Animation,
+    #[knuffel(child, default = Animation::default_overview_open_close())]
+    pub overview_open_close: Animation,
 }
 
 impl Default for Animations {
@@ -497,7 +533,9 @@ impl Default for Animations {
             workspace_switch: Animation::default_workspace_switch(),
             horizontal_view_movement: Animation::default_horizontal_view_movement(),
             window_open: Animation::default_window_open(),
+            window_movement: Animation::default_window_movement(),
             config_notification_open_close: Animation::default_config_notification_open_close(),
+            overview_open_close: Animation::default_overview_open_close(),
         }
     }
 }
@@ -545,6 +583,17 @@ impl Animation {
         }
     }
 
+    pub const fn default_window_movement() -> Self {
+        Self {
+            off: false,
+            kind: AnimationKind::Spring(SpringParams {
+                damping_ratio: 1.,
+                stiffness: 800,
+                epsilon: 0.0001,
+            }),
+        }
+    }
+
     pub const fn default_config_notification_open_close() -> Self {
         Self {
             off: false,
@@ -556,6 +605,17 @@ impl Animation {
         }
     }
 
+    pub const fn default_overview_open_close() -> Self {
+        Self {
+            off: false,
+            kind: AnimationKind::Spring(SpringParams {
+                damping_ratio: 1.,
+                stiffness: 800,
+                epsilon: 0.0001,
+            }),
+        }
+    }
+
     pub const fn default_window_open() -> Self {
         Self {
             off: false,
@@ -619,6 +679,16 @@ pub struct EnvironmentVariable {
     pub value: Option<String>,
 }
 
+#[derive(knuffel::Decode, Debug, Clone, PartialEq)]
+pub struct Workspace {
+    #[knuffel(argument)]
+    pub name: String,
+    #[knuffel(child, unwrap(argument))]
+    pub open_on_output: Option<String>,
+    #[knuffel(child)]
+    pub layout: Option<LayoutOverride>,
+}
+
 #[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
 pub struct WindowRule {
     #[knuffel(children(name = "match"))]
@@ -632,9 +702,13 @@ pub struct WindowRule {
     #[knuffel(child, unwrap(argument))]
     pub open_on_output: Option<String>,
     #[knuffel(child, unwrap(argument))]
+    pub open_on_workspace: Option<WorkspaceReference>,
+    #[knuffel(child, unwrap(argument))]
     pub open_maximized: Option<bool>,
     #[knuffel(child, unwrap(argument))]
     pub open_fullscreen: Option<bool>,
+    #[knuffel(child, unwrap(argument))]
+    pub open_floating: Option<bool>,
 
     // Rules applied dynamically.
     #[knuffel(child, unwrap(argument))]
@@ -741,62 +815,144 @@ pub enum Action {
     ScreenshotScreen,
     ScreenshotWindow,
     CloseWindow,
+    #[knuffel(skip)]
+    CloseWindowById(u64),
     FullscreenWindow,
+    #[knuffel(skip)]
+    FullscreenWindowById(u64),
+    #[knuffel(skip)]
+    FocusWindow(u64),
+    FocusWindowPrevious,
+    WindowSwitcherNext,
+    WindowSwitcherPrevious,
     FocusColumnLeft,
     FocusColumnRight,
     FocusColumnFirst,
     FocusColumnLast,
+    FocusColumn(#[knuffel(argument)] usize),
+    FocusColumnOrMonitorLeft,
+    FocusColumnOrMonitorRight,
     FocusWindowDown,
     FocusWindowUp,
     FocusWindowOrWorkspaceDown,
     FocusWindowOrWorkspaceUp,
+    FocusWindowOrMonitorDown,
+    FocusWindowOrMonitorUp,
     MoveColumnLeft,
     MoveColumnRight,
     MoveColumnToFirst,
     MoveColumnToLast,
+    MoveColumnToIndex(#[knuffel(argument)] usize),
+    MoveColumnLeftOrToMonitorLeft,
+    MoveColumnRightOrToMonitorRight,
     MoveWindowDown,
     MoveWindowUp,
     MoveWindowDownOrToWorkspaceDown,
     MoveWindowUpOrToWorkspaceUp,
+    MoveWindowDownOrToMonitorDown,
+    MoveWindowUpOrToMonitorUp,
     ConsumeOrExpelWindowLeft,
+    #[knuffel(skip)]
+    ConsumeOrExpelWindowLeftById(u64),
     ConsumeOrExpelWindowRight,
+    #[knuffel(skip)]
+    ConsumeOrExpelWindowRightById(u64),
+    SwapWindowLeft,
+    SwapWindowRight,
     ConsumeWindowIntoColumn,
     ExpelWindowFromColumn,
     CenterColumn,
+    ToggleColumnTabbedDisplay,
     FocusWorkspaceDown,
     FocusWorkspaceUp,
-    FocusWorkspace(#[knuffel(argument)] u8),
+    FocusWorkspace(#[knuffel(argument)] WorkspaceReference),
     FocusWorkspacePrevious,
     MoveWindowToWorkspaceDown,
     MoveWindowToWorkspaceUp,
-    MoveWindowToWorkspace(#[knuffel(argument)] u8),
+    MoveWindowToWorkspace(#[knuffel(argument)] WorkspaceReference),
+    #[knuffel(skip)]
+    MoveWindowToWorkspaceById {
+        window_id: u64,
+        reference: WorkspaceReference,
+    },
     MoveColumnToWorkspaceDown,
     MoveColumnToWorkspaceUp,
-    MoveColumnToWorkspace(#[knuffel(argument)] u8),
+    MoveColumnToWorkspace(#[knuffel(argument)] WorkspaceReference),
     MoveWorkspaceDown,
     MoveWorkspaceUp,
+    SetWorkspaceLayout(#[knuffel(argument, str)] LayoutOverride),
+    ToggleOverview,
     FocusMonitorLeft,
     FocusMonitorRight,
     FocusMonitorDown,
     FocusMonitorUp,
     MoveWindowToMonitorLeft,
+    #[knuffel(skip)]
+    MoveWindowToMonitorLeftById(u64),
     MoveWindowToMonitorRight,
+    #[knuffel(skip)]
+    MoveWindowToMonitorRightById(u64),
     MoveWindowToMonitorDown,
+    #[knuffel(skip)]
+    MoveWindowToMonitorDownById(u64),
     MoveWindowToMonitorUp,
+    #[knuffel(skip)]
+    MoveWindowToMonitorUpById(u64),
     MoveColumnToMonitorLeft,
     MoveColumnToMonitorRight,
     MoveColumnToMonitorDown,
     MoveColumnToMonitorUp,
     SetWindowHeight(#[knuffel(argument, str)] SizeChange),
+    #[knuffel(skip)]
+    SetWindowHeightById {
+        id: u64,
+        change: SizeChange,
+    },
+    SwitchPresetWindowHeight,
+    #[knuffel(skip)]
+    SwitchPresetWindowHeightById(u64),
+    ResetWindowHeight,
+    #[knuffel(skip)]
+    ResetWindowHeightById(u64),
     SwitchPresetColumnWidth,
     MaximizeColumn,
     SetColumnWidth(#[knuffel(argument, str)] SizeChange),
+    #[knuffel(skip)]
+    SetColumnWidthById {
+        id: u64,
+        change: SizeChange,
+    },
     SwitchLayout(#[knuffel(argument, str)] LayoutSwitchTarget),
     ShowHotkeyOverlay,
     MoveWorkspaceToMonitorLeft,
     MoveWorkspaceToMonitorRight,
     MoveWorkspaceToMonitorDown,
     MoveWorkspaceToMonitorUp,
+    ToggleWindowFloating,
+    #[knuffel(skip)]
+    ToggleWindowFloatingById(u64),
+    SwitchFocusBetweenFloatingAndTiling,
+    MoveWindowToScratchpad,
+    #[knuffel(skip)]
+    MoveWindowToScratchpadById(u64),
+    ToggleScratchpad,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkspaceReference {
+    /// Index of the workspace on its monitor, starting from 1.
+    Index(u8),
+    /// Name of a named workspace.
+    Name(String),
+}
+
+impl From<niri_ipc::WorkspaceReferenceArg> for WorkspaceReference {
+    fn from(reference: niri_ipc::WorkspaceReferenceArg) -> Self {
+        match reference {
+            niri_ipc::WorkspaceReferenceArg::Index(index) => Self::Index(index),
+            niri_ipc::WorkspaceReferenceArg::Name(name) => Self::Name(name),
+        }
+    }
 }
 
 impl From<niri_ipc::Action> for Action {
@@ -808,65 +964,157 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::Screenshot => Self::Screenshot,
             niri_ipc::Action::ScreenshotScreen => Self::ScreenshotScreen,
             niri_ipc::Action::ScreenshotWindow => Self::ScreenshotWindow,
-            niri_ipc::Action::CloseWindow => Self::CloseWindow,
-            niri_ipc::Action::FullscreenWindow => Self::FullscreenWindow,
+            niri_ipc::Action::CloseWindow { id: None } => Self::CloseWindow,
+            niri_ipc::Action::CloseWindow { id: Some(id) } => Self::CloseWindowById(id),
+            niri_ipc::Action::FullscreenWindow { id: None } => Self::FullscreenWindow,
+            niri_ipc::Action::FullscreenWindow { id: Some(id) } => Self::FullscreenWindowById(id),
+            niri_ipc::Action::FocusWindow { id } => Self::FocusWindow(id),
+            niri_ipc::Action::FocusWindowPrevious => Self::FocusWindowPrevious,
+            niri_ipc::Action::WindowSwitcherNext => Self::WindowSwitcherNext,
+            niri_ipc::Action::WindowSwitcherPrevious => Self::WindowSwitcherPrevious,
             niri_ipc::Action::FocusColumnLeft => Self::FocusColumnLeft,
             niri_ipc::Action::FocusColumnRight => Self::FocusColumnRight,
             niri_ipc::Action::FocusColumnFirst => Self::FocusColumnFirst,
             niri_ipc::Action::FocusColumnLast => Self::FocusColumnLast,
+            niri_ipc::Action::FocusColumn { index } => Self::FocusColumn(index),
+            niri_ipc::Action::FocusColumnOrMonitorLeft => Self::FocusColumnOrMonitorLeft,
+            niri_ipc::Action::FocusColumnOrMonitorRight => Self::FocusColumnOrMonitorRight,
             niri_ipc::Action::FocusWindowDown => Self::FocusWindowDown,
             niri_ipc::Action::FocusWindowUp => Self::FocusWindowUp,
             niri_ipc::Action::FocusWindowOrWorkspaceDown => Self::FocusWindowOrWorkspaceDown,
             niri_ipc::Action::FocusWindowOrWorkspaceUp => Self::FocusWindowOrWorkspaceUp,
+            niri_ipc::Action::FocusWindowOrMonitorDown => Self::FocusWindowOrMonitorDown,
+            niri_ipc::Action::FocusWindowOrMonitorUp => Self::FocusWindowOrMonitorUp,
             niri_ipc::Action::MoveColumnLeft => Self::MoveColumnLeft,
             niri_ipc::Action::MoveColumnRight => Self::MoveColumnRight,
             niri_ipc::Action::MoveColumnToFirst => Self::MoveColumnToFirst,
             niri_ipc::Action::MoveColumnToLast => Self::MoveColumnToLast,
+            niri_ipc::Action::MoveColumnToIndex { index } => Self::MoveColumnToIndex(index),
+            niri_ipc::Action::MoveColumnLeftOrToMonitorLeft => Self::MoveColumnLeftOrToMonitorLeft,
+            niri_ipc::Action::MoveColumnRightOrToMonitorRight => {
+                Self::MoveColumnRightOrToMonitorRight
+            }
             niri_ipc::Action::MoveWindowDown => Self::MoveWindowDown,
             niri_ipc::Action::MoveWindowUp => Self::MoveWindowUp,
             niri_ipc::Action::MoveWindowDownOrToWorkspaceDown => {
                 Self::MoveWindowDownOrToWorkspaceDown
             }
             niri_ipc::Action::MoveWindowUpOrToWorkspaceUp => Self::MoveWindowUpOrToWorkspaceUp,
-            niri_ipc::Action::ConsumeOrExpelWindowLeft => Self::ConsumeOrExpelWindowLeft,
-            niri_ipc::Action::ConsumeOrExpelWindowRight => Self::ConsumeOrExpelWindowRight,
+            niri_ipc::Action::MoveWindowDownOrToMonitorDown => Self::MoveWindowDownOrToMonitorDown,
+            niri_ipc::Action::MoveWindowUpOrToMonitorUp => Self::MoveWindowUpOrToMonitorUp,
+            niri_ipc::Action::ConsumeOrExpelWindowLeft { id: None } => {
+                Self::ConsumeOrExpelWindowLeft
+            }
+            niri_ipc::Action::ConsumeOrExpelWindowLeft { id: Some(id) } => {
+                Self::ConsumeOrExpelWindowLeftById(id)
+            }
+            niri_ipc::Action::ConsumeOrExpelWindowRight { id: None } => {
+                Self::ConsumeOrExpelWindowRight
+            }
+            niri_ipc::Action::ConsumeOrExpelWindowRight { id: Some(id) } => {
+                Self::ConsumeOrExpelWindowRightById(id)
+            }
+            niri_ipc::Action::SwapWindowLeft => Self::SwapWindowLeft,
+            niri_ipc::Action::SwapWindowRight => Self::SwapWindowRight,
             niri_ipc::Action::ConsumeWindowIntoColumn => Self::ConsumeWindowIntoColumn,
             niri_ipc::Action::ExpelWindowFromColumn => Self::ExpelWindowFromColumn,
             niri_ipc::Action::CenterColumn => Self::CenterColumn,
+            niri_ipc::Action::ToggleColumnTabbedDisplay => Self::ToggleColumnTabbedDisplay,
             niri_ipc::Action::FocusWorkspaceDown => Self::FocusWorkspaceDown,
             niri_ipc::Action::FocusWorkspaceUp => Self::FocusWorkspaceUp,
-            niri_ipc::Action::FocusWorkspace { index } => Self::FocusWorkspace(index),
+            niri_ipc::Action::FocusWorkspace { reference } => {
+                Self::FocusWorkspace(WorkspaceReference::from(reference))
+            }
             niri_ipc::Action::FocusWorkspacePrevious => Self::FocusWorkspacePrevious,
             niri_ipc::Action::MoveWindowToWorkspaceDown => Self::MoveWindowToWorkspaceDown,
             niri_ipc::Action::MoveWindowToWorkspaceUp => Self::MoveWindowToWorkspaceUp,
-            niri_ipc::Action::MoveWindowToWorkspace { index } => Self::MoveWindowToWorkspace(index),
+            niri_ipc::Action::MoveWindowToWorkspace {
+                window_id: None,
+                reference,
+            } => Self::MoveWindowToWorkspace(WorkspaceReference::from(reference)),
+            niri_ipc::Action::MoveWindowToWorkspace {
+                window_id: Some(window_id),
+                reference,
+            } => Self::MoveWindowToWorkspaceById {
+                window_id,
+                reference: WorkspaceReference::from(reference),
+            },
             niri_ipc::Action::MoveColumnToWorkspaceDown => Self::MoveColumnToWorkspaceDown,
             niri_ipc::Action::MoveColumnToWorkspaceUp => Self::MoveColumnToWorkspaceUp,
-            niri_ipc::Action::MoveColumnToWorkspace { index } => Self::MoveColumnToWorkspace(index),
+            niri_ipc::Action::MoveColumnToWorkspace { reference } => {
+                Self::MoveColumnToWorkspace(WorkspaceReference::from(reference))
+            }
             niri_ipc::Action::MoveWorkspaceDown => Self::MoveWorkspaceDown,
             niri_ipc::Action::MoveWorkspaceUp => Self::MoveWorkspaceUp,
+            // niri checks the layout before converting IPC actions, so it always parses here.
+            niri_ipc::Action::SetWorkspaceLayout { layout } => {
+                Self::SetWorkspaceLayout(LayoutOverride::parse(&layout).unwrap_or_default())
+            }
+            niri_ipc::Action::ToggleOverview => Self::ToggleOverview,
             niri_ipc::Action::FocusMonitorLeft => Self::FocusMonitorLeft,
             niri_ipc::Action::FocusMonitorRight => Self::FocusMonitorRight,
             niri_ipc::Action::FocusMonitorDown => Self::FocusMonitorDown,
             niri_ipc::Action::FocusMonitorUp => Self::FocusMonitorUp,
-            niri_ipc::Action::MoveWindowToMonitorLeft => Self::MoveWindowToMonitorLeft,
-            niri_ipc::Action::MoveWindowToMonitorRight => Self::MoveWindowToMonitorRight,
-            niri_ipc::Action::MoveWindowToMonitorDown => Self::MoveWindowToMonitorDown,
-            niri_ipc::Action::MoveWindowToMonitorUp => Self::MoveWindowToMonitorUp,
+            niri_ipc::Action::MoveWindowToMonitorLeft { id: None } => Self::MoveWindowToMonitorLeft,
+            niri_ipc::Action::MoveWindowToMonitorLeft { id: Some(id) } => {
+                Self::MoveWindowToMonitorLeftById(id)
+            }
+            niri_ipc::Action::MoveWindowToMonitorRight { id: None } => {
+                Self::MoveWindowToMonitorRight
+            }
+            niri_ipc::Action::MoveWindowToMonitorRight { id: Some(id) } => {
+                Self::MoveWindowToMonitorRightById(id)
+            }
+            niri_ipc::Action::MoveWindowToMonitorDown { id: None } => Self::MoveWindowToMonitorDown,
+            niri_ipc::Action::MoveWindowToMonitorDown { id: Some(id) } => {
+                Self::MoveWindowToMonitorDownById(id)
+            }
+            niri_ipc::Action::MoveWindowToMonitorUp { id: None } => Self::MoveWindowToMonitorUp,
+            niri_ipc::Action::MoveWindowToMonitorUp { id: Some(id) } => {
+                Self::MoveWindowToMonitorUpById(id)
+            }
             niri_ipc::Action::MoveColumnToMonitorLeft => Self::MoveColumnToMonitorLeft,
             niri_ipc::Action::MoveColumnToMonitorRight => Self::MoveColumnToMonitorRight,
             niri_ipc::Action::MoveColumnToMonitorDown => Self::MoveColumnToMonitorDown,
             niri_ipc::Action::MoveColumnToMonitorUp => Self::MoveColumnToMonitorUp,
-            niri_ipc::Action::SetWindowHeight { change } => Self::SetWindowHeight(change),
+            niri_ipc::Action::SetWindowHeight { id: None, change } => Self::SetWindowHeight(change),
+            niri_ipc::Action::SetWindowHeight {
+                id: Some(id),
+                change,
+            } => Self::SetWindowHeightById { id, change },
+            niri_ipc::Action::SwitchPresetWindowHeight { id: None } => {
+                Self::SwitchPresetWindowHeight
+            }
+            niri_ipc::Action::SwitchPresetWindowHeight { id: Some(id) } => {
+                Self::SwitchPresetWindowHeightById(id)
+            }
+            niri_ipc::Action::ResetWindowHeight { id: None } => Self::ResetWindowHeight,
+            niri_ipc::Action::ResetWindowHeight { id: Some(id) } => Self::ResetWindowHeightById(id),
             niri_ipc::Action::SwitchPresetColumnWidth => Self::SwitchPresetColumnWidth,
             niri_ipc::Action::MaximizeColumn => Self::MaximizeColumn,
-            niri_ipc::Action::SetColumnWidth { change } => Self::SetColumnWidth(change),
+            niri_ipc::Action::SetColumnWidth { id: None, change } => Self::SetColumnWidth(change),
+            niri_ipc::Action::SetColumnWidth {
+                id: Some(id),
+                change,
+            } => Self::SetColumnWidthById { id, change },
             niri_ipc::Action::SwitchLayout { layout } => Self::SwitchLayout(layout),
             niri_ipc::Action::ShowHotkeyOverlay => Self::ShowHotkeyOverlay,
             niri_ipc::Action::MoveWorkspaceToMonitorLeft => Self::MoveWorkspaceToMonitorLeft,
             niri_ipc::Action::MoveWorkspaceToMonitorRight => Self::MoveWorkspaceToMonitorRight,
             niri_ipc::Action::MoveWorkspaceToMonitorDown => Self::MoveWorkspaceToMonitorDown,
             niri_ipc::Action::MoveWorkspaceToMonitorUp => Self::MoveWorkspaceToMonitorUp,
+            niri_ipc::Action::ToggleWindowFloating { id: None } => Self::ToggleWindowFloating,
+            niri_ipc::Action::ToggleWindowFloating { id: Some(id) } => {
+                Self::ToggleWindowFloatingById(id)
+            }
+            niri_ipc::Action::SwitchFocusBetweenFloatingAndTiling => {
+                Self::SwitchFocusBetweenFloatingAndTiling
+            }
+            niri_ipc::Action::MoveWindowToScratchpad { id: None } => Self::MoveWindowToScratchpad,
+            niri_ipc::Action::MoveWindowToScratchpad { id: Some(id) } => {
+                Self::MoveWindowToScratchpadById(id)
+            }
+            niri_ipc::Action::ToggleScratchpad => Self::ToggleScratchpad,
             niri_ipc::Action::ToggleDebugTint => Self::ToggleDebugTint,
         }
     }
@@ -1037,6 +1285,48 @@ where
     }
 }
 
+// Manual impl to accept both a workspace index and a workspace name.
+impl<S> knuffel::traits::DecodeScalar<S> for WorkspaceReference
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn type_check(
+        type_name: &Option<knuffel::span::Spanned<knuffel::ast::TypeName, S>>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) {
+        if let Some(type_name) = &type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+    }
+
+    fn raw_decode(
+        val: &knuffel::span::Spanned<knuffel::ast::Literal, S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        match &**val {
+            knuffel::ast::Literal::String(s) => Ok(Self::Name(s.to_string())),
+            knuffel::ast::Literal::Int(value) => match value.try_into() {
+                Ok(index) => Ok(Self::Index(index)),
+                Err(e) => {
+                    ctx.emit_error(DecodeError::conversion(val, e));
+                    Ok(Self::Index(0))
+                }
+            },
+            _ => {
+                ctx.emit_error(DecodeError::unsupported(
+                    val,
+                    "expected a workspace index or a workspace name",
+                ));
+                Ok(Self::Index(0))
+            }
+        }
+    }
+}
+
 fn expect_only_children<S>(
     node: &knuffel::ast::SpannedNode<S>,
     ctx: &mut knuffel::decode::Context<S>,
@@ -1088,7 +1378,7 @@ where
                     "expected no more than one child",
                 ));
             }
-            PresetWidth::decode_node(child, ctx).map(Some).map(Self)
+            PresetSize::decode_node(child, ctx).map(Some).map(Self)
         } else {
             Ok(Self(None))
         }
@@ -1467,6 +1757,22 @@ where
     }
 }
 
+impl FromStr for LayoutOverride {
+    type Err = miette::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).map_err(|err| {
+            // knuffel keeps the actual problems as related diagnostics.
+            let problems = miette::Diagnostic::related(&err)
+                .into_iter()
+                .flatten()
+                .map(|problem| problem.to_string())
+                .collect::<Vec<_>>();
+            miette!("error parsing layout: {}", problems.join("; "))
+        })
+    }
+}
+
 impl FromStr for Mode {
     type Err = miette::Error;
 
@@ -1606,6 +1912,49 @@ pub fn set_miette_hook() -> Result<(), miette::InstallError> {
     miette::set_hook(Box::new(|_| Box::new(NarratableReportHandler::new())))
 }
 
+/// Converts a config error into a list of problems along with their locations.
+pub fn diagnostics(error: &dyn miette::Diagnostic) -> Vec<niri_ipc::ConfigDiagnostic> {
+    // knuffel reports every problem as a related diagnostic, and keeps the source code in the
+    // top-level error.
+    let source_code = error.source_code();
+    let make = |diagnostic: &dyn miette::Diagnostic, message: String| {
+        let location = diagnostic
+            .labels()
+            .and_then(|mut labels| labels.next())
+            .and_then(|label| {
+                let source_code = diagnostic.source_code().or(source_code)?;
+                source_code.read_span(label.inner(), 0, 0).ok()
+            });
+
+        niri_ipc::ConfigDiagnostic {
+            file: location
+                .as_ref()
+                .and_then(|contents| contents.name())
+                .map(String::from),
+            line: location.as_ref().map(|contents| contents.line() + 1),
+            column: location.as_ref().map(|contents| contents.column() + 1),
+            message,
+        }
+    };
+
+    match error.related() {
+        Some(related) => related
+            .map(|diagnostic| make(diagnostic, diagnostic.to_string()))
+            .collect(),
+        None => {
+            // Not a parsing error (e.g. the file could not be read), so include all the causes.
+            let mut message = error.to_string();
+            let mut source = error.source();
+            while let Some(err) = source {
+                message.push_str(": ");
+                message.push_str(&err.to_string());
+                source = err.source();
+            }
+            vec![make(error, message)]
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1677,6 +2026,14 @@ mod tests {
                 transform "flipped-90"
                 position x=10 y=20
                 mode "1920x1080@144"
+
+                layout {
+                    gaps 0
+                    center-focused-column "always"
+                    preset-column-widths {
+                        proportion 0.5
+                    }
+                }
             }
 
             layout {
@@ -1701,6 +2058,11 @@ mod tests {
 
                 default-column-width { proportion 0.25; }
 
+                preset-window-heights {
+                    proportion 0.25
+                    fixed 640
+                }
+
                 gaps 8
 
                 struts {
@@ -1753,8 +2115,21 @@ mod tests {
                 exclude is-active=true is-focused=false
 
                 open-on-output "eDP-1"
+                open-on-workspace "chat"
                 open-maximized true
                 open-fullscreen false
+                open-floating true
+            }
+
+            workspace "chat" {
+                open-on-output "eDP-1"
+            }
+
+            workspace "music" {
+                layout {
+                    center-focused-column "always"
+                    default-column-width { proportion 0.5; }
+                }
             }
 
             binds {
@@ -1764,6 +2139,7 @@ mod tests {
                 Mod+Ctrl+Shift+L { move-window-to-monitor-right; }
                 Mod+Comma { consume-window-into-column; }
                 Mod+1 { focus-workspace 1; }
+                Mod+2 { focus-workspace "chat"; }
                 Mod+Shift+E { quit skip-confirmation=true; }
                 Mod+WheelScrollDown cooldown-ms=150 { focus-workspace-down; }
             }
@@ -1826,6 +2202,12 @@ mod tests {
                         height: 1080,
                         refresh: Some(144.),
                     }),
+                    layout: Some(LayoutOverride {
+                        preset_column_widths: Some(vec![PresetSize::Proportion(0.5)]),
+                        center_focused_column: Some(CenterFocusedColumn::Always),
+                        gaps: Some(0),
+                        ..Default::default()
+                    }),
                 }],
                 layout: Layout {
                     focus_ring: FocusRing {
@@ -1870,14 +2252,18 @@ mod tests {
                         inactive_gradient: None,
                     },
                     preset_column_widths: vec![
-                        PresetWidth::Proportion(0.25),
-                        PresetWidth::Proportion(0.5),
-                        PresetWidth::Fixed(960),
-                        PresetWidth::Fixed(1280),
+                        PresetSize::Proportion(0.25),
+                        PresetSize::Proportion(0.5),
+                        PresetSize::Fixed(960),
+                        PresetSize::Fixed(1280),
                     ],
-                    default_column_width: Some(DefaultColumnWidth(Some(PresetWidth::Proportion(
+                    default_column_width: Some(DefaultColumnWidth(Some(PresetSize::Proportion(
                         0.25,
                     )))),
+                    preset_window_heights: vec![
+                        PresetSize::Proportion(0.25),
+                        PresetSize::Fixed(640),
+                    ],
                     gaps: 8,
                     struts: Struts {
                         left: 1,
@@ -1954,10 +2340,30 @@ mod tests {
                         },
                     ],
                     open_on_output: Some("eDP-1".to_owned()),
+                    open_on_workspace: Some(WorkspaceReference::Name(String::from("chat"))),
                     open_maximized: Some(true),
                     open_fullscreen: Some(false),
+                    open_floating: Some(true),
                     ..Default::default()
                 }],
+                workspaces: vec![
+                    Workspace {
+                        name: String::from("chat"),
+                        open_on_output: Some(String::from("eDP-1")),
+                        layout: None,
+                    },
+                    Workspace {
+                        name: String::from("music"),
+                        open_on_output: None,
+                        layout: Some(LayoutOverride {
+                            default_column_width: Some(DefaultColumnWidth(Some(
+                                PresetSize::Proportion(0.5),
+                            ))),
+                            center_focused_column: Some(CenterFocusedColumn::Always),
+                            ..Default::default()
+                        }),
+                    },
+                ],
                 binds: Binds(vec![
                     Bind {
                         key: Key {
@@ -2004,7 +2410,17 @@ mod tests {
                             trigger: Trigger::Keysym(Keysym::_1),
                             modifiers: Modifiers::COMPOSITOR,
                         },
-                        action: Action::FocusWorkspace(1),
+                        action: Action::FocusWorkspace(WorkspaceReference::Index(1)),
+                        cooldown: None,
+                    },
+                    Bind {
+                        key: Key {
+                            trigger: Trigger::Keysym(Keysym::_2),
+                            modifiers: Modifiers::COMPOSITOR,
+                        },
+                        action: Action::FocusWorkspace(WorkspaceReference::Name(String::from(
+                            "chat",
+                        ))),
                         cooldown: None,
                     },
                     Bind {
@@ -2094,6 +2510,56 @@ mod tests {
         assert!("10% ".parse::<SizeChange>().is_err());
     }
 
+    #[test]
+    fn parse_layout_override() {
+        assert_eq!(
+            LayoutOverride::parse("").unwrap(),
+            LayoutOverride::default()
+        );
+        assert_eq!(
+            LayoutOverride::parse(r#"gaps 0; center-focused-column "always""#).unwrap(),
+            LayoutOverride {
+                center_focused_column: Some(CenterFocusedColumn::Always),
+                gaps: Some(0),
+                ..Default::default()
+            },
+        );
+
+        assert!(LayoutOverride::parse("gaps -1").is_err());
+        assert!(LayoutOverride::parse("focus-ring { off; }").is_err());
+    }
+
+    #[test]
+    fn parse_set_workspace_layout_bind() {
+        let config = Config::parse(
+            "test.kdl",
+            r#"
+            binds {
+                Mod+C { set-workspace-layout "gaps 0"; }
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.binds.0[0].action,
+            Action::SetWorkspaceLayout(LayoutOverride {
+                gaps: Some(0),
+                ..Default::default()
+            }),
+        );
+
+        // An invalid layout fails the config load rather than the key press.
+        assert!(Config::parse(
+            "test.kdl",
+            r#"
+            binds {
+                Mod+C { set-workspace-layout "gaps -1"; }
+            }
+            "#,
+        )
+        .is_err());
+    }
+
     #[test]
     fn parse_iso_level3_shift() {
         assert_eq!(
@@ -2111,4 +2577,13 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn diagnostics_point_at_problem() {
+        let err = Config::parse("test.kdl", "input {\n    unknown-node\n}\n").unwrap_err();
+        let diagnostics = diagnostics(&err);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].file.as_deref(), Some("test.kdl"));
+        assert_eq!(diagnostics[0].line, Some(2));
+    }
 }
@@ -2,6 +2,7 @@
 #![warn(missing_docs)]
 
 use std::collections::HashMap;
+use std::fmt;
 use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
@@ -10,37 +11,139 @@ use serde::{Deserialize, Serialize};
 pub const SOCKET_PATH_ENV: &str = "NIRI_SOCKET";
 
 /// Request from client to niri.
+///
+/// Requests are sent as JSON terminated by a newline. A client can send any number of requests
+/// over the same connection, one after another; niri replies to each of them in order.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum Request {
+    /// Request the version string for the running niri instance.
+    ///
+    /// Clients can send this first to check that they are compatible with the compositor.
+    Version,
     /// Request information about connected outputs.
     Outputs,
     /// Request information about the focused window.
     FocusedWindow,
+    /// Request information about workspaces.
+    Workspaces,
+    /// Request information about all open windows.
+    Windows,
+    /// Request information about the configured keyboard layouts.
+    KeyboardLayouts,
     /// Perform an action.
     Action(Action),
+    /// Change output configuration temporarily.
+    ///
+    /// The configuration is changed temporarily and not saved into the config file. If the output
+    /// configuration in the config file changes, those changes will override the changes made
+    /// here.
+    Output {
+        /// Output name.
+        output: String,
+        /// Configuration to apply.
+        action: OutputAction,
+    },
+    /// Perform several actions in order, all at once.
+    ///
+    /// The actions are applied together before niri renders the next frame, so intermediate
+    /// states are never shown on screen, and any animations start from the state before the
+    /// first action.
+    Batch(Vec<Action>),
+    /// Reload the config file.
+    ///
+    /// If the config is invalid, niri keeps the current config and replies with
+    /// [`Error::InvalidConfig`].
+    ReloadConfig,
+    /// Check a config without applying it.
+    ///
+    /// The config is parsed by the running niri, so this also catches options that it does not
+    /// support yet.
+    ValidateConfig {
+        /// Text of the config to check.
+        text: String,
+    },
+    /// Start continuously receiving events from the compositor.
+    ///
+    /// The compositor will reply with `Reply::Ok(Response::Handled)`, then keep the connection
+    /// open and send [`Event`]s, one per line.
+    ///
+    /// The stream starts with events describing the current state: all existing workspaces and
+    /// windows, the active workspaces, the focused window, the outputs and the keyboard layouts.
+    ///
+    /// Once the event stream starts, niri no longer reads requests from this connection.
+    EventStream,
 }
 
 /// Reply from niri to client.
 ///
-/// Every request gets one reply.
+/// Every request gets one reply. Replies are terminated by a newline.
 ///
-/// * If an error had occurred, it will be an `Reply::Err`.
+/// * If an error had occurred, it will be an `Reply::Err` with the [`Error`].
 /// * If the request does not need any particular response, it will be
 ///   `Reply::Ok(Response::Handled)`. Kind of like an `Ok(())`.
 /// * Otherwise, it will be `Reply::Ok(response)` with one of the other [`Response`] variants.
-pub type Reply = Result<Response, String>;
+pub type Reply = Result<Response, Error>;
+
+/// Error from niri to client.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The request is not valid JSON.
+    MalformedRequest(String),
+    /// The request is valid JSON, but this niri version does not support it.
+    ///
+    /// This usually means that the client is newer than the running compositor. Contains the
+    /// parsing error.
+    UnsupportedRequest(String),
+    /// The config is invalid.
+    InvalidConfig(Vec<ConfigDiagnostic>),
+    /// The request was understood, but niri could not handle it.
+    Failed(String),
+}
+
+/// Problem found in a niri config.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ConfigDiagnostic {
+    /// Name of the file containing the problem, if known.
+    pub file: Option<String>,
+    /// Line of the problem, starting from 1.
+    ///
+    /// `None` if the problem does not refer to a particular place in the file.
+    pub line: Option<usize>,
+    /// Column of the problem, starting from 1.
+    ///
+    /// `None` if the problem does not refer to a particular place in the file.
+    pub column: Option<usize>,
+    /// Description of the problem.
+    pub message: String,
+}
 
 /// Successful response from niri to client.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum Response {
     /// A request that does not need a response was handled successfully.
     Handled,
+    /// The version string for the running niri instance.
+    Version(String),
     /// Information about connected outputs.
     ///
     /// Map from connector name to output info.
     Outputs(HashMap<String, Output>),
     /// Information about the focused window.
     FocusedWindow(Option<Window>),
+    /// Information about workspaces.
+    ///
+    /// Sorted by output name, then by index on the output.
+    Workspaces(Vec<Workspace>),
+    /// Information about all open windows.
+    ///
+    /// Sorted by ID.
+    Windows(Vec<Window>),
+    /// Information about the keyboard layouts.
+    KeyboardLayouts(KeyboardLayouts),
+    /// Problems found in the config.
+    ///
+    /// Empty if the config is valid.
+    ConfigDiagnostics(Vec<ConfigDiagnostic>),
 }
 
 /// Actions that niri can perform.
@@ -71,10 +174,37 @@ pub enum Action {
     ScreenshotScreen,
     /// Screenshot the focused window.
     ScreenshotWindow,
-    /// Close the focused window.
-    CloseWindow,
-    /// Toggle fullscreen on the focused window.
-    FullscreenWindow,
+    /// Close a window.
+    CloseWindow {
+        /// Id of the window to close.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Toggle fullscreen on a window.
+    FullscreenWindow {
+        /// Id of the window to toggle fullscreen of.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Focus a window by id.
+    FocusWindow {
+        /// Id of the window to focus.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: u64,
+    },
+    /// Focus the previously focused window.
+    FocusWindowPrevious,
+    /// Open the window switcher, or select the next window in it.
+    ///
+    /// The windows are ordered by recent use. The selected window is focused once the modifiers
+    /// held when opening the switcher are released.
+    WindowSwitcherNext,
+    /// Open the window switcher, or select the previous window in it.
+    WindowSwitcherPrevious,
     /// Focus the column to the left.
     FocusColumnLeft,
     /// Focus the column to the right.
@@ -83,6 +213,18 @@ pub enum Action {
     FocusColumnFirst,
     /// Focus the last column.
     FocusColumnLast,
+    /// Focus a column by index.
+    FocusColumn {
+        /// Index of the column to focus.
+        ///
+        /// The index starts from 1 for the first column.
+        #[cfg_attr(feature = "clap", arg())]
+        index: usize,
+    },
+    /// Focus the column or the monitor to the left.
+    FocusColumnOrMonitorLeft,
+    /// Focus the column or the monitor to the right.
+    FocusColumnOrMonitorRight,
     /// Focus the window below.
     FocusWindowDown,
     /// Focus the window above.
@@ -91,6 +233,10 @@ pub enum Action {
     FocusWindowOrWorkspaceDown,
     /// Focus the window or the workspace above.
     FocusWindowOrWorkspaceUp,
+    /// Focus the window or the monitor below.
+    FocusWindowOrMonitorDown,
+    /// Focus the window or the monitor above.
+    FocusWindowOrMonitorUp,
     /// Move the focused column to the left.
     MoveColumnLeft,
     /// Move the focused column to the right.
@@ -99,6 +245,18 @@ pub enum Action {
     MoveColumnToFirst,
     /// Move the focused column to the end of the workspace.
     MoveColumnToLast,
+    /// Move the focused column to a specific index on the workspace.
+    MoveColumnToIndex {
+        /// New index for the column.
+        ///
+        /// The index starts from 1 for the first column.
+        #[cfg_attr(feature = "clap", arg())]
+        index: usize,
+    },
+    /// Move the focused column to the left or to the monitor to the left.
+    MoveColumnLeftOrToMonitorLeft,
+    /// Move the focused column to the right or to the monitor to the right.
+    MoveColumnRightOrToMonitorRight,
     /// Move the focused window down in a column.
     MoveWindowDown,
     /// Move the focused window up in a column.
@@ -107,25 +265,47 @@ pub enum Action {
     MoveWindowDownOrToWorkspaceDown,
     /// Move the focused window up in a column or to the workspace above.
     MoveWindowUpOrToWorkspaceUp,
-    /// Consume or expel the focused window left.
-    ConsumeOrExpelWindowLeft,
-    /// Consume or expel the focused window right.
-    ConsumeOrExpelWindowRight,
+    /// Move the focused window down in a column or to the monitor below.
+    MoveWindowDownOrToMonitorDown,
+    /// Move the focused window up in a column or to the monitor above.
+    MoveWindowUpOrToMonitorUp,
+    /// Consume or expel a window left.
+    ConsumeOrExpelWindowLeft {
+        /// Id of the window to consume or expel.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Consume or expel a window right.
+    ConsumeOrExpelWindowRight {
+        /// Id of the window to consume or expel.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Swap the focused window with the active window of the column to the left.
+    SwapWindowLeft,
+    /// Swap the focused window with the active window of the column to the right.
+    SwapWindowRight,
     /// Consume the window to the right into the focused column.
     ConsumeWindowIntoColumn,
     /// Expel the focused window from the column.
     ExpelWindowFromColumn,
     /// Center the focused column on the screen.
     CenterColumn,
+    /// Toggle the focused column between normal and tabbed display.
+    ToggleColumnTabbedDisplay,
     /// Focus the workspace below.
     FocusWorkspaceDown,
     /// Focus the workspace above.
     FocusWorkspaceUp,
-    /// Focus a workspace by index.
+    /// Focus a workspace by reference (index or name).
     FocusWorkspace {
-        /// Index of the workspace to focus.
+        /// Reference (index or name) of the workspace to focus.
         #[cfg_attr(feature = "clap", arg())]
-        index: u8,
+        reference: WorkspaceReferenceArg,
     },
     /// Focus the previous workspace.
     FocusWorkspacePrevious,
@@ -133,26 +313,48 @@ pub enum Action {
     MoveWindowToWorkspaceDown,
     /// Move the focused window to the workspace above.
     MoveWindowToWorkspaceUp,
-    /// Move the focused window to a workspace by index.
+    /// Move a window to a workspace by reference (index or name).
     MoveWindowToWorkspace {
-        /// Index of the target workspace.
+        /// Id of the window to move.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        window_id: Option<u64>,
+
+        /// Reference (index or name) of the target workspace.
+        ///
+        /// An index is relative to the monitor that the window is on.
         #[cfg_attr(feature = "clap", arg())]
-        index: u8,
+        reference: WorkspaceReferenceArg,
     },
     /// Move the focused column to the workspace below.
     MoveColumnToWorkspaceDown,
     /// Move the focused column to the workspace above.
     MoveColumnToWorkspaceUp,
-    /// Move the focused column to a workspace by index.
+    /// Move the focused column to a workspace by reference (index or name).
     MoveColumnToWorkspace {
-        /// Index of the target workspace.
+        /// Reference (index or name) of the target workspace.
         #[cfg_attr(feature = "clap", arg())]
-        index: u8,
+        reference: WorkspaceReferenceArg,
     },
     /// Move the focused workspace down.
     MoveWorkspaceDown,
     /// Move the focused workspace up.
     MoveWorkspaceUp,
+    /// Override layout settings on the focused workspace.
+    ///
+    /// Replaces the overrides previously set with this action. Settings that aren't overridden
+    /// come from the config.
+    SetWorkspaceLayout {
+        /// Contents of a `layout` block from the config, e.g. `gaps 0; center-focused-column
+        /// "always"`.
+        ///
+        /// Pass an empty string to remove the overrides.
+        #[cfg_attr(feature = "clap", arg())]
+        layout: String,
+    },
+    /// Toggle the overview of all workspaces on the focused monitor.
+    ToggleOverview,
     /// Focus the monitor to the left.
     FocusMonitorLeft,
     /// Focus the monitor to the right.
@@ -161,14 +363,42 @@ pub enum Action {
     FocusMonitorDown,
     /// Focus the monitor above.
     FocusMonitorUp,
-    /// Move the focused window to the monitor to the left.
-    MoveWindowToMonitorLeft,
-    /// Move the focused window to the monitor to the right.
-    MoveWindowToMonitorRight,
-    /// Move the focused window to the monitor below.
-    MoveWindowToMonitorDown,
-    /// Move the focused window to the monitor above.
-    MoveWindowToMonitorUp,
+    /// Move a window to the monitor to the left.
+    MoveWindowToMonitorLeft {
+        /// Id of the window to move.
+        ///
+        /// If `None`, uses the focused window. The direction is relative to the monitor that the
+        /// window is on.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Move a window to the monitor to the right.
+    MoveWindowToMonitorRight {
+        /// Id of the window to move.
+        ///
+        /// If `None`, uses the focused window. The direction is relative to the monitor that the
+        /// window is on.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Move a window to the monitor below.
+    MoveWindowToMonitorDown {
+        /// Id of the window to move.
+        ///
+        /// If `None`, uses the focused window. The direction is relative to the monitor that the
+        /// window is on.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Move a window to the monitor above.
+    MoveWindowToMonitorUp {
+        /// Id of the window to move.
+        ///
+        /// If `None`, uses the focused window. The direction is relative to the monitor that the
+        /// window is on.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
     /// Move the focused column to the monitor to the left.
     MoveColumnToMonitorLeft,
     /// Move the focused column to the monitor to the right.
@@ -177,18 +407,46 @@ pub enum Action {
     MoveColumnToMonitorDown,
     /// Move the focused column to the monitor above.
     MoveColumnToMonitorUp,
-    /// Change the height of the focused window.
+    /// Change the height of a window.
     SetWindowHeight {
+        /// Id of the window whose height to set.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+
         /// How to change the height.
         #[cfg_attr(feature = "clap", arg())]
         change: SizeChange,
     },
+    /// Switch between preset window heights.
+    SwitchPresetWindowHeight {
+        /// Id of the window whose height to switch.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Reset the height of a window back to automatic.
+    ResetWindowHeight {
+        /// Id of the window whose height to reset.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
     /// Switch between preset column widths.
     SwitchPresetColumnWidth,
     /// Toggle the maximized state of the focused column.
     MaximizeColumn,
-    /// Change the width of the focused column.
+    /// Change the width of a column.
     SetColumnWidth {
+        /// Id of a window in the column whose width to set.
+        ///
+        /// If `None`, uses the focused column.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+
         /// How to change the width.
         #[cfg_attr(feature = "clap", arg())]
         change: SizeChange,
@@ -209,10 +467,115 @@ pub enum Action {
     MoveWorkspaceToMonitorDown,
     /// Move the focused workspace to the monitor above.
     MoveWorkspaceToMonitorUp,
+    /// Move a window between the floating and the tiling layout.
+    ToggleWindowFloating {
+        /// Id of the window to move.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Switch focus between the floating and the tiling layout.
+    SwitchFocusBetweenFloatingAndTiling,
+    /// Hide a window in the scratchpad.
+    MoveWindowToScratchpad {
+        /// Id of the window to hide.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Show the windows hidden in the scratchpad, or hide them again.
+    ToggleScratchpad,
     /// Toggle a debug tint on windows.
     ToggleDebugTint,
 }
 
+/// Output actions that niri can perform.
+// Variants in this enum should match the spelling of the ones in niri-config.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::Parser))]
+#[cfg_attr(feature = "clap", command(subcommand_value_name = "ACTION"))]
+#[cfg_attr(feature = "clap", command(subcommand_help_heading = "Actions"))]
+pub enum OutputAction {
+    /// Turn off the output.
+    Off,
+    /// Turn on the output.
+    On,
+    /// Set the output mode.
+    Mode {
+        /// Mode to set, or "auto" for automatic selection.
+        ///
+        /// Run `niri msg outputs` to see the available modes.
+        #[cfg_attr(feature = "clap", arg())]
+        mode: ModeToSet,
+    },
+    /// Set the output scale.
+    Scale {
+        /// Scale factor to set.
+        #[cfg_attr(feature = "clap", arg())]
+        scale: f64,
+    },
+    /// Set the output transform.
+    Transform {
+        /// Transform to set, counter-clockwise.
+        #[cfg_attr(feature = "clap", arg())]
+        transform: Transform,
+    },
+    /// Set the output position.
+    Position {
+        /// Position to set, or "auto" for automatic selection.
+        #[cfg_attr(feature = "clap", command(subcommand))]
+        position: PositionToSet,
+    },
+}
+
+/// Output mode to set.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum ModeToSet {
+    /// Niri will pick the mode automatically.
+    Automatic,
+    /// Specific mode.
+    Specific(ConfiguredMode),
+}
+
+/// Output mode as set in the config file.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ConfiguredMode {
+    /// Width in physical pixels.
+    pub width: u16,
+    /// Height in physical pixels.
+    pub height: u16,
+    /// Refresh rate.
+    pub refresh: Option<f64>,
+}
+
+/// Output position to set.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::Subcommand))]
+#[cfg_attr(feature = "clap", command(subcommand_value_name = "POSITION"))]
+#[cfg_attr(feature = "clap", command(subcommand_help_heading = "Position Values"))]
+pub enum PositionToSet {
+    /// Position the output automatically.
+    #[cfg_attr(feature = "clap", command(name = "auto"))]
+    Automatic,
+    /// Set a specific position.
+    #[cfg_attr(feature = "clap", command(name = "set"))]
+    Specific(ConfiguredPosition),
+}
+
+/// Output position as set in the config file.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+pub struct ConfiguredPosition {
+    /// Logical X position.
+    #[cfg_attr(feature = "clap", arg(allow_negative_numbers = true))]
+    pub x: i32,
+    /// Logical Y position.
+    #[cfg_attr(feature = "clap", arg(allow_negative_numbers = true))]
+    pub y: i32,
+}
+
 /// Change in window or column size.
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub enum SizeChange {
@@ -226,6 +589,15 @@ pub enum SizeChange {
     AdjustProportion(f64),
 }
 
+/// Workspace reference (index or name) to operate on.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum WorkspaceReferenceArg {
+    /// Index of the workspace on its monitor, starting from 1.
+    Index(u8),
+    /// Name of the workspace.
+    Name(String),
+}
+
 /// Layout to switch to.
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LayoutSwitchTarget {
@@ -233,6 +605,8 @@ pub enum LayoutSwitchTarget {
     Next,
     /// The previous configured layout.
     Prev,
+    /// The layout at the given index, starting from 0.
+    Index(u8),
 }
 
 /// Connected output.
@@ -313,12 +687,197 @@ pub enum Transform {
 }
 
 /// Toplevel window.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Window {
+    /// Unique ID of this window.
+    ///
+    /// The ID does not change while the window is open.
+    pub id: u64,
     /// Title, if set.
     pub title: Option<String>,
     /// Application ID, if set.
     pub app_id: Option<String>,
+    /// Process ID of the client that created this window, if known.
+    pub pid: Option<i32>,
+    /// ID of the workspace this window is on, if any.
+    ///
+    /// `None` while the window is being moved with the mouse or is hidden in the scratchpad.
+    pub workspace_id: Option<u64>,
+    /// Name of the output this window is on, if any.
+    ///
+    /// `None` while the window is hidden in the scratchpad.
+    pub output: Option<String>,
+    /// Index of the column containing this window, starting from 1.
+    ///
+    /// `None` if the window is not in a column.
+    pub column_idx: Option<usize>,
+    /// Index of this window within its column, starting from 1.
+    ///
+    /// `None` if the window is not in a column.
+    pub tile_idx: Option<usize>,
+    /// Size of the window in logical pixels, as (width, height).
+    ///
+    /// Size changes alone do not send [`Event::WindowChanged`], since they happen continuously
+    /// during resizes.
+    pub size: (i32, i32),
+    /// Whether this window has the keyboard focus.
+    pub is_focused: bool,
+    /// Whether this window is fullscreen.
+    pub is_fullscreen: bool,
+    /// Whether this window is in the floating layout.
+    pub is_floating: bool,
+    /// Whether this window requested attention.
+    pub is_urgent: bool,
+}
+
+/// Configured keyboard layouts.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct KeyboardLayouts {
+    /// XKB names of the configured layouts.
+    pub names: Vec<String>,
+    /// Index of the currently active layout in `names`.
+    pub current_idx: u8,
+}
+
+/// Workspace.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    /// Unique ID of this workspace.
+    ///
+    /// The ID does not change while the workspace exists, even if it moves to another output.
+    pub id: u64,
+    /// Index of the workspace on its output.
+    ///
+    /// This is the same index that [`Action::FocusWorkspace`] takes, so it starts from 1.
+    pub idx: u8,
+    /// Name of the workspace, if it is a named workspace from the config.
+    pub name: Option<String>,
+    /// Name of the output that the workspace is on.
+    ///
+    /// `None` if no outputs are connected.
+    pub output: Option<String>,
+    /// Whether the workspace is currently active on its output.
+    ///
+    /// Every output has one active workspace, the one that is currently visible on that output.
+    pub is_active: bool,
+    /// Whether the workspace is active on the focused output.
+    ///
+    /// At most one workspace is focused at a time.
+    pub is_focused: bool,
+}
+
+/// Event from the compositor, sent over an event stream.
+///
+/// See [`Request::EventStream`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum Event {
+    /// A new window was opened.
+    WindowOpened {
+        /// The new window.
+        window: Window,
+    },
+    /// Information about a window changed, for example its title or its position in the layout.
+    ///
+    /// Focus changes are sent separately as [`Event::WindowFocusChanged`].
+    WindowChanged {
+        /// The window with the updated information.
+        window: Window,
+    },
+    /// A window was closed.
+    WindowClosed {
+        /// ID of the closed window.
+        id: u64,
+    },
+    /// Keyboard focus moved to a different window.
+    WindowFocusChanged {
+        /// ID of the newly focused window, or `None` if no window is focused.
+        id: Option<u64>,
+    },
+    /// A new workspace was created.
+    WorkspaceCreated {
+        /// ID of the new workspace.
+        id: u64,
+        /// Name of the output that the workspace is on.
+        ///
+        /// `None` if no outputs are connected.
+        output: Option<String>,
+    },
+    /// A workspace was removed.
+    WorkspaceRemoved {
+        /// ID of the removed workspace.
+        id: u64,
+    },
+    /// A workspace moved to a different output.
+    WorkspaceOutputChanged {
+        /// ID of the workspace.
+        id: u64,
+        /// Name of the new output of the workspace.
+        ///
+        /// `None` if no outputs are connected.
+        output: Option<String>,
+    },
+    /// A workspace became active on its output.
+    WorkspaceActivated {
+        /// ID of the activated workspace.
+        id: u64,
+        /// Whether the workspace is also on the focused output.
+        focused: bool,
+    },
+    /// The configuration of the connected outputs changed.
+    OutputsChanged {
+        /// Map from connector name to output info.
+        outputs: HashMap<String, Output>,
+    },
+    /// The configured keyboard layouts changed, for example after a config reload.
+    KeyboardLayoutsChanged {
+        /// The new keyboard layout configuration.
+        keyboard_layouts: KeyboardLayouts,
+    },
+    /// The active keyboard layout switched.
+    ///
+    /// This also happens when niri switches the layout on focus change with `track-layout
+    /// "window"`.
+    KeyboardLayoutSwitched {
+        /// Index of the newly active layout.
+        idx: u8,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MalformedRequest(msg) => write!(f, "malformed request: {msg}"),
+            Error::UnsupportedRequest(msg) => write!(f, "unsupported request: {msg}"),
+            Error::InvalidConfig(diagnostics) => {
+                f.write_str("invalid config")?;
+                for diagnostic in diagnostics {
+                    write!(f, "\n{diagnostic}")?;
+                }
+                Ok(())
+            }
+            Error::Failed(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{file}:")?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{line}:")?;
+        }
+        if let Some(column) = self.column {
+            write!(f, "{column}:")?;
+        }
+        if self.file.is_some() || self.line.is_some() || self.column.is_some() {
+            f.write_str(" ")?;
+        }
+        f.write_str(&self.message)
+    }
 }
 
 impl FromStr for SizeChange {
@@ -361,6 +920,22 @@ impl FromStr for SizeChange {
     }
 }
 
+impl FromStr for WorkspaceReferenceArg {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("workspace reference is empty");
+        }
+
+        // Anything that parses as an index is an index, everything else is a name.
+        match s.parse() {
+            Ok(index) => Ok(Self::Index(index)),
+            Err(_) => Ok(Self::Name(s.to_string())),
+        }
+    }
+}
+
 impl FromStr for LayoutSwitchTarget {
     type Err = &'static str;
 
@@ -368,8 +943,52 @@ impl FromStr for LayoutSwitchTarget {
         match s {
             "next" => Ok(Self::Next),
             "prev" => Ok(Self::Prev),
-            _ => Err(r#"invalid layout action, can be "next" or "prev""#),
+            _ => s
+                .parse()
+                .map(Self::Index)
+                .map_err(|_| r#"invalid layout action, can be "next", "prev" or a layout index"#),
+        }
+    }
+}
+
+impl FromStr for ModeToSet {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(Self::Automatic);
         }
+
+        let mode = s.parse()?;
+        Ok(Self::Specific(mode))
+    }
+}
+
+impl FromStr for ConfiguredMode {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((width, rest)) = s.split_once('x') else {
+            return Err("no 'x' separator found");
+        };
+
+        let (height, refresh) = match rest.split_once('@') {
+            Some((height, refresh)) => (height, Some(refresh)),
+            None => (rest, None),
+        };
+
+        let width = width.parse().map_err(|_| "error parsing width")?;
+        let height = height.parse().map_err(|_| "error parsing height")?;
+        let refresh = refresh
+            .map(str::parse)
+            .transpose()
+            .map_err(|_| "error parsing refresh rate")?;
+
+        Ok(Self {
+            width,
+            height,
+            refresh,
+        })
     }
 }
 
@@ -2,7 +2,7 @@ use std::ffi::OsString;
 use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
-use niri_ipc::Action;
+use niri_ipc::{Action, OutputAction};
 
 use crate::utils::version;
 
@@ -52,13 +52,40 @@ pub enum Sub {
 
 #[derive(Subcommand)]
 pub enum Msg {
+    /// Print the version of the running niri instance.
+    Version,
     /// List connected outputs.
     Outputs,
     /// Print information about the focused window.
     FocusedWindow,
+    /// List workspaces.
+    Workspaces,
+    /// List open windows.
+    Windows,
+    /// List the configured keyboard layouts.
+    KeyboardLayouts,
     /// Perform an action.
     Action {
         #[command(subcommand)]
         action: Action,
     },
+    /// Change output configuration temporarily.
+    ///
+    /// The configuration is changed temporarily and not saved into the config file. If the output
+    /// configuration in the config file changes, those changes will override the changes made
+    /// here.
+    Output {
+        /// Output name.
+        ///
+        /// Run `niri msg outputs` to see the output names.
+        #[arg()]
+        output: String,
+        /// Configuration to apply.
+        #[command(subcommand)]
+        action: OutputAction,
+    },
+    /// Reload the config file.
+    ReloadConfig,
+    /// Start continuously receiving events from the compositor.
+    EventStream,
 }
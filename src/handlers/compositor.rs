@@ -126,10 +126,27 @@ impl CompositorHandler for State {
                             (ResolvedWindowRules::empty(), None, false, None)
                         };
 
+                    // Resolve the workspace from the window rules. A named workspace could have
+                    // moved to a different output since the initial configure, so look it up
+                    // again.
+                    let mut output = output;
+                    let mut workspace_idx = None;
+                    if let Some(reference) = rules.open_on_workspace.clone() {
+                        if let Some((ws_output, idx)) =
+                            self.niri.find_output_and_workspace_index(reference)
+                        {
+                            output = ws_output.or(output);
+                            workspace_idx = Some(idx);
+                        }
+                    }
+
                     let parent = window
                         .toplevel()
                         .expect("no x11 support")
                         .parent()
+                        // An explicit workspace from the window rules takes precedence over the
+                        // parent.
+                        .filter(|_| workspace_idx.is_none())
                         .and_then(|parent| self.niri.layout.find_window_and_output(&parent))
                         // Only consider the parent if we configured the window for the same
                         // output.
@@ -151,9 +168,13 @@ impl CompositorHandler for State {
                             .layout
                             .add_window_right_of(&p, mapped, width, is_full_width)
                     } else if let Some(output) = &output {
-                        self.niri
-                            .layout
-                            .add_window_on_output(output, mapped, width, is_full_width);
+                        self.niri.layout.add_window_on_output(
+                            output,
+                            workspace_idx,
+                            mapped,
+                            width,
+                            is_full_width,
+                        );
                         Some(output)
                     } else {
                         self.niri.layout.add_window(mapped, width, is_full_width)
@@ -220,6 +241,29 @@ impl CompositorHandler for State {
                 return;
             }
 
+            // This is a commit of a toplevel hidden in the scratchpad.
+            if let Some(mapped) = self.niri.layout.find_scratchpad_window(surface) {
+                let window = mapped.window.clone();
+                window.on_commit();
+
+                let is_mapped =
+                    with_renderer_surface_state(surface, |state| state.buffer().is_some())
+                        .unwrap_or_else(|| {
+                            error!("no renderer surface state even though we use commit handler");
+                            false
+                        });
+
+                if !is_mapped {
+                    // The toplevel got unmapped.
+                    self.niri.layout.remove_window(&window);
+
+                    let unmapped = Unmapped::new(window);
+                    self.niri.unmapped_windows.insert(surface.clone(), unmapped);
+                }
+
+                return;
+            }
+
             // This is a commit of a non-toplevel root.
         }
 
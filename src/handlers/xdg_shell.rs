@@ -1,3 +1,8 @@
+use std::cmp::min;
+use std::time::Duration;
+
+use niri_config::WorkspaceReference;
+
 use smithay::desktop::{
     find_popup_root_surface, get_popup_toplevel_coords, layer_map_for_output, LayerSurface,
     PopupKeyboardGrab, PopupKind, PopupManager, PopupPointerGrab, PopupUngrabStrategy, Window,
@@ -11,6 +16,7 @@ use smithay::reexports::wayland_protocols::xdg::shell::server::xdg_toplevel::{se
 use smithay::reexports::wayland_server::protocol::wl_output;
 use smithay::reexports::wayland_server::protocol::wl_seat::WlSeat;
 use smithay::reexports::wayland_server::protocol::wl_surface::WlSurface;
+use smithay::reexports::wayland_server::Resource;
 use smithay::utils::{Logical, Rectangle, Serial};
 use smithay::wayland::compositor::{send_surface_state, with_states};
 use smithay::wayland::input_method::InputMethodSeat;
@@ -21,15 +27,23 @@ use smithay::wayland::shell::xdg::{
     PopupSurface, PositionerState, ToplevelSurface, XdgPopupSurfaceData, XdgShellHandler,
     XdgShellState, XdgToplevelSurfaceData,
 };
+use smithay::wayland::xdg_activation::{
+    XdgActivationHandler, XdgActivationState, XdgActivationToken, XdgActivationTokenData,
+};
 use smithay::wayland::xdg_foreign::{XdgForeignHandler, XdgForeignState};
 use smithay::{
-    delegate_kde_decoration, delegate_xdg_decoration, delegate_xdg_foreign, delegate_xdg_shell,
+    delegate_kde_decoration, delegate_xdg_activation, delegate_xdg_decoration,
+    delegate_xdg_foreign, delegate_xdg_shell,
 };
 
+use crate::input::move_grab::MoveGrab;
+use crate::input::resize_grab::ResizeGrab;
 use crate::layout::workspace::ColumnWidth;
 use crate::niri::{PopupGrabState, State};
 use crate::window::{InitialConfigureState, ResolvedWindowRules, Unmapped, WindowRef};
 
+const XDG_ACTIVATION_TOKEN_TIMEOUT: Duration = Duration::from_secs(10);
+
 impl XdgShellHandler for State {
     fn xdg_shell_state(&mut self) -> &mut XdgShellState {
         &mut self.niri.xdg_shell_state
@@ -50,18 +64,89 @@ impl XdgShellHandler for State {
         }
     }
 
-    fn move_request(&mut self, _surface: ToplevelSurface, _seat: WlSeat, _serial: Serial) {
-        // FIXME
+    fn move_request(&mut self, surface: ToplevelSurface, _seat: WlSeat, serial: Serial) {
+        let pointer = self.niri.seat.get_pointer().unwrap();
+        if !pointer.has_grab(serial) {
+            return;
+        }
+
+        let Some(start_data) = pointer.grab_start_data() else {
+            return;
+        };
+
+        // The grab must have started on this window.
+        let wl_surface = surface.wl_surface();
+        let Some((focus, _)) = &start_data.focus else {
+            return;
+        };
+        if !focus.id().same_client_as(&wl_surface.id()) {
+            return;
+        }
+
+        let Some((mapped, _)) = self.niri.layout.find_window_and_output(wl_surface) else {
+            return;
+        };
+        let window = mapped.window.clone();
+
+        let location = pointer.current_location();
+        let Some((output, pos_within_output)) = self.niri.output_under(location) else {
+            return;
+        };
+        let output = output.clone();
+
+        if !self
+            .niri
+            .layout
+            .interactive_move_begin(window.clone(), &output, pos_within_output)
+        {
+            return;
+        }
+
+        let grab = MoveGrab::new(start_data, window);
+        pointer.set_grab(self, grab, serial, Focus::Clear);
     }
 
     fn resize_request(
         &mut self,
-        _surface: ToplevelSurface,
+        surface: ToplevelSurface,
         _seat: WlSeat,
-        _serial: Serial,
-        _edges: ResizeEdge,
+        serial: Serial,
+        edges: ResizeEdge,
     ) {
-        // FIXME
+        let pointer = self.niri.seat.get_pointer().unwrap();
+        if !pointer.has_grab(serial) {
+            return;
+        }
+
+        let Some(start_data) = pointer.grab_start_data() else {
+            return;
+        };
+
+        // The grab must have started on this window.
+        let wl_surface = surface.wl_surface();
+        let Some((focus, _)) = &start_data.focus else {
+            return;
+        };
+        if !focus.id().same_client_as(&wl_surface.id()) {
+            return;
+        }
+
+        let Some((mapped, _)) = self.niri.layout.find_window_and_output(wl_surface) else {
+            return;
+        };
+        let window = mapped.window.clone();
+
+        let edges = crate::utils::ResizeEdge::from(edges);
+        if !self
+            .niri
+            .layout
+            .interactive_resize_begin(window.clone(), edges)
+        {
+            return;
+        }
+
+        let grab = ResizeGrab::new(start_data, window);
+        pointer.set_grab(self, grab, serial, Focus::Clear);
     }
 
     fn reposition_request(
@@ -264,7 +349,7 @@ impl XdgShellHandler for State {
                         toplevel.with_pending_state(|state| {
                             state.states.set(xdg_toplevel::State::Fullscreen);
                         });
-                        ws.configure_new_window(&unmapped.window, None);
+                        ws.configure_new_window(&unmapped.window, None, false);
                     }
 
                     // We already sent the initial configure, so we need to reconfigure.
@@ -297,10 +382,10 @@ impl XdgShellHandler for State {
                     // The required configure will be the initial configure.
                 }
                 InitialConfigureState::Configured {
+                    rules,
                     width,
                     is_full_width,
                     output,
-                    ..
                 } => {
                     // Figure out the monitor following a similar logic to initial configure.
                     // FIXME: deduplicate.
@@ -344,7 +429,8 @@ impl XdgShellHandler for State {
                         } else {
                             *width
                         };
-                        ws.configure_new_window(&unmapped.window, configure_width);
+                        let is_floating = rules.open_floating == Some(true);
+                        ws.configure_new_window(&unmapped.window, configure_width, is_floating);
                     }
 
                     // We already sent the initial configure, so we need to reconfigure.
@@ -374,6 +460,17 @@ impl XdgShellHandler for State {
             .find_window_and_output(surface.wl_surface());
 
         let Some((mapped, output)) = win_out else {
+            if let Some(mapped) = self
+                .niri
+                .layout
+                .find_scratchpad_window(surface.wl_surface())
+            {
+                // A toplevel hidden in the scratchpad got destroyed.
+                let window = mapped.window.clone();
+                self.niri.layout.remove_window(&window);
+                return;
+            }
+
             // I have no idea how this can happen, but I saw it happen once, in a weird interaction
             // involving laptop going to sleep and resuming.
             error!("toplevel missing from both unmapped_windows and layout");
@@ -468,6 +565,38 @@ impl XdgForeignHandler for State {
 }
 delegate_xdg_foreign!(State);
 
+impl XdgActivationHandler for State {
+    fn activation_state(&mut self) -> &mut XdgActivationState {
+        &mut self.niri.activation_state
+    }
+
+    fn request_activation(
+        &mut self,
+        token: XdgActivationToken,
+        token_data: XdgActivationTokenData,
+        surface: WlSurface,
+    ) {
+        // Only let the window take the focus if the token was requested by the focused surface
+        // recently. Otherwise, mark the window as wanting attention.
+        let is_fresh = token_data.timestamp.elapsed() < XDG_ACTIVATION_TOKEN_TIMEOUT;
+        let from_focus = token_data.surface.is_some()
+            && token_data.surface.as_ref() == self.niri.keyboard_focus.surface();
+
+        if let Some((mapped, _)) = self.niri.layout.find_window_and_output_mut(&surface) {
+            if is_fresh && from_focus {
+                let window = mapped.window.clone();
+                self.niri.layout.activate_window(&window);
+            } else {
+                mapped.set_urgent(true);
+            }
+            self.niri.queue_redraw_all();
+        }
+
+        self.niri.activation_state.remove_token(&token);
+    }
+}
+delegate_xdg_activation!(State);
+
 fn initial_configure_sent(toplevel: &ToplevelSurface) -> bool {
     with_states(toplevel.wl_surface(), |states| {
         states
@@ -500,12 +629,24 @@ impl State {
             return;
         };
 
-        // Pick the target monitor. First, check if we had an output set in the window rules.
-        let mon = rules
-            .open_on_output
-            .as_deref()
-            .and_then(|name| self.niri.output_by_name.get(name))
-            .and_then(|o| self.niri.layout.monitor_for_output(o));
+        // Pick the target monitor. First, check if the window rules put it on a named workspace.
+        let mon = match &rules.open_on_workspace {
+            Some(WorkspaceReference::Name(name)) => self
+                .niri
+                .layout
+                .find_workspace_by_name(name)
+                .and_then(|(mon, _, _)| mon),
+            _ => None,
+        };
+
+        // If not, check if we had an output set in the window rules.
+        let mon = mon.or_else(|| {
+            rules
+                .open_on_output
+                .as_deref()
+                .and_then(|name| self.niri.output_by_name.get(name))
+                .and_then(|o| self.niri.layout.monitor_for_output(o))
+        });
 
         // If not, check if the window requested one for fullscreen.
         let mon = mon.or_else(|| {
@@ -516,10 +657,12 @@ impl State {
                 .and_then(|o| self.niri.layout.monitor_for_output(o))
         });
 
-        // If not, check if this is a dialog with a parent, to place it next to the parent.
+        // If not, check if this is a dialog with a parent, to place it next to the parent. An
+        // explicit workspace from the window rules takes precedence over the parent.
         let mon = mon.map(|mon| (mon, false)).or_else(|| {
             toplevel
                 .parent()
+                .filter(|_| rules.open_on_workspace.is_none())
                 .and_then(|parent| self.niri.layout.find_window_and_output(&parent))
                 .map(|(_win, output)| output)
                 .and_then(|o| self.niri.layout.monitor_for_output(o))
@@ -541,12 +684,25 @@ impl State {
             .map(|(mon, _)| mon.output.clone());
         let mon = mon.map(|(mon, _)| mon);
 
+        // Pick the target workspace on the monitor, if we had one set in the window rules.
+        let ws = mon.and_then(|mon| {
+            let idx = match rules.open_on_workspace.as_ref()? {
+                WorkspaceReference::Index(index) => {
+                    min(index.saturating_sub(1) as usize, mon.workspaces.len() - 1)
+                }
+                WorkspaceReference::Name(name) => {
+                    mon.workspaces.iter().position(|ws| ws.has_name(name))?
+                }
+            };
+            Some(&mon.workspaces[idx])
+        });
+
         let mut width = None;
         let is_full_width = rules.open_maximized.unwrap_or(false);
 
         // Tell the surface the preferred size and bounds for its likely output.
-        let ws = mon
-            .map(|mon| mon.active_workspace_ref())
+        let ws = ws
+            .or_else(|| mon.map(|mon| mon.active_workspace_ref()))
             .or_else(|| self.niri.layout.active_workspace());
 
         if let Some(ws) = ws {
@@ -566,7 +722,8 @@ impl State {
             } else {
                 width
             };
-            ws.configure_new_window(window, configure_width);
+            let is_floating = rules.open_floating == Some(true);
+            ws.configure_new_window(window, configure_width, is_floating);
         }
 
         // If the user prefers no CSD, it's a reasonable assumption that they would prefer to get
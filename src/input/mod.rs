@@ -10,26 +10,42 @@ use niri_ipc::LayoutSwitchTarget;
 use smithay::backend::input::{
     AbsolutePositionEvent, Axis, AxisSource, ButtonState, Device, DeviceCapability, Event,
     GestureBeginEvent, GestureEndEvent, GesturePinchUpdateEvent as _, GestureSwipeUpdateEvent as _,
-    InputBackend, InputEvent, KeyState, KeyboardKeyEvent, PointerAxisEvent, PointerButtonEvent,
-    PointerMotionEvent, ProximityState, TabletToolButtonEvent, TabletToolEvent,
+    InputBackend, InputEvent, KeyState, KeyboardKeyEvent, MouseButton, PointerAxisEvent,
+    PointerButtonEvent, PointerMotionEvent, ProximityState, TabletToolButtonEvent, TabletToolEvent,
     TabletToolProximityEvent, TabletToolTipEvent, TabletToolTipState, TouchEvent,
 };
 use smithay::backend::libinput::LibinputInputBackend;
-use smithay::input::keyboard::{keysyms, FilterResult, Keysym, ModifiersState};
+use smithay::desktop::Window;
+use smithay::input::keyboard::{
+    keysyms, FilterResult, Keysym, Layout as KeyboardLayout, ModifiersState,
+};
 use smithay::input::pointer::{
-    AxisFrame, ButtonEvent, CursorImageStatus, GestureHoldBeginEvent, GestureHoldEndEvent,
+    AxisFrame, ButtonEvent, CursorImageStatus, Focus, GestureHoldBeginEvent, GestureHoldEndEvent,
     GesturePinchBeginEvent, GesturePinchEndEvent, GesturePinchUpdateEvent, GestureSwipeBeginEvent,
-    GestureSwipeEndEvent, GestureSwipeUpdateEvent, MotionEvent, RelativeMotionEvent,
+    GestureSwipeEndEvent, GestureSwipeUpdateEvent, GrabStartData as PointerGrabStartData,
+    MotionEvent, RelativeMotionEvent,
 };
 use smithay::input::touch::{DownEvent, MotionEvent as TouchMotionEvent, UpEvent};
+use smithay::output::Output;
 use smithay::utils::{Logical, Point, SERIAL_COUNTER};
 use smithay::wayland::pointer_constraints::{with_pointer_constraint, PointerConstraint};
 use smithay::wayland::tablet_manager::{TabletDescriptor, TabletSeatTrait};
 
+use self::move_grab::MoveGrab;
+use self::resize_grab::ResizeGrab;
 use crate::niri::State;
 use crate::ui::screenshot_ui::ScreenshotUi;
 use crate::utils::spawning::spawn;
-use crate::utils::{center, get_monotonic_time};
+use crate::utils::{center, get_monotonic_time, ResizeEdge};
+
+pub mod move_grab;
+pub mod resize_grab;
+
+/// Pinch scale below which a three-finger pinch opens the overview.
+const OVERVIEW_PINCH_IN_SCALE: f64 = 0.8;
+
+/// Pinch scale above which a three-finger pinch closes the overview.
+const OVERVIEW_PINCH_OUT_SCALE: f64 = 1.2;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CompositorMod {
@@ -251,7 +267,9 @@ impl State {
         let time = Event::time_msec(&event);
         let pressed = event.state() == KeyState::Pressed;
 
-        let Some(Some(bind)) = self.niri.seat.get_keyboard().unwrap().input(
+        let keyboard = self.niri.seat.get_keyboard().unwrap();
+        let mut switcher_key = None;
+        let bind = keyboard.input(
             self,
             event.key_code(),
             event.state(),
@@ -263,6 +281,16 @@ impl State {
                 let modified = keysym.modified_sym();
                 let raw = keysym.raw_latin_sym_or_raw_current_sym();
 
+                // While the window switcher is open, Escape cancels it and Return confirms it.
+                if this.niri.window_switcher.is_open()
+                    && pressed
+                    && (raw == Some(Keysym::Escape) || raw == Some(Keysym::Return))
+                {
+                    this.niri.suppressed_keys.insert(key_code);
+                    switcher_key = raw;
+                    return FilterResult::Intercept(None);
+                }
+
                 if let Some(dialog) = &this.niri.exit_confirm_dialog {
                     if dialog.is_open() && pressed && raw == Some(Keysym::Return) {
                         info!("quitting after confirming exit dialog");
@@ -270,7 +298,7 @@ impl State {
                     }
                 }
 
-                should_intercept_key(
+                let res = should_intercept_key(
                     &mut this.niri.suppressed_keys,
                     bindings,
                     comp_mod,
@@ -281,9 +309,43 @@ impl State {
                     *mods,
                     &this.niri.screenshot_ui,
                     this.niri.config.borrow().input.disable_power_key_handling,
-                )
+                );
+
+                // Unbound Escape and Return close the overview, keeping the picked focus.
+                if matches!(res, FilterResult::Forward)
+                    && pressed
+                    && (raw == Some(Keysym::Escape) || raw == Some(Keysym::Return))
+                    && this.niri.layout.is_overview_open()
+                {
+                    this.niri.suppressed_keys.insert(key_code);
+                    return FilterResult::Intercept(Some(Bind {
+                        key: Key {
+                            trigger: Trigger::Keysym(raw.unwrap()),
+                            modifiers: Modifiers::empty(),
+                        },
+                        action: Action::ToggleOverview,
+                        cooldown: None,
+                    }));
+                }
+
+                res
             },
-        ) else {
+        );
+
+        if switcher_key == Some(Keysym::Escape) {
+            self.niri.window_switcher.close();
+            // FIXME: granular
+            self.niri.queue_redraw_all();
+        } else if switcher_key == Some(Keysym::Return)
+            || self
+                .niri
+                .window_switcher
+                .should_confirm(modifiers_from_state(keyboard.modifier_state()))
+        {
+            self.confirm_window_switcher();
+        }
+
+        let Some(Some(bind)) = bind else {
             return;
         };
 
@@ -328,6 +390,39 @@ impl State {
         }
     }
 
+    fn focus_window(&mut self, window: &Window) {
+        self.niri.layout.activate_window(window);
+        self.maybe_warp_cursor_to_focus();
+        // FIXME: granular
+        self.niri.queue_redraw_all();
+    }
+
+    fn open_window_switcher(&mut self, backwards: bool) {
+        let windows = self.niri.windows_by_recent_use();
+        // The first window is usually the focused one, so start on the one used before it.
+        let selected_idx = if backwards {
+            windows.len().saturating_sub(1)
+        } else {
+            1
+        };
+        let keyboard = self.niri.seat.get_keyboard().unwrap();
+        let mods = modifiers_from_state(keyboard.modifier_state());
+        self.niri.window_switcher.open(windows, selected_idx, mods);
+    }
+
+    fn confirm_window_switcher(&mut self) {
+        let Some(id) = self.niri.window_switcher.close() else {
+            return;
+        };
+
+        if let Some((window, _)) = self.niri.find_window_by_id(u64::from(id.get())) {
+            self.focus_window(&window);
+        } else {
+            // FIXME: granular
+            self.niri.queue_redraw_all();
+        }
+    }
+
     pub fn do_action(&mut self, action: Action) {
         if self.niri.is_locked() && !allowed_when_locked(&action) {
             return;
@@ -428,6 +523,11 @@ impl State {
                     mapped.toplevel().send_close();
                 }
             }
+            Action::CloseWindowById(id) => {
+                if let Some((window, _)) = self.niri.find_window_by_id(id) {
+                    window.toplevel().expect("no X11 support").send_close();
+                }
+            }
             Action::FullscreenWindow => {
                 let focus = self.niri.layout.focus().map(|m| m.window.clone());
                 if let Some(window) = focus {
@@ -436,12 +536,61 @@ impl State {
                     self.niri.queue_redraw_all();
                 }
             }
+            Action::FullscreenWindowById(id) => {
+                if let Some((window, _)) = self.niri.find_window_by_id(id) {
+                    self.niri.layout.toggle_fullscreen(&window);
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::FocusWindow(id) => {
+                if let Some((window, _)) = self.niri.find_window_by_id(id) {
+                    self.focus_window(&window);
+                }
+            }
+            Action::FocusWindowPrevious => {
+                let focus = self.niri.layout.focus().map(|mapped| mapped.id());
+                let previous = self
+                    .niri
+                    .windows_by_recent_use()
+                    .into_iter()
+                    .find(|id| Some(*id) != focus);
+                if let Some(id) = previous {
+                    if let Some((window, _)) = self.niri.find_window_by_id(u64::from(id.get())) {
+                        self.focus_window(&window);
+                    }
+                }
+            }
+            Action::WindowSwitcherNext => {
+                if self.niri.window_switcher.is_open() {
+                    self.niri.window_switcher.select_next();
+                } else {
+                    self.open_window_switcher(false);
+                }
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::WindowSwitcherPrevious => {
+                if self.niri.window_switcher.is_open() {
+                    self.niri.window_switcher.select_previous();
+                } else {
+                    self.open_window_switcher(true);
+                }
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::SwitchLayout(action) => {
                 self.niri.seat.get_keyboard().unwrap().with_xkb_state(
                     self,
                     |mut state| match action {
                         LayoutSwitchTarget::Next => state.cycle_next_layout(),
                         LayoutSwitchTarget::Prev => state.cycle_prev_layout(),
+                        LayoutSwitchTarget::Index(idx) => {
+                            let idx = u32::from(idx);
+                            if state.layouts().any(|layout| layout.0 == idx) {
+                                state.set_layout(KeyboardLayout(idx));
+                            }
+                        }
                     },
                 );
             }
@@ -469,6 +618,44 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::MoveColumnToIndex(index) => {
+                self.niri.layout.move_column_to_index(index);
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::MoveColumnLeftOrToMonitorLeft => {
+                if let Some(output) = self.niri.output_left() {
+                    if self.niri.layout.move_column_left_or_to_output(&output) {
+                        if !self.maybe_warp_cursor_to_focus_centered() {
+                            self.move_cursor_to_output(&output);
+                        }
+                    } else {
+                        self.maybe_warp_cursor_to_focus();
+                    }
+                } else {
+                    self.niri.layout.move_left();
+                    self.maybe_warp_cursor_to_focus();
+                }
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::MoveColumnRightOrToMonitorRight => {
+                if let Some(output) = self.niri.output_right() {
+                    if self.niri.layout.move_column_right_or_to_output(&output) {
+                        if !self.maybe_warp_cursor_to_focus_centered() {
+                            self.move_cursor_to_output(&output);
+                        }
+                    } else {
+                        self.maybe_warp_cursor_to_focus();
+                    }
+                } else {
+                    self.niri.layout.move_right();
+                    self.maybe_warp_cursor_to_focus();
+                }
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::MoveWindowDown => {
                 self.niri.layout.move_down();
                 self.maybe_warp_cursor_to_focus();
@@ -493,14 +680,76 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::MoveWindowDownOrToMonitorDown => {
+                if let Some(output) = self.niri.output_down() {
+                    if self.niri.layout.move_down_or_to_output(&output) {
+                        if !self.maybe_warp_cursor_to_focus_centered() {
+                            self.move_cursor_to_output(&output);
+                        }
+                    } else {
+                        self.maybe_warp_cursor_to_focus();
+                    }
+                } else {
+                    self.niri.layout.move_down();
+                    self.maybe_warp_cursor_to_focus();
+                }
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::MoveWindowUpOrToMonitorUp => {
+                if let Some(output) = self.niri.output_up() {
+                    if self.niri.layout.move_up_or_to_output(&output) {
+                        if !self.maybe_warp_cursor_to_focus_centered() {
+                            self.move_cursor_to_output(&output);
+                        }
+                    } else {
+                        self.maybe_warp_cursor_to_focus();
+                    }
+                } else {
+                    self.niri.layout.move_up();
+                    self.maybe_warp_cursor_to_focus();
+                }
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::ConsumeOrExpelWindowLeft => {
-                self.niri.layout.consume_or_expel_window_left();
+                self.niri.layout.consume_or_expel_window_left(None);
                 self.maybe_warp_cursor_to_focus();
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::ConsumeOrExpelWindowLeftById(id) => {
+                if let Some((window, _)) = self.niri.find_window_by_id(id) {
+                    self.niri.layout.consume_or_expel_window_left(Some(&window));
+                    self.maybe_warp_cursor_to_focus();
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
             Action::ConsumeOrExpelWindowRight => {
-                self.niri.layout.consume_or_expel_window_right();
+                self.niri.layout.consume_or_expel_window_right(None);
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::ConsumeOrExpelWindowRightById(id) => {
+                if let Some((window, _)) = self.niri.find_window_by_id(id) {
+                    self.niri
+                        .layout
+                        .consume_or_expel_window_right(Some(&window));
+                    self.maybe_warp_cursor_to_focus();
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::SwapWindowLeft => {
+                self.niri.layout.swap_window_left();
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::SwapWindowRight => {
+                self.niri.layout.swap_window_right();
                 self.maybe_warp_cursor_to_focus();
                 // FIXME: granular
                 self.niri.queue_redraw_all();
@@ -529,6 +778,44 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::FocusColumn(index) => {
+                self.niri.layout.focus_column(index);
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::FocusColumnOrMonitorLeft => {
+                if let Some(output) = self.niri.output_left() {
+                    if self.niri.layout.focus_column_left_or_output(&output) {
+                        if !self.maybe_warp_cursor_to_focus_centered() {
+                            self.move_cursor_to_output(&output);
+                        }
+                    } else {
+                        self.maybe_warp_cursor_to_focus();
+                    }
+                } else {
+                    self.niri.layout.focus_left();
+                    self.maybe_warp_cursor_to_focus();
+                }
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::FocusColumnOrMonitorRight => {
+                if let Some(output) = self.niri.output_right() {
+                    if self.niri.layout.focus_column_right_or_output(&output) {
+                        if !self.maybe_warp_cursor_to_focus_centered() {
+                            self.move_cursor_to_output(&output);
+                        }
+                    } else {
+                        self.maybe_warp_cursor_to_focus();
+                    }
+                } else {
+                    self.niri.layout.focus_right();
+                    self.maybe_warp_cursor_to_focus();
+                }
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::FocusWindowDown => {
                 self.niri.layout.focus_down();
                 self.maybe_warp_cursor_to_focus();
@@ -553,6 +840,38 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::FocusWindowOrMonitorDown => {
+                if let Some(output) = self.niri.output_down() {
+                    if self.niri.layout.focus_window_down_or_output(&output) {
+                        if !self.maybe_warp_cursor_to_focus_centered() {
+                            self.move_cursor_to_output(&output);
+                        }
+                    } else {
+                        self.maybe_warp_cursor_to_focus();
+                    }
+                } else {
+                    self.niri.layout.focus_down();
+                    self.maybe_warp_cursor_to_focus();
+                }
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::FocusWindowOrMonitorUp => {
+                if let Some(output) = self.niri.output_up() {
+                    if self.niri.layout.focus_window_up_or_output(&output) {
+                        if !self.maybe_warp_cursor_to_focus_centered() {
+                            self.move_cursor_to_output(&output);
+                        }
+                    } else {
+                        self.maybe_warp_cursor_to_focus();
+                    }
+                } else {
+                    self.niri.layout.focus_up();
+                    self.maybe_warp_cursor_to_focus();
+                }
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::MoveWindowToWorkspaceDown => {
                 self.niri.layout.move_to_workspace_down();
                 self.maybe_warp_cursor_to_focus();
@@ -565,12 +884,53 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
-            Action::MoveWindowToWorkspace(idx) => {
-                let idx = idx.saturating_sub(1) as usize;
-                self.niri.layout.move_to_workspace(idx);
-                self.maybe_warp_cursor_to_focus();
-                // FIXME: granular
-                self.niri.queue_redraw_all();
+            Action::MoveWindowToWorkspace(reference) => {
+                if let Some((mut output, index)) =
+                    self.niri.find_output_and_workspace_index(reference)
+                {
+                    // Named workspaces on the current output are handled like indices.
+                    if output.as_ref() == self.niri.layout.active_output() {
+                        output = None;
+                    }
+
+                    if let Some(output) = output {
+                        self.niri.layout.move_to_output(None, &output, Some(index));
+                        self.niri.layout.focus_output(&output);
+                        if !self.maybe_warp_cursor_to_focus_centered() {
+                            self.move_cursor_to_output(&output);
+                        }
+                    } else {
+                        self.niri.layout.move_to_workspace(None, index);
+                        self.maybe_warp_cursor_to_focus();
+                    }
+
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::MoveWindowToWorkspaceById {
+                window_id,
+                reference,
+            } => {
+                if let Some((window, current)) = self.niri.find_window_by_id(window_id) {
+                    if let Some((mut output, index)) =
+                        self.niri.find_output_and_workspace_index(reference)
+                    {
+                        // Named workspaces on the window's output are handled like indices.
+                        if output == current {
+                            output = None;
+                        }
+
+                        if let Some(output) = output {
+                            self.move_window_to_output(&window, &output, Some(index));
+                        } else {
+                            self.niri.layout.move_to_workspace(Some(&window), index);
+                            self.maybe_warp_cursor_to_focus();
+                            // FIXME: granular
+                            self.niri.queue_redraw_all();
+                        }
+                    }
+                }
             }
             Action::MoveColumnToWorkspaceDown => {
                 self.niri.layout.move_column_to_workspace_down();
@@ -584,12 +944,29 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
-            Action::MoveColumnToWorkspace(idx) => {
-                let idx = idx.saturating_sub(1) as usize;
-                self.niri.layout.move_column_to_workspace(idx);
-                self.maybe_warp_cursor_to_focus();
-                // FIXME: granular
-                self.niri.queue_redraw_all();
+            Action::MoveColumnToWorkspace(reference) => {
+                if let Some((mut output, index)) =
+                    self.niri.find_output_and_workspace_index(reference)
+                {
+                    // Named workspaces on the current output are handled like indices.
+                    if output.as_ref() == self.niri.layout.active_output() {
+                        output = None;
+                    }
+
+                    if let Some(output) = output {
+                        self.niri.layout.move_column_to_output(&output, Some(index));
+                        self.niri.layout.focus_output(&output);
+                        if !self.maybe_warp_cursor_to_focus_centered() {
+                            self.move_cursor_to_output(&output);
+                        }
+                    } else {
+                        self.niri.layout.move_column_to_workspace(index);
+                        self.maybe_warp_cursor_to_focus();
+                    }
+
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
             }
             Action::FocusWorkspaceDown => {
                 self.niri.layout.switch_workspace_down();
@@ -603,19 +980,35 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
-            Action::FocusWorkspace(idx) => {
-                let idx = idx.saturating_sub(1) as usize;
+            Action::FocusWorkspace(reference) => {
+                if let Some((mut output, index)) =
+                    self.niri.find_output_and_workspace_index(reference)
+                {
+                    // Named workspaces on the current output are handled like indices.
+                    if output.as_ref() == self.niri.layout.active_output() {
+                        output = None;
+                    }
 
-                let config = &self.niri.config;
-                if config.borrow().input.workspace_auto_back_and_forth {
-                    self.niri.layout.switch_workspace_auto_back_and_forth(idx);
-                } else {
-                    self.niri.layout.switch_workspace(idx);
-                }
+                    if let Some(output) = output {
+                        self.niri.layout.focus_output(&output);
+                        self.niri.layout.switch_workspace(index);
+                        if !self.maybe_warp_cursor_to_focus_centered() {
+                            self.move_cursor_to_output(&output);
+                        }
+                    } else {
+                        let config = &self.niri.config;
+                        if config.borrow().input.workspace_auto_back_and_forth {
+                            self.niri.layout.switch_workspace_auto_back_and_forth(index);
+                        } else {
+                            self.niri.layout.switch_workspace(index);
+                        }
 
-                self.maybe_warp_cursor_to_focus();
-                // FIXME: granular
-                self.niri.queue_redraw_all();
+                        self.maybe_warp_cursor_to_focus();
+                    }
+
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
             }
             Action::FocusWorkspacePrevious => {
                 self.niri.layout.switch_workspace_previous();
@@ -632,6 +1025,16 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::SetWorkspaceLayout(layout) => {
+                self.niri.layout.set_workspace_layout(layout);
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::ToggleOverview => {
+                self.niri.layout.toggle_overview();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::ConsumeWindowIntoColumn => {
                 self.niri.layout.consume_into_column();
                 // This does not cause immediate focus or window size change, so warping mouse to
@@ -653,6 +1056,11 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::ToggleColumnTabbedDisplay => {
+                self.niri.layout.toggle_column_tabbed_display();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::MaximizeColumn => {
                 self.niri.layout.toggle_full_width();
             }
@@ -690,43 +1098,71 @@ impl State {
             }
             Action::MoveWindowToMonitorLeft => {
                 if let Some(output) = self.niri.output_left() {
-                    self.niri.layout.move_to_output(&output);
+                    self.niri.layout.move_to_output(None, &output, None);
                     self.niri.layout.focus_output(&output);
                     if !self.maybe_warp_cursor_to_focus_centered() {
                         self.move_cursor_to_output(&output);
                     }
                 }
             }
+            Action::MoveWindowToMonitorLeftById(id) => {
+                if let Some((window, Some(current))) = self.niri.find_window_by_id(id) {
+                    if let Some(output) = self.niri.output_left_of(&current) {
+                        self.move_window_to_output(&window, &output, None);
+                    }
+                }
+            }
             Action::MoveWindowToMonitorRight => {
                 if let Some(output) = self.niri.output_right() {
-                    self.niri.layout.move_to_output(&output);
+                    self.niri.layout.move_to_output(None, &output, None);
                     self.niri.layout.focus_output(&output);
                     if !self.maybe_warp_cursor_to_focus_centered() {
                         self.move_cursor_to_output(&output);
                     }
                 }
             }
+            Action::MoveWindowToMonitorRightById(id) => {
+                if let Some((window, Some(current))) = self.niri.find_window_by_id(id) {
+                    if let Some(output) = self.niri.output_right_of(&current) {
+                        self.move_window_to_output(&window, &output, None);
+                    }
+                }
+            }
             Action::MoveWindowToMonitorDown => {
                 if let Some(output) = self.niri.output_down() {
-                    self.niri.layout.move_to_output(&output);
+                    self.niri.layout.move_to_output(None, &output, None);
                     self.niri.layout.focus_output(&output);
                     if !self.maybe_warp_cursor_to_focus_centered() {
                         self.move_cursor_to_output(&output);
                     }
                 }
             }
+            Action::MoveWindowToMonitorDownById(id) => {
+                if let Some((window, Some(current))) = self.niri.find_window_by_id(id) {
+                    if let Some(output) = self.niri.output_down_of(&current) {
+                        self.move_window_to_output(&window, &output, None);
+                    }
+                }
+            }
             Action::MoveWindowToMonitorUp => {
                 if let Some(output) = self.niri.output_up() {
-                    self.niri.layout.move_to_output(&output);
+                    self.niri.layout.move_to_output(None, &output, None);
                     self.niri.layout.focus_output(&output);
                     if !self.maybe_warp_cursor_to_focus_centered() {
                         self.move_cursor_to_output(&output);
                     }
                 }
             }
+            Action::MoveWindowToMonitorUpById(id) => {
+                if let Some((window, Some(current))) = self.niri.find_window_by_id(id) {
+                    if let Some(output) = self.niri.output_up_of(&current) {
+                        self.move_window_to_output(&window, &output, None);
+                    }
+                }
+            }
             Action::MoveColumnToMonitorLeft => {
                 if let Some(output) = self.niri.output_left() {
-                    self.niri.layout.move_column_to_output(&output);
+                    self.niri.layout.move_column_to_output(&output, None);
                     self.niri.layout.focus_output(&output);
                     if !self.maybe_warp_cursor_to_focus_centered() {
                         self.move_cursor_to_output(&output);
@@ -735,7 +1171,7 @@ impl State {
             }
             Action::MoveColumnToMonitorRight => {
                 if let Some(output) = self.niri.output_right() {
-                    self.niri.layout.move_column_to_output(&output);
+                    self.niri.layout.move_column_to_output(&output, None);
                     self.niri.layout.focus_output(&output);
                     if !self.maybe_warp_cursor_to_focus_centered() {
                         self.move_cursor_to_output(&output);
@@ -744,7 +1180,7 @@ impl State {
             }
             Action::MoveColumnToMonitorDown => {
                 if let Some(output) = self.niri.output_down() {
-                    self.niri.layout.move_column_to_output(&output);
+                    self.niri.layout.move_column_to_output(&output, None);
                     self.niri.layout.focus_output(&output);
                     if !self.maybe_warp_cursor_to_focus_centered() {
                         self.move_cursor_to_output(&output);
@@ -753,7 +1189,7 @@ impl State {
             }
             Action::MoveColumnToMonitorUp => {
                 if let Some(output) = self.niri.output_up() {
-                    self.niri.layout.move_column_to_output(&output);
+                    self.niri.layout.move_column_to_output(&output, None);
                     self.niri.layout.focus_output(&output);
                     if !self.maybe_warp_cursor_to_focus_centered() {
                         self.move_cursor_to_output(&output);
@@ -761,10 +1197,36 @@ impl State {
                 }
             }
             Action::SetColumnWidth(change) => {
-                self.niri.layout.set_column_width(change);
+                self.niri.layout.set_column_width(None, change);
+            }
+            Action::SetColumnWidthById { id, change } => {
+                if let Some((window, _)) = self.niri.find_window_by_id(id) {
+                    self.niri.layout.set_column_width(Some(&window), change);
+                }
             }
             Action::SetWindowHeight(change) => {
-                self.niri.layout.set_window_height(change);
+                self.niri.layout.set_window_height(None, change);
+            }
+            Action::SetWindowHeightById { id, change } => {
+                if let Some((window, _)) = self.niri.find_window_by_id(id) {
+                    self.niri.layout.set_window_height(Some(&window), change);
+                }
+            }
+            Action::SwitchPresetWindowHeight => {
+                self.niri.layout.toggle_window_height(None);
+            }
+            Action::SwitchPresetWindowHeightById(id) => {
+                if let Some((window, _)) = self.niri.find_window_by_id(id) {
+                    self.niri.layout.toggle_window_height(Some(&window));
+                }
+            }
+            Action::ResetWindowHeight => {
+                self.niri.layout.reset_window_height(None);
+            }
+            Action::ResetWindowHeightById(id) => {
+                if let Some((window, _)) = self.niri.find_window_by_id(id) {
+                    self.niri.layout.reset_window_height(Some(&window));
+                }
             }
             Action::ShowHotkeyOverlay => {
                 if self.niri.hotkey_overlay.show() {
@@ -803,9 +1265,77 @@ impl State {
                     }
                 }
             }
+            Action::ToggleWindowFloating => {
+                self.niri.layout.toggle_window_floating(None);
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::ToggleWindowFloatingById(id) => {
+                if let Some((window, _)) = self.niri.find_window_by_id(id) {
+                    self.niri.layout.toggle_window_floating(Some(&window));
+                    self.maybe_warp_cursor_to_focus();
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::SwitchFocusBetweenFloatingAndTiling => {
+                self.niri.layout.switch_focus_floating_tiling();
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::MoveWindowToScratchpad => {
+                self.niri.layout.move_to_scratchpad(None);
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::MoveWindowToScratchpadById(id) => {
+                if let Some((window, _)) = self.niri.find_window_by_id(id) {
+                    self.niri.layout.move_to_scratchpad(Some(&window));
+                    self.maybe_warp_cursor_to_focus();
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::ToggleScratchpad => {
+                self.niri.layout.toggle_scratchpad();
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
         }
     }
 
+    fn move_window_to_output(
+        &mut self,
+        window: &Window,
+        output: &Output,
+        target_ws_idx: Option<usize>,
+    ) {
+        let was_focused = self
+            .niri
+            .layout
+            .focus()
+            .is_some_and(|mapped| &mapped.window == window);
+
+        self.niri
+            .layout
+            .move_to_output(Some(window), output, target_ws_idx);
+
+        // Follow the window only if it was focused.
+        if was_focused {
+            self.niri.layout.focus_output(output);
+            if !self.maybe_warp_cursor_to_focus_centered() {
+                self.move_cursor_to_output(output);
+            }
+        }
+
+        // FIXME: granular
+        self.niri.queue_redraw_all();
+    }
+
     fn on_pointer_motion<I: InputBackend>(&mut self, event: I::PointerMotionEvent) {
         // We need an output to be able to move the pointer.
         if self.niri.global_space.outputs().next().is_none() {
@@ -1053,10 +1583,79 @@ impl State {
         let button_state = event.state();
 
         if ButtonState::Pressed == button_state {
-            if let Some(mapped) = self.niri.window_under_cursor() {
+            let overview_output = self.niri.output_under_cursor().filter(|output| {
+                self.niri
+                    .layout
+                    .monitor_for_output(output)
+                    .is_some_and(|mon| mon.is_overview_open())
+            });
+
+            if let Some(output) = overview_output {
+                // Clicking in the overview picks a window or a workspace and closes the overview.
+                let location = pointer.current_location();
+                let output_geo = self.niri.global_space.output_geometry(&output).unwrap();
+                let pos_within_output = location - output_geo.loc.to_f64();
+                self.niri
+                    .layout
+                    .pick_in_overview(&output, pos_within_output);
+
+                // FIXME: granular.
+                self.niri.queue_redraw_all();
+            } else if let Some(mapped) = self.niri.window_under_cursor() {
                 let window = mapped.window.clone();
                 self.niri.layout.activate_window(&window);
 
+                // Mod+left-drag moves the window, Mod+right-drag resizes it.
+                let mods = self.niri.seat.get_keyboard().unwrap().modifier_state();
+                let mod_down = match self.backend.mod_key() {
+                    CompositorMod::Super => mods.logo,
+                    CompositorMod::Alt => mods.alt,
+                };
+
+                if mod_down && !pointer.is_grabbed() {
+                    let location = pointer.current_location();
+                    let (output, pos_within_output) = self.niri.output_under(location).unwrap();
+                    let output = output.clone();
+
+                    let start_data = PointerGrabStartData {
+                        focus: None,
+                        button,
+                        location,
+                    };
+
+                    match event.button() {
+                        Some(MouseButton::Left) => {
+                            if self.niri.layout.interactive_move_begin(
+                                window.clone(),
+                                &output,
+                                pos_within_output,
+                            ) {
+                                let grab = MoveGrab::new(start_data, window);
+                                pointer.set_grab(self, grab, serial, Focus::Clear);
+                            }
+                        }
+                        Some(MouseButton::Right) => {
+                            let edges = self
+                                .niri
+                                .layout
+                                .monitor_for_output(&output)
+                                .and_then(|mon| mon.active_workspace_ref().tile_rect(&window))
+                                .map(|rect| ResizeEdge::for_pos_in_rect(pos_within_output, rect))
+                                .unwrap_or(ResizeEdge::BOTTOM_RIGHT);
+
+                            if self
+                                .niri
+                                .layout
+                                .interactive_resize_begin(window.clone(), edges)
+                            {
+                                let grab = ResizeGrab::new(start_data, window);
+                                pointer.set_grab(self, grab, serial, Focus::Clear);
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+
                 // FIXME: granular.
                 self.niri.queue_redraw_all();
             } else if let Some(output) = self.niri.output_under_cursor() {
@@ -1588,6 +2187,13 @@ impl State {
     }
 
     fn on_gesture_pinch_begin<I: InputBackend>(&mut self, event: I::GesturePinchBeginEvent) {
+        if event.fingers() == 3 {
+            self.niri.gesture_pinch_3f_active = true;
+
+            // We handled this event.
+            return;
+        }
+
         let serial = SERIAL_COUNTER.next_serial();
         let pointer = self.niri.seat.get_pointer().unwrap();
 
@@ -1606,6 +2212,28 @@ impl State {
     }
 
     fn on_gesture_pinch_update<I: InputBackend>(&mut self, event: I::GesturePinchUpdateEvent) {
+        if self.niri.gesture_pinch_3f_active {
+            // Pinching in opens the overview, pinching out closes it.
+            let scale = event.scale();
+            let open = if scale <= OVERVIEW_PINCH_IN_SCALE {
+                Some(true)
+            } else if scale >= OVERVIEW_PINCH_OUT_SCALE {
+                Some(false)
+            } else {
+                None
+            };
+
+            if let Some(open) = open {
+                if let Some(output) = self.niri.output_under_cursor() {
+                    self.niri.layout.set_overview_open(&output, open);
+                    self.niri.queue_redraw(&output);
+                }
+            }
+
+            // We handled this event.
+            return;
+        }
+
         let pointer = self.niri.seat.get_pointer().unwrap();
 
         if self.update_pointer_focus() {
@@ -1624,6 +2252,13 @@ impl State {
     }
 
     fn on_gesture_pinch_end<I: InputBackend>(&mut self, event: I::GesturePinchEndEvent) {
+        if self.niri.gesture_pinch_3f_active {
+            self.niri.gesture_pinch_3f_active = false;
+
+            // We handled this event.
+            return;
+        }
+
         let serial = SERIAL_COUNTER.next_serial();
         let pointer = self.niri.seat.get_pointer().unwrap();
 
@@ -1,12 +1,13 @@
 use std::env;
-use std::io::{Read, Write};
-use std::net::Shutdown;
+use std::ffi::OsStr;
+use std::io::{BufRead, BufReader, Write};
 use std::os::unix::net::UnixStream;
 
-use anyhow::{anyhow, bail, Context};
-use niri_ipc::{LogicalOutput, Mode, Output, Reply, Request, Response};
+use anyhow::{bail, Context};
+use niri_ipc::{Event, LogicalOutput, Mode, Output, Reply, Request, Response};
 
 use crate::cli::Msg;
+use crate::utils::version;
 
 pub fn handle_msg(msg: Msg, json: bool) -> anyhow::Result<()> {
     let socket_path = env::var_os(niri_ipc::SOCKET_PATH_ENV).with_context(|| {
@@ -16,32 +17,66 @@ pub fn handle_msg(msg: Msg, json: bool) -> anyhow::Result<()> {
         )
     })?;
 
-    let mut stream =
-        UnixStream::connect(socket_path).context("error connecting to {socket_path}")?;
-
     let request = match &msg {
+        Msg::Version => Request::Version,
         Msg::Outputs => Request::Outputs,
         Msg::FocusedWindow => Request::FocusedWindow,
+        Msg::Workspaces => Request::Workspaces,
+        Msg::Windows => Request::Windows,
+        Msg::KeyboardLayouts => Request::KeyboardLayouts,
         Msg::Action { action } => Request::Action(action.clone()),
+        Msg::Output { output, action } => Request::Output {
+            output: output.clone(),
+            action: action.clone(),
+        },
+        Msg::ReloadConfig => Request::ReloadConfig,
+        Msg::EventStream => Request::EventStream,
     };
-    let mut buf = serde_json::to_vec(&request).unwrap();
-    stream
-        .write_all(&buf)
-        .context("error writing IPC request")?;
-    stream
-        .shutdown(Shutdown::Write)
-        .context("error closing IPC stream for writing")?;
 
-    buf.clear();
-    stream
-        .read_to_end(&mut buf)
+    // Check the compositor version first, so that a newer niri msg running against an older
+    // compositor (or the other way around) can say so.
+    let client_version = version();
+    let compositor_version = compositor_version(&socket_path)?;
+    let versions_match = compositor_version.as_ref() == Some(&client_version);
+    let compositor_version_str = compositor_version.as_deref().unwrap_or("unknown");
+
+    // The version request prints both versions by itself.
+    if !versions_match && !matches!(msg, Msg::Version) {
+        eprintln!(
+            "warning: niri msg is version {client_version}, but the running niri compositor is \
+             version {compositor_version_str}; make sure that you've restarted niri after upgrading"
+        );
+    }
+
+    let add_version_context = |err: anyhow::Error| {
+        if versions_match {
+            return err;
+        }
+
+        err.context(format!(
+            "niri msg is version {client_version}, but the running niri compositor is version \
+             {compositor_version_str}; make sure that you've restarted niri after upgrading"
+        ))
+    };
+
+    let mut read = send_request(&socket_path, &request)?;
+    let mut buf = String::new();
+    read.read_line(&mut buf)
         .context("error reading IPC response")?;
 
-    let reply: Reply = serde_json::from_slice(&buf).context("error parsing IPC reply")?;
+    let reply: Reply = serde_json::from_str(&buf)
+        .context("error parsing IPC reply")
+        .map_err(add_version_context)?;
 
-    let response = reply
-        .map_err(|msg| anyhow!(msg))
-        .context("niri could not handle the request")?;
+    let response = reply.map_err(|err| {
+        let is_unsupported = matches!(err, niri_ipc::Error::UnsupportedRequest(_));
+        let err = anyhow::Error::new(err).context("niri could not handle the request");
+        if is_unsupported {
+            add_version_context(err)
+        } else {
+            err
+        }
+    })?;
 
     // Default SIGPIPE so that our prints don't panic on stdout closing.
     unsafe {
@@ -49,6 +84,28 @@ pub fn handle_msg(msg: Msg, json: bool) -> anyhow::Result<()> {
     }
 
     match msg {
+        Msg::Version => {
+            let Response::Version(compositor_version) = response else {
+                bail!("unexpected response: expected Version, got {response:?}");
+            };
+
+            if json {
+                let versions = serde_json::json!({
+                    "cli": client_version,
+                    "compositor": compositor_version,
+                });
+                println!("{versions}");
+                return Ok(());
+            }
+
+            println!("niri msg is {client_version}");
+            println!("Compositor is {compositor_version}");
+            if client_version != compositor_version {
+                println!();
+                println!("These versions don't match.");
+                println!("Make sure that you've restarted niri after upgrading.");
+            }
+        }
         Msg::Outputs => {
             let Response::Outputs(outputs) = response else {
                 bail!("unexpected response: expected Outputs, got {response:?}");
@@ -166,20 +223,103 @@ pub fn handle_msg(msg: Msg, json: bool) -> anyhow::Result<()> {
 
             if let Some(window) = window {
                 println!("Focused window:");
+                print_window(&window);
+            } else {
+                println!("No window is focused.");
+            }
+        }
+        Msg::Windows => {
+            let Response::Windows(windows) = response else {
+                bail!("unexpected response: expected Windows, got {response:?}");
+            };
 
-                if let Some(title) = window.title {
-                    println!("  Title: \"{title}\"");
-                } else {
-                    println!("  Title: (unset)");
+            if json {
+                let windows =
+                    serde_json::to_string(&windows).context("error formatting response")?;
+                println!("{windows}");
+                return Ok(());
+            }
+
+            if windows.is_empty() {
+                println!("No windows.");
+                return Ok(());
+            }
+
+            for window in windows {
+                let focused = if window.is_focused { " (focused)" } else { "" };
+                println!("Window ID {}:{focused}", window.id);
+                print_window(&window);
+                println!();
+            }
+        }
+        Msg::Workspaces => {
+            let Response::Workspaces(workspaces) = response else {
+                bail!("unexpected response: expected Workspaces, got {response:?}");
+            };
+
+            if json {
+                let workspaces =
+                    serde_json::to_string(&workspaces).context("error formatting response")?;
+                println!("{workspaces}");
+                return Ok(());
+            }
+
+            if workspaces.is_empty() {
+                println!("No workspaces.");
+                return Ok(());
+            }
+
+            let mut current_output = None;
+            for workspace in workspaces {
+                if current_output != Some(workspace.output.clone()) {
+                    if current_output.is_some() {
+                        println!();
+                    }
+
+                    if let Some(output) = &workspace.output {
+                        println!("Output \"{output}\":");
+                    } else {
+                        println!("No output:");
+                    }
+                    current_output = Some(workspace.output.clone());
                 }
 
-                if let Some(app_id) = window.app_id {
-                    println!("  App ID: \"{app_id}\"");
+                let active = if workspace.is_active { " * " } else { "   " };
+                let focused = if workspace.is_focused {
+                    " (focused)"
                 } else {
-                    println!("  App ID: (unset)");
-                }
-            } else {
-                println!("No window is focused.");
+                    ""
+                };
+                let name = match &workspace.name {
+                    Some(name) => format!(" \"{name}\""),
+                    None => String::new(),
+                };
+                println!(
+                    "{active}{}{name} (id {}){focused}",
+                    workspace.idx, workspace.id
+                );
+            }
+        }
+        Msg::KeyboardLayouts => {
+            let Response::KeyboardLayouts(layouts) = response else {
+                bail!("unexpected response: expected KeyboardLayouts, got {response:?}");
+            };
+
+            if json {
+                let layouts =
+                    serde_json::to_string(&layouts).context("error formatting response")?;
+                println!("{layouts}");
+                return Ok(());
+            }
+
+            println!("Keyboard layouts:");
+            for (idx, name) in layouts.names.iter().enumerate() {
+                let current = if idx == usize::from(layouts.current_idx) {
+                    " * "
+                } else {
+                    "   "
+                };
+                println!("{current}{idx} {name}");
             }
         }
         Msg::Action { .. } => {
@@ -187,7 +327,177 @@ pub fn handle_msg(msg: Msg, json: bool) -> anyhow::Result<()> {
                 bail!("unexpected response: expected Handled, got {response:?}");
             };
         }
+        Msg::Output { .. } => {
+            let Response::Handled = response else {
+                bail!("unexpected response: expected Handled, got {response:?}");
+            };
+        }
+        Msg::ReloadConfig => {
+            let Response::Handled = response else {
+                bail!("unexpected response: expected Handled, got {response:?}");
+            };
+        }
+        Msg::EventStream => {
+            let Response::Handled = response else {
+                bail!("unexpected response: expected Handled, got {response:?}");
+            };
+
+            if !json {
+                println!("Started reading events.");
+            }
+
+            loop {
+                buf.clear();
+                let len = read
+                    .read_line(&mut buf)
+                    .context("error reading event from niri")?;
+                if len == 0 {
+                    break;
+                }
+
+                if json {
+                    print!("{buf}");
+                    continue;
+                }
+
+                let event: Event = serde_json::from_str(&buf).context("error parsing event")?;
+                print_event(event);
+            }
+        }
     }
 
     Ok(())
 }
+
+fn print_window(window: &niri_ipc::Window) {
+    if let Some(title) = &window.title {
+        println!("  Title: \"{title}\"");
+    } else {
+        println!("  Title: (unset)");
+    }
+
+    if let Some(app_id) = &window.app_id {
+        println!("  App ID: \"{app_id}\"");
+    } else {
+        println!("  App ID: (unset)");
+    }
+
+    if let Some(pid) = window.pid {
+        println!("  PID: {pid}");
+    } else {
+        println!("  PID: (unknown)");
+    }
+
+    if let Some(workspace_id) = window.workspace_id {
+        println!("  Workspace ID: {workspace_id}");
+    }
+
+    if let Some(output) = &window.output {
+        println!("  Output: \"{output}\"");
+    }
+
+    if let (Some(column), Some(tile)) = (window.column_idx, window.tile_idx) {
+        println!("  Position: column {column}, window {tile} in the column");
+    }
+
+    let (width, height) = window.size;
+    println!("  Size: {width}x{height}");
+
+    if window.is_fullscreen {
+        println!("  Fullscreen");
+    }
+
+    if window.is_floating {
+        println!("  Floating");
+    }
+
+    if window.is_urgent {
+        println!("  Urgent");
+    }
+}
+
+fn send_request(socket_path: &OsStr, request: &Request) -> anyhow::Result<BufReader<UnixStream>> {
+    let mut stream = UnixStream::connect(socket_path)
+        .with_context(|| format!("error connecting to {socket_path:?}"))?;
+
+    let mut buf = serde_json::to_vec(request).unwrap();
+    buf.push(b'\n');
+    stream
+        .write_all(&buf)
+        .context("error writing IPC request")?;
+
+    Ok(BufReader::new(stream))
+}
+
+/// Returns the version of the running compositor, or `None` if it is too old to report it.
+fn compositor_version(socket_path: &OsStr) -> anyhow::Result<Option<String>> {
+    let mut read = send_request(socket_path, &Request::Version)?;
+    let mut buf = String::new();
+    read.read_line(&mut buf)
+        .context("error reading IPC response")?;
+
+    // Older compositors reply with an error that may not even parse as the current Reply.
+    match serde_json::from_str::<Reply>(&buf) {
+        Ok(Ok(Response::Version(version))) => Ok(Some(version)),
+        _ => Ok(None),
+    }
+}
+
+fn print_event(event: Event) {
+    match event {
+        Event::WindowOpened { window } => {
+            println!("Window opened: {}", format_window(&window));
+        }
+        Event::WindowChanged { window } => {
+            println!("Window changed: {}", format_window(&window));
+        }
+        Event::WindowClosed { id } => {
+            println!("Window closed: {id}");
+        }
+        Event::WindowFocusChanged { id } => {
+            if let Some(id) = id {
+                println!("Window focus changed: {id}");
+            } else {
+                println!("Window focus changed: no window is focused");
+            }
+        }
+        Event::WorkspaceCreated { id, output } => {
+            let output = output.as_deref().unwrap_or("(no output)");
+            println!("Workspace created: {id} on {output}");
+        }
+        Event::WorkspaceRemoved { id } => {
+            println!("Workspace removed: {id}");
+        }
+        Event::WorkspaceOutputChanged { id, output } => {
+            let output = output.as_deref().unwrap_or("(no output)");
+            println!("Workspace moved: {id} to {output}");
+        }
+        Event::WorkspaceActivated { id, focused } => {
+            let focused = if focused { " (focused)" } else { "" };
+            println!("Workspace activated: {id}{focused}");
+        }
+        Event::OutputsChanged { outputs } => {
+            let mut names = outputs.into_keys().collect::<Vec<_>>();
+            names.sort_unstable();
+            println!("Outputs changed: {}", names.join(", "));
+        }
+        Event::KeyboardLayoutsChanged { keyboard_layouts } => {
+            let names = keyboard_layouts.names.join(", ");
+            let idx = keyboard_layouts.current_idx;
+            println!("Keyboard layouts changed: {names} (active: {idx})");
+        }
+        Event::KeyboardLayoutSwitched { idx } => {
+            println!("Keyboard layout switched: {idx}");
+        }
+    }
+}
+
+fn format_window(window: &niri_ipc::Window) -> String {
+    let quote_or_unset = |value: &Option<String>| match value {
+        Some(value) => format!("\"{value}\""),
+        None => String::from("(unset)"),
+    };
+    let title = quote_or_unset(&window.title);
+    let app_id = quote_or_unset(&window.app_id);
+    format!("{}, title {title}, app ID {app_id}", window.id)
+}
@@ -1,32 +1,58 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::os::unix::net::{UnixListener, UnixStream};
 use std::path::PathBuf;
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use std::{env, io, process};
 
-use anyhow::Context;
+use anyhow::{bail, Context};
+use async_channel::{Sender, TrySendError};
 use calloop::io::Async;
 use directories::BaseDirs;
 use futures_util::io::{AsyncReadExt, BufReader};
-use futures_util::{AsyncBufReadExt, AsyncWriteExt};
-use niri_ipc::{Request, Response};
-use smithay::desktop::Window;
+use futures_util::{AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+use niri_ipc::{Event, KeyboardLayouts, Request, Response};
 use smithay::reexports::calloop::generic::Generic;
 use smithay::reexports::calloop::{Interest, LoopHandle, Mode, PostAction};
 use smithay::reexports::rustix::fs::unlink;
+use smithay::reexports::wayland_server::{DisplayHandle, Resource};
 use smithay::wayland::compositor::with_states;
 use smithay::wayland::shell::xdg::XdgToplevelSurfaceData;
 
 use crate::backend::IpcOutputMap;
+use crate::layout::LayoutElement as _;
 use crate::niri::State;
+use crate::utils::version;
+use crate::window::Mapped;
+
+// If an event stream client fails to read events fast enough that we accumulate more than this
+// number in our buffer, we drop that event stream client.
+const EVENT_STREAM_BUFFER_SIZE: usize = 64;
 
 pub struct IpcServer {
     pub socket_path: PathBuf,
+    event_streams: Rc<RefCell<Vec<Sender<Event>>>>,
+    event_stream_state: Rc<RefCell<EventStreamState>>,
 }
 
 struct ClientCtx {
     event_loop: LoopHandle<'static, State>,
     ipc_outputs: Arc<Mutex<IpcOutputMap>>,
-    ipc_focused_window: Arc<Mutex<Option<Window>>>,
+    event_streams: Rc<RefCell<Vec<Sender<Event>>>>,
+    event_stream_state: Rc<RefCell<EventStreamState>>,
+}
+
+/// State as last sent to the event stream clients.
+///
+/// New event stream clients receive this state up-front.
+#[derive(Default)]
+struct EventStreamState {
+    windows: HashMap<u64, niri_ipc::Window>,
+    focused_window: Option<u64>,
+    workspaces: HashMap<u64, niri_ipc::Workspace>,
+    focused_workspace: Option<u64>,
+    keyboard_layouts: Option<KeyboardLayouts>,
 }
 
 impl IpcServer {
@@ -58,7 +84,23 @@ impl IpcServer {
             })
             .unwrap();
 
-        Ok(Self { socket_path })
+        Ok(Self {
+            socket_path,
+            event_streams: Rc::new(RefCell::new(Vec::new())),
+            event_stream_state: Rc::new(RefCell::new(EventStreamState::default())),
+        })
+    }
+
+    pub fn send_event(&self, event: Event) {
+        let mut streams = self.event_streams.borrow_mut();
+        streams.retain(|tx| match tx.try_send(event.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Closed(_)) => false,
+            Err(TrySendError::Full(_)) => {
+                warn!("IPC event stream client is not reading events fast enough, disconnecting");
+                false
+            }
+        });
     }
 }
 
@@ -80,6 +122,8 @@ fn on_new_ipc_client(state: &mut State, stream: UnixStream) {
     let _span = tracy_client::span!("on_new_ipc_client");
     trace!("new IPC client connected");
 
+    let ipc_server = state.niri.ipc_server.as_ref().unwrap();
+
     let stream = match state.niri.event_loop.adapt_io(stream) {
         Ok(stream) => stream,
         Err(err) => {
@@ -91,7 +135,8 @@ fn on_new_ipc_client(state: &mut State, stream: UnixStream) {
     let ctx = ClientCtx {
         event_loop: state.niri.event_loop.clone(),
         ipc_outputs: state.backend.ipc_outputs(),
-        ipc_focused_window: state.niri.ipc_focused_window.clone(),
+        event_streams: ipc_server.event_streams.clone(),
+        event_stream_state: ipc_server.event_stream_state.clone(),
     };
 
     let future = async move {
@@ -106,61 +151,672 @@ fn on_new_ipc_client(state: &mut State, stream: UnixStream) {
 
 async fn handle_client(ctx: ClientCtx, stream: Async<'_, UnixStream>) -> anyhow::Result<()> {
     let (read, mut write) = stream.split();
+    let mut read = BufReader::new(read);
     let mut buf = String::new();
 
-    // Read a single line to allow extensibility in the future to keep reading.
-    BufReader::new(read)
-        .read_line(&mut buf)
-        .await
-        .context("error reading request")?;
+    // Requests are newline-delimited, so keep serving them until the client closes the
+    // connection.
+    loop {
+        buf.clear();
+        let len = read
+            .read_line(&mut buf)
+            .await
+            .context("error reading request")?;
+        if len == 0 {
+            return Ok(());
+        }
+
+        let request = serde_json::from_str(&buf).map_err(|err| {
+            // Data errors mean that the JSON is fine but doesn't match any request we know, which
+            // usually happens with a newer client.
+            if err.is_data() {
+                niri_ipc::Error::UnsupportedRequest(err.to_string())
+            } else {
+                niri_ipc::Error::MalformedRequest(err.to_string())
+            }
+        });
+        let requested_event_stream = matches!(request, Ok(Request::EventStream));
+
+        let reply = match request {
+            Ok(request) => process(&ctx, request).await.map_err(|err| {
+                // Pass typed errors through as is.
+                err.downcast()
+                    .unwrap_or_else(|err| niri_ipc::Error::Failed(err.to_string()))
+            }),
+            Err(err) => Err(err),
+        };
+        let reply = reply.map_err(|err| {
+            warn!("error processing IPC request: {err}");
+            err
+        });
 
-    let reply = process(&ctx, &buf).map_err(|err| {
-        warn!("error processing IPC request: {err:?}");
-        err.to_string()
-    });
+        let mut reply = serde_json::to_vec(&reply).context("error formatting reply")?;
+        reply.push(b'\n');
+        write
+            .write_all(&reply)
+            .await
+            .context("error writing reply")?;
 
-    let buf = serde_json::to_vec(&reply).context("error formatting reply")?;
-    write.write_all(&buf).await.context("error writing reply")?;
+        if requested_event_stream {
+            break;
+        }
+    }
+
+    // The connection is now dedicated to the event stream, so further requests are ignored.
+
+    // Register the stream and take the initial state at the same time, so that no events fall in
+    // between.
+    let (events_tx, events_rx) = async_channel::bounded(EVENT_STREAM_BUFFER_SIZE);
+    let initial_events = ctx.event_stream_state.borrow().replicate();
+    ctx.event_streams.borrow_mut().push(events_tx);
+
+    let outputs = ctx.ipc_outputs.lock().unwrap().clone();
+    let initial_events = initial_events
+        .into_iter()
+        .chain([Event::OutputsChanged { outputs }]);
+    for event in initial_events {
+        write_event(&mut write, &event).await?;
+    }
+
+    while let Ok(event) = events_rx.recv().await {
+        write_event(&mut write, &event).await?;
+    }
 
     Ok(())
 }
 
-fn process(ctx: &ClientCtx, buf: &str) -> anyhow::Result<Response> {
-    let request: Request = serde_json::from_str(buf).context("error parsing request")?;
+async fn write_event(write: &mut (impl AsyncWrite + Unpin), event: &Event) -> anyhow::Result<()> {
+    let mut buf = serde_json::to_vec(event).context("error formatting event")?;
+    buf.push(b'\n');
+    write.write_all(&buf).await.context("error writing event")
+}
 
+async fn process(ctx: &ClientCtx, request: Request) -> anyhow::Result<Response> {
     let response = match request {
+        Request::Version => Response::Version(version()),
         Request::Outputs => {
             let ipc_outputs = ctx.ipc_outputs.lock().unwrap().clone();
             Response::Outputs(ipc_outputs)
         }
         Request::FocusedWindow => {
-            let window = ctx.ipc_focused_window.lock().unwrap().clone();
-            let window = window.map(|window| {
-                let wl_surface = window.toplevel().expect("no X11 support").wl_surface();
-                with_states(wl_surface, |states| {
-                    let role = states
-                        .data_map
-                        .get::<XdgToplevelSurfaceData>()
-                        .unwrap()
-                        .lock()
-                        .unwrap();
-
-                    niri_ipc::Window {
-                        title: role.title.clone(),
-                        app_id: role.app_id.clone(),
-                    }
-                })
-            });
+            let state = ctx.event_stream_state.borrow();
+            let window = state
+                .focused_window
+                .and_then(|id| state.windows.get(&id))
+                .cloned();
             Response::FocusedWindow(window)
         }
         Request::Action(action) => {
+            validate_action(&action)?;
             let action = niri_config::Action::from(action);
             ctx.event_loop.insert_idle(move |state| {
                 state.do_action(action);
             });
             Response::Handled
         }
+        Request::Output { output, action } => {
+            let ipc_outputs = ctx.ipc_outputs.lock().unwrap();
+            if !ipc_outputs.contains_key(&output) {
+                bail!("output {output:?} is not connected");
+            }
+            drop(ipc_outputs);
+
+            ctx.event_loop.insert_idle(move |state| {
+                state.apply_transient_output_config(&output, action);
+            });
+            Response::Handled
+        }
+        Request::Batch(actions) => {
+            for action in &actions {
+                validate_action(action)?;
+            }
+            let actions = actions
+                .into_iter()
+                .map(niri_config::Action::from)
+                .collect::<Vec<_>>();
+            // Apply all actions in the same idle callback so that no frame is rendered in between.
+            ctx.event_loop.insert_idle(move |state| {
+                for action in actions {
+                    state.do_action(action);
+                }
+            });
+            Response::Handled
+        }
+        Request::Windows => {
+            let state = ctx.event_stream_state.borrow();
+            let mut windows = state.windows.values().cloned().collect::<Vec<_>>();
+            windows.sort_unstable_by_key(|win| win.id);
+            Response::Windows(windows)
+        }
+        Request::Workspaces => {
+            let state = ctx.event_stream_state.borrow();
+            let mut workspaces = state.workspaces.values().cloned().collect::<Vec<_>>();
+            workspaces.sort_unstable_by(|a, b| (&a.output, a.idx).cmp(&(&b.output, b.idx)));
+            Response::Workspaces(workspaces)
+        }
+        Request::KeyboardLayouts => {
+            // Read the layouts from the keyboard rather than from the event stream state, which
+            // may not be updated yet right after a keymap change.
+            let (tx, rx) = async_channel::bounded(1);
+            ctx.event_loop.insert_idle(move |state| {
+                let _ = tx.try_send(state.keyboard_layouts());
+            });
+
+            let layouts = rx.recv().await.context("error getting keyboard layouts")?;
+            Response::KeyboardLayouts(layouts)
+        }
+        Request::ReloadConfig => {
+            let (tx, rx) = async_channel::bounded(1);
+            ctx.event_loop.insert_idle(move |state| {
+                let result = match state.niri.config_path.clone() {
+                    Some(path) => state
+                        .reload_config(path)
+                        .map_err(niri_ipc::Error::InvalidConfig),
+                    None => Err(niri_ipc::Error::Failed(String::from(
+                        "niri is running without a config file",
+                    ))),
+                };
+                let _ = tx.try_send(result);
+            });
+
+            let result = rx.recv().await.context("error reloading config")?;
+            result?;
+            Response::Handled
+        }
+        Request::ValidateConfig { text } => {
+            let diagnostics = match niri_config::Config::parse("config.kdl", &text) {
+                Ok(_) => Vec::new(),
+                Err(err) => niri_config::diagnostics(&err),
+            };
+            Response::ConfigDiagnostics(diagnostics)
+        }
+        // Handled in handle_client().
+        Request::EventStream => Response::Handled,
     };
 
     Ok(response)
 }
+
+/// Checks the action arguments that can only be validated by niri, so that errors reach the client.
+fn validate_action(action: &niri_ipc::Action) -> Result<(), niri_ipc::Error> {
+    if let niri_ipc::Action::SetWorkspaceLayout { layout } = action {
+        if let Err(err) = niri_config::LayoutOverride::parse(layout) {
+            return Err(niri_ipc::Error::InvalidConfig(niri_config::diagnostics(
+                &err,
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates the IPC description of a window, without its position in the layout.
+fn make_ipc_window(mapped: &Mapped, pid: Option<i32>) -> niri_ipc::Window {
+    let size = mapped.size();
+
+    with_states(mapped.toplevel().wl_surface(), |states| {
+        let role = states
+            .data_map
+            .get::<XdgToplevelSurfaceData>()
+            .unwrap()
+            .lock()
+            .unwrap();
+
+        niri_ipc::Window {
+            id: u64::from(mapped.id().get()),
+            title: role.title.clone(),
+            app_id: role.app_id.clone(),
+            pid,
+            workspace_id: None,
+            output: None,
+            column_idx: None,
+            tile_idx: None,
+            size: (size.w, size.h),
+            is_focused: mapped.is_focused(),
+            is_fullscreen: mapped.is_fullscreen(),
+            is_floating: false,
+            is_urgent: mapped.is_urgent(),
+        }
+    })
+}
+
+fn client_pid(display_handle: &DisplayHandle, mapped: &Mapped) -> Option<i32> {
+    let client = mapped.toplevel().wl_surface().client()?;
+    let credentials = client.get_credentials(display_handle).ok()?;
+    Some(credentials.pid)
+}
+
+impl EventStreamState {
+    /// Returns the events that bring a new event stream client up to date.
+    fn replicate(&self) -> Vec<Event> {
+        let mut events = Vec::new();
+
+        let mut workspaces = self.workspaces.iter().collect::<Vec<_>>();
+        workspaces.sort_unstable_by_key(|(id, _)| **id);
+        for (id, ws) in &workspaces {
+            events.push(Event::WorkspaceCreated {
+                id: **id,
+                output: ws.output.clone(),
+            });
+        }
+
+        // Send the focused workspace last so that clients end up with the right one focused.
+        for (id, ws) in &workspaces {
+            if ws.is_active && self.focused_workspace != Some(**id) {
+                events.push(Event::WorkspaceActivated {
+                    id: **id,
+                    focused: false,
+                });
+            }
+        }
+        if let Some(id) = self.focused_workspace {
+            events.push(Event::WorkspaceActivated { id, focused: true });
+        }
+
+        let mut windows = self.windows.values().cloned().collect::<Vec<_>>();
+        windows.sort_unstable_by_key(|win| win.id);
+        events.extend(
+            windows
+                .into_iter()
+                .map(|window| Event::WindowOpened { window }),
+        );
+
+        events.push(Event::WindowFocusChanged {
+            id: self.focused_window,
+        });
+
+        if let Some(keyboard_layouts) = self.keyboard_layouts.clone() {
+            events.push(Event::KeyboardLayoutsChanged { keyboard_layouts });
+        }
+
+        events
+    }
+
+    /// Updates the workspaces and the windows, returning the events that describe the changes.
+    fn update_layout(
+        &mut self,
+        workspaces: Vec<niri_ipc::Workspace>,
+        windows: Vec<niri_ipc::Window>,
+    ) -> Vec<Event> {
+        let mut events = Vec::new();
+
+        // Workspaces.
+        let focused_workspace = workspaces.iter().find(|ws| ws.is_focused).map(|ws| ws.id);
+        let mut seen = HashSet::new();
+        for workspace in workspaces {
+            let id = workspace.id;
+            seen.insert(id);
+
+            let is_active = workspace.is_active;
+            match self.workspaces.insert(id, workspace.clone()) {
+                Some(existing) => {
+                    if existing.output != workspace.output {
+                        events.push(Event::WorkspaceOutputChanged {
+                            id,
+                            output: workspace.output,
+                        });
+                    }
+
+                    if is_active && !existing.is_active && focused_workspace != Some(id) {
+                        events.push(Event::WorkspaceActivated { id, focused: false });
+                    }
+                }
+                None => {
+                    events.push(Event::WorkspaceCreated {
+                        id,
+                        output: workspace.output,
+                    });
+
+                    if is_active && focused_workspace != Some(id) {
+                        events.push(Event::WorkspaceActivated { id, focused: false });
+                    }
+                }
+            }
+        }
+
+        self.workspaces.retain(|id, _| {
+            if seen.contains(id) {
+                return true;
+            }
+
+            events.push(Event::WorkspaceRemoved { id: *id });
+            false
+        });
+
+        if self.focused_workspace != focused_workspace {
+            self.focused_workspace = focused_workspace;
+            if let Some(id) = focused_workspace {
+                events.push(Event::WorkspaceActivated { id, focused: true });
+            }
+        }
+
+        // Windows.
+        let focused_window = windows.iter().find(|win| win.is_focused).map(|win| win.id);
+        let mut seen = HashSet::new();
+        for window in windows {
+            let id = window.id;
+            seen.insert(id);
+
+            match self.windows.insert(id, window.clone()) {
+                Some(mut existing) => {
+                    // Focus changes are sent as a separate event. Size changes alone are not sent
+                    // since they happen on every frame of a resize, but the stored size is kept
+                    // up to date for the requests.
+                    existing.is_focused = window.is_focused;
+                    existing.size = window.size;
+                    if existing != window {
+                        events.push(Event::WindowChanged { window });
+                    }
+                }
+                None => events.push(Event::WindowOpened { window }),
+            }
+        }
+
+        self.windows.retain(|id, _| {
+            if seen.contains(id) {
+                return true;
+            }
+
+            events.push(Event::WindowClosed { id: *id });
+            false
+        });
+
+        if self.focused_window != focused_window {
+            self.focused_window = focused_window;
+            events.push(Event::WindowFocusChanged { id: focused_window });
+        }
+
+        events
+    }
+}
+
+impl State {
+    /// Sends the layout changes since the last call to the event stream clients.
+    pub fn ipc_refresh_layout(&mut self) {
+        let Some(server) = &self.niri.ipc_server else {
+            return;
+        };
+
+        let _span = tracy_client::span!("State::ipc_refresh_layout");
+
+        let mut state = server.event_stream_state.borrow_mut();
+        let layout = &self.niri.layout;
+
+        // Workspaces.
+        let focused_workspace = layout.active_workspace().map(|ws| u64::from(ws.id().get()));
+        let workspaces = layout
+            .workspaces()
+            .map(|(mon, idx, ws)| {
+                let id = u64::from(ws.id().get());
+                niri_ipc::Workspace {
+                    id,
+                    idx: u8::try_from(idx + 1).unwrap_or(u8::MAX),
+                    name: ws.name.clone(),
+                    output: mon.map(|mon| mon.output.name()),
+                    is_active: mon.is_some_and(|mon| mon.active_workspace_idx == idx),
+                    is_focused: focused_workspace == Some(id),
+                }
+            })
+            .collect();
+
+        // Windows.
+        let make_window = |mapped: &Mapped| {
+            // Looking up the client credentials is a syscall, so only do it once per window.
+            let id = u64::from(mapped.id().get());
+            let pid = match state.windows.get(&id) {
+                Some(existing) => existing.pid,
+                None => client_pid(&self.niri.display_handle, mapped),
+            };
+            make_ipc_window(mapped, pid)
+        };
+
+        let mut windows = Vec::new();
+        for (mon, _, ws) in layout.workspaces() {
+            let workspace_id = Some(u64::from(ws.id().get()));
+            let output = mon.map(|mon| mon.output.name());
+
+            for (col_idx, col) in ws.columns.iter().enumerate() {
+                for (tile_idx, tile) in col.tiles.iter().enumerate() {
+                    let mut window = make_window(tile.window());
+                    window.workspace_id = workspace_id;
+                    window.output = output.clone();
+                    window.column_idx = Some(col_idx + 1);
+                    window.tile_idx = Some(tile_idx + 1);
+                    windows.push(window);
+                }
+            }
+
+            for mapped in ws.floating_windows() {
+                let mut window = make_window(mapped);
+                window.workspace_id = workspace_id;
+                window.output = output.clone();
+                window.is_floating = true;
+                windows.push(window);
+            }
+        }
+
+        // The interactively moved window belongs to no workspace until it's dropped.
+        if let Some((mapped, output, is_floating)) = layout.interactive_moved_window() {
+            let mut window = make_window(mapped);
+            window.output = Some(output.name());
+            window.is_floating = is_floating;
+            windows.push(window);
+        }
+
+        // Windows hidden in the scratchpad are neither on a workspace nor on an output.
+        for mapped in layout.scratchpad_windows() {
+            windows.push(make_window(mapped));
+        }
+
+        let events = state.update_layout(workspaces, windows);
+        drop(state);
+
+        for event in events {
+            server.send_event(event);
+        }
+    }
+
+    /// Returns the keyboard layouts of the current xkb keymap.
+    fn keyboard_layouts(&mut self) -> KeyboardLayouts {
+        let keyboard = self.niri.seat.get_keyboard().unwrap();
+        keyboard.with_xkb_state(self, |context| {
+            let names = context
+                .layouts()
+                .map(|layout| context.layout_name(layout).to_owned())
+                .collect();
+            KeyboardLayouts {
+                names,
+                current_idx: u8::try_from(context.active_layout().0).unwrap_or(u8::MAX),
+            }
+        })
+    }
+
+    /// Sends the current keyboard layouts to the event stream clients.
+    ///
+    /// Must be called after changing the xkb keymap.
+    pub fn ipc_keyboard_layouts_changed(&mut self) {
+        let keyboard_layouts = self.keyboard_layouts();
+
+        let Some(server) = &self.niri.ipc_server else {
+            return;
+        };
+
+        let mut state = server.event_stream_state.borrow_mut();
+        if state.keyboard_layouts.as_ref() == Some(&keyboard_layouts) {
+            return;
+        }
+        state.keyboard_layouts = Some(keyboard_layouts.clone());
+        drop(state);
+
+        server.send_event(Event::KeyboardLayoutsChanged { keyboard_layouts });
+    }
+
+    /// Sends the active keyboard layout to the event stream clients if it had changed.
+    ///
+    /// This picks up layout switches from both actions and `track-layout "window"`.
+    pub fn ipc_refresh_keyboard_layout_index(&mut self) {
+        let keyboard = self.niri.seat.get_keyboard().unwrap();
+        let idx = keyboard.with_xkb_state(self, |context| context.active_layout().0);
+        let idx = u8::try_from(idx).unwrap_or(u8::MAX);
+
+        let Some(server) = &self.niri.ipc_server else {
+            return;
+        };
+
+        let mut state = server.event_stream_state.borrow_mut();
+        let Some(keyboard_layouts) = &mut state.keyboard_layouts else {
+            // Not initialized yet, send the full layout list.
+            drop(state);
+            self.ipc_keyboard_layouts_changed();
+            return;
+        };
+
+        if keyboard_layouts.current_idx == idx {
+            return;
+        }
+        keyboard_layouts.current_idx = idx;
+        drop(state);
+
+        server.send_event(Event::KeyboardLayoutSwitched { idx });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(id: u64, is_active: bool, is_focused: bool) -> niri_ipc::Workspace {
+        niri_ipc::Workspace {
+            id,
+            idx: 1,
+            name: None,
+            output: Some(String::from("output1")),
+            is_active,
+            is_focused,
+        }
+    }
+
+    fn window(id: u64, is_focused: bool) -> niri_ipc::Window {
+        niri_ipc::Window {
+            id,
+            title: None,
+            app_id: None,
+            pid: None,
+            workspace_id: Some(1),
+            output: Some(String::from("output1")),
+            column_idx: Some(1),
+            tile_idx: Some(1),
+            size: (100, 200),
+            is_focused,
+            is_fullscreen: false,
+            is_floating: false,
+            is_urgent: false,
+        }
+    }
+
+    #[test]
+    fn window_open_and_close() {
+        let mut state = EventStreamState::default();
+
+        let events = state.update_layout(vec![workspace(1, true, true)], vec![window(1, true)]);
+        assert!(matches!(
+            &events[..],
+            [
+                Event::WorkspaceCreated { id: 1, .. },
+                Event::WorkspaceActivated {
+                    id: 1,
+                    focused: true
+                },
+                Event::WindowOpened { window },
+                Event::WindowFocusChanged { id: Some(1) },
+            ] if window.id == 1
+        ));
+
+        let events = state.update_layout(vec![workspace(1, true, true)], vec![]);
+        assert!(matches!(
+            &events[..],
+            [
+                Event::WindowClosed { id: 1 },
+                Event::WindowFocusChanged { id: None },
+            ]
+        ));
+        assert!(state.windows.is_empty());
+    }
+
+    #[test]
+    fn window_focus_change() {
+        let mut state = EventStreamState::default();
+        state.update_layout(
+            vec![workspace(1, true, true)],
+            vec![window(1, true), window(2, false)],
+        );
+
+        // Only the focus event is sent, without a WindowChanged for either window.
+        let events = state.update_layout(
+            vec![workspace(1, true, true)],
+            vec![window(1, false), window(2, true)],
+        );
+        assert!(matches!(
+            &events[..],
+            [Event::WindowFocusChanged { id: Some(2) }]
+        ));
+        assert!(state.windows[&2].is_focused);
+    }
+
+    #[test]
+    fn window_resize_is_not_sent() {
+        let mut state = EventStreamState::default();
+        state.update_layout(vec![workspace(1, true, true)], vec![window(1, true)]);
+
+        let mut resized = window(1, true);
+        resized.size = (300, 400);
+        let events = state.update_layout(vec![workspace(1, true, true)], vec![resized]);
+        assert!(events.is_empty());
+        assert_eq!(state.windows[&1].size, (300, 400));
+    }
+
+    #[test]
+    fn window_urgency_is_sent() {
+        let mut state = EventStreamState::default();
+        state.update_layout(
+            vec![workspace(1, true, true)],
+            vec![window(1, true), window(2, false)],
+        );
+
+        let mut urgent = window(2, false);
+        urgent.is_urgent = true;
+        let events = state.update_layout(
+            vec![workspace(1, true, true)],
+            vec![window(1, true), urgent],
+        );
+        assert!(matches!(
+            &events[..],
+            [Event::WindowChanged { window }] if window.id == 2 && window.is_urgent
+        ));
+    }
+
+    #[test]
+    fn workspace_activation_is_sent_once() {
+        let mut state = EventStreamState::default();
+        let workspaces = vec![workspace(1, true, true), workspace(2, false, false)];
+        state.update_layout(workspaces.clone(), vec![]);
+
+        // Nothing changed.
+        let events = state.update_layout(workspaces, vec![]);
+        assert!(events.is_empty());
+
+        // The focused workspace is activated with focus only, without a separate unfocused
+        // activation.
+        let events = state.update_layout(
+            vec![workspace(1, false, false), workspace(2, true, true)],
+            vec![],
+        );
+        assert!(matches!(
+            &events[..],
+            [Event::WorkspaceActivated {
+                id: 2,
+                focused: true
+            }]
+        ));
+    }
+}
@@ -30,30 +30,38 @@
 //! making the primary output their original output.
 
 use std::cmp::min;
+use std::collections::HashMap;
+use std::fmt::Debug;
 use std::mem;
 use std::rc::Rc;
 use std::time::Duration;
 
-use niri_config::{CenterFocusedColumn, Config, Struts};
+use niri_config::{
+    CenterFocusedColumn, Config, LayoutOverride, PresetSize, Struts, Workspace as WorkspaceConfig,
+};
 use niri_ipc::SizeChange;
-use smithay::backend::renderer::element::solid::SolidColorRenderElement;
+use smithay::backend::renderer::element::solid::{SolidColorBuffer, SolidColorRenderElement};
 use smithay::backend::renderer::element::surface::WaylandSurfaceRenderElement;
-use smithay::backend::renderer::element::Id;
+use smithay::backend::renderer::element::{Id, Kind};
 use smithay::output::Output;
 use smithay::reexports::wayland_server::protocol::wl_surface::WlSurface;
-use smithay::utils::{Logical, Point, Scale, Size, Transform};
+use smithay::utils::{Logical, Point, Rectangle, Scale, Size, Transform};
 
 use self::monitor::Monitor;
 pub use self::monitor::MonitorRenderElement;
-use self::workspace::{compute_working_area, Column, ColumnWidth, OutputId, Workspace};
+use self::tile::{Tile, TileRenderElement};
+use self::workspace::{
+    compute_working_area, Column, ColumnWidth, InsertPosition, OutputId, WindowPlacement, Workspace,
+};
 use crate::niri_render_elements;
 use crate::render_helpers::renderer::NiriRenderer;
 use crate::render_helpers::RenderTarget;
-use crate::utils::output_size;
+use crate::utils::{output_size, ResizeEdge};
 use crate::window::ResolvedWindowRules;
 
 pub mod focus_ring;
 pub mod monitor;
+pub mod tab_indicator;
 pub mod tile;
 pub mod workspace;
 
@@ -64,9 +72,19 @@ niri_render_elements! {
     }
 }
 
+niri_render_elements! {
+    InteractiveMoveRenderElement<R> => {
+        Tile = TileRenderElement<R>,
+        InsertHint = SolidColorRenderElement,
+    }
+}
+
+/// Distance the pointer has to move before an interactive move lifts the window.
+const INTERACTIVE_MOVE_START_THRESHOLD: f64 = 16.;
+
 pub trait LayoutElement {
     /// Type that can be used as a unique ID of this element.
-    type Id: PartialEq;
+    type Id: PartialEq + Debug;
 
     /// Unique ID of this element.
     fn id(&self) -> &Self::Id;
@@ -135,8 +153,18 @@ pub trait LayoutElement {
 pub struct Layout<W: LayoutElement> {
     /// Monitors and workspaes in the layout.
     monitor_set: MonitorSet<W>,
+    /// Ongoing interactive move of a window with the pointer.
+    interactive_move: Option<InteractiveMoveState<W>>,
+    /// Ongoing interactive resize of a window with the pointer.
+    interactive_resize: Option<InteractiveResizeData<W>>,
+    /// Windows hidden in the scratchpad, in the order they were stashed.
+    ///
+    /// They are not part of any workspace until summoned.
+    scratchpad: Vec<ScratchpadWindow<W>>,
     /// Configurable properties of the layout.
     options: Rc<Options>,
+    /// Properties of the layout on outputs that override them, by output name.
+    output_options: HashMap<String, Rc<Options>>,
 }
 
 #[derive(Debug)]
@@ -157,7 +185,59 @@ enum MonitorSet<W: LayoutElement> {
     },
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
+enum InteractiveMoveState<W: LayoutElement> {
+    /// The pointer was pressed, but hasn't moved far enough to lift the window yet.
+    Starting {
+        window_id: W::Id,
+        /// Pointer movement accumulated so far.
+        pointer_delta: Point<f64, Logical>,
+        /// Position of the pointer within the tile, relative to the tile size.
+        pointer_ratio_within_tile: (f64, f64),
+    },
+    /// The window is lifted out of the layout and follows the pointer.
+    Moving(InteractiveMoveData<W>),
+}
+
+#[derive(Debug)]
+struct InteractiveMoveData<W: LayoutElement> {
+    /// The lifted tile.
+    tile: Tile<W>,
+    /// Where the window was before it was lifted.
+    placement: WindowPlacement,
+    /// Output that the pointer is on.
+    output: Output,
+    /// Position of the pointer within the output.
+    pointer_pos_within_output: Point<f64, Logical>,
+    /// Position of the pointer within the tile, relative to the tile size.
+    pointer_ratio_within_tile: (f64, f64),
+    /// Buffer for the hint showing where the window will be inserted.
+    insert_hint: SolidColorBuffer,
+    /// Area of the insert hint within the output, if it should be shown.
+    insert_hint_area: Option<Rectangle<i32, Logical>>,
+}
+
+#[derive(Debug)]
+struct InteractiveResizeData<W: LayoutElement> {
+    window_id: W::Id,
+    /// Edges being dragged.
+    edges: ResizeEdge,
+    /// Window size when the resize started.
+    original_window_size: Size<i32, Logical>,
+    /// Window size that was last requested by the resize.
+    last_window_size: Size<i32, Logical>,
+    /// Pointer movement accumulated so far.
+    pointer_delta: Point<f64, Logical>,
+}
+
+#[derive(Debug)]
+struct ScratchpadWindow<W: LayoutElement> {
+    window: W,
+    /// Size of the window when it was stashed, restored when it's summoned.
+    size: Size<i32, Logical>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Options {
     /// Padding around windows in logical pixels.
     pub gaps: i32,
@@ -170,6 +250,8 @@ pub struct Options {
     pub preset_widths: Vec<ColumnWidth>,
     /// Initial width for new columns.
     pub default_width: Option<ColumnWidth>,
+    /// Window heights that `toggle_window_height()` switches between.
+    pub preset_window_heights: Vec<PresetSize>,
     pub animations: niri_config::Animations,
 }
 
@@ -187,6 +269,11 @@ impl Default for Options {
                 ColumnWidth::Proportion(2. / 3.),
             ],
             default_width: None,
+            preset_window_heights: vec![
+                PresetSize::Proportion(1. / 3.),
+                PresetSize::Proportion(0.5),
+                PresetSize::Proportion(2. / 3.),
+            ],
             animations: Default::default(),
         }
     }
@@ -195,17 +282,6 @@ impl Default for Options {
 impl Options {
     fn from_config(config: &Config) -> Self {
         let layout = &config.layout;
-        let preset_column_widths = &layout.preset_column_widths;
-
-        let preset_widths = if preset_column_widths.is_empty() {
-            Options::default().preset_widths
-        } else {
-            preset_column_widths
-                .iter()
-                .copied()
-                .map(ColumnWidth::from)
-                .collect()
-        };
 
         // Missing default_column_width maps to Some(ColumnWidth::Proportion(0.5)),
         // while present, but empty, maps to None.
@@ -221,27 +297,103 @@ impl Options {
             focus_ring: layout.focus_ring,
             border: layout.border,
             center_focused_column: layout.center_focused_column,
-            preset_widths,
+            preset_widths: preset_widths_from_config(&layout.preset_column_widths),
             default_width,
+            preset_window_heights: preset_heights_from_config(&layout.preset_window_heights),
             animations: config.animations,
         }
     }
+
+    /// Returns a copy of these options with the set fields of the override applied.
+    fn with_override(&self, layout: &LayoutOverride) -> Self {
+        let mut options = self.clone();
+
+        if let Some(gaps) = layout.gaps {
+            options.gaps = gaps.into();
+        }
+        if let Some(struts) = layout.struts {
+            options.struts = struts;
+        }
+        if let Some(center) = layout.center_focused_column {
+            options.center_focused_column = center;
+        }
+        if let Some(widths) = &layout.preset_column_widths {
+            options.preset_widths = preset_widths_from_config(widths);
+        }
+        // Present, but empty, default_column_width maps to None.
+        if let Some(width) = &layout.default_column_width {
+            options.default_width = width.0.map(ColumnWidth::from);
+        }
+        if let Some(heights) = &layout.preset_window_heights {
+            options.preset_window_heights = preset_heights_from_config(heights);
+        }
+
+        options
+    }
+}
+
+fn preset_widths_from_config(presets: &[PresetSize]) -> Vec<ColumnWidth> {
+    if presets.is_empty() {
+        Options::default().preset_widths
+    } else {
+        presets.iter().copied().map(ColumnWidth::from).collect()
+    }
+}
+
+fn preset_heights_from_config(presets: &[PresetSize]) -> Vec<PresetSize> {
+    if presets.is_empty() {
+        Options::default().preset_window_heights
+    } else {
+        presets.to_vec()
+    }
+}
+
+/// Computes the layout options of every output that has a layout override in the config.
+fn output_options_from_config(config: &Config, options: &Options) -> HashMap<String, Rc<Options>> {
+    config
+        .outputs
+        .iter()
+        .filter_map(|output| {
+            let layout = output.layout.as_ref()?;
+            Some((output.name.clone(), Rc::new(options.with_override(layout))))
+        })
+        .collect()
 }
 
 impl<W: LayoutElement> Layout<W> {
     pub fn new(config: &Config) -> Self {
-        Self::with_options(Options::from_config(config))
+        let mut layout = Self::with_options(Options::from_config(config));
+        layout.output_options = output_options_from_config(config, &layout.options);
+
+        for ws_config in &config.workspaces {
+            layout.ensure_named_workspace(ws_config);
+        }
+
+        layout
     }
 
     pub fn with_options(options: Options) -> Self {
         Self {
             monitor_set: MonitorSet::NoOutputs { workspaces: vec![] },
+            interactive_move: None,
+            interactive_resize: None,
+            scratchpad: vec![],
             options: Rc::new(options),
+            output_options: HashMap::new(),
         }
     }
 
+    /// Returns the layout options to use on this output.
+    fn options_for_output(&self, output: &Output) -> Rc<Options> {
+        self.output_options
+            .get(&output.name())
+            .unwrap_or(&self.options)
+            .clone()
+    }
+
     pub fn add_output(&mut self, output: Output) {
         let id = OutputId::new(&output);
+        let options = self.options_for_output(&output);
 
         self.monitor_set = match mem::take(&mut self.monitor_set) {
             MonitorSet::Normal {
@@ -269,7 +421,7 @@ impl<W: LayoutElement> Layout<W> {
                         // The user could've closed a window while remaining on this workspace, on
                         // another monitor. However, we will add an empty workspace in the end
                         // instead.
-                        if ws.has_windows() {
+                        if ws.has_windows_or_name() {
                             workspaces.push(ws);
                         }
 
@@ -288,13 +440,15 @@ impl<W: LayoutElement> Layout<W> {
                 workspaces.reverse();
 
                 // Make sure there's always an empty workspace.
-                workspaces.push(Workspace::new(output.clone(), self.options.clone()));
+                workspaces.push(Workspace::new(output.clone(), options.clone()));
 
                 for ws in &mut workspaces {
+                    // Update the options first since the working area depends on the struts.
+                    ws.update_config(options.clone());
                     ws.set_output(Some(output.clone()));
                 }
 
-                monitors.push(Monitor::new(output, workspaces, self.options.clone()));
+                monitors.push(Monitor::new(output, workspaces, options));
                 MonitorSet::Normal {
                     monitors,
                     primary_idx,
@@ -303,13 +457,14 @@ impl<W: LayoutElement> Layout<W> {
             }
             MonitorSet::NoOutputs { mut workspaces } => {
                 // We know there are no empty workspaces there, so add one.
-                workspaces.push(Workspace::new(output.clone(), self.options.clone()));
+                workspaces.push(Workspace::new(output.clone(), options.clone()));
 
                 for workspace in &mut workspaces {
+                    workspace.update_config(options.clone());
                     workspace.set_output(Some(output.clone()));
                 }
 
-                let monitor = Monitor::new(output, workspaces, self.options.clone());
+                let monitor = Monitor::new(output, workspaces, options);
 
                 MonitorSet::Normal {
                     monitors: vec![monitor],
@@ -336,10 +491,11 @@ impl<W: LayoutElement> Layout<W> {
 
                 for ws in &mut workspaces {
                     ws.set_output(None);
+                    ws.update_config(self.options.clone());
                 }
 
                 // Get rid of empty workspaces.
-                workspaces.retain(|ws| ws.has_windows());
+                workspaces.retain(|ws| ws.has_windows_or_name());
 
                 if monitors.is_empty() {
                     // Removed the last monitor.
@@ -359,6 +515,7 @@ impl<W: LayoutElement> Layout<W> {
 
                     let primary = &mut monitors[primary_idx];
                     for ws in &mut workspaces {
+                        ws.update_config(primary.options.clone());
                         ws.set_output(Some(primary.output.clone()));
                     }
 
@@ -395,8 +552,7 @@ impl<W: LayoutElement> Layout<W> {
         workspace_idx: usize,
         window: W,
         activate: bool,
-        width: ColumnWidth,
-        is_full_width: bool,
+        placement: WindowPlacement,
     ) {
         let MonitorSet::Normal {
             monitors,
@@ -407,7 +563,7 @@ impl<W: LayoutElement> Layout<W> {
             panic!()
         };
 
-        monitors[monitor_idx].add_window(workspace_idx, window, activate, width, is_full_width);
+        monitors[monitor_idx].add_window_with_placement(workspace_idx, window, activate, placement);
 
         if activate {
             *active_monitor_idx = monitor_idx;
@@ -446,12 +602,7 @@ impl<W: LayoutElement> Layout<W> {
         width: Option<ColumnWidth>,
         is_full_width: bool,
     ) -> Option<&Output> {
-        let mut width = width.unwrap_or_else(|| ColumnWidth::Fixed(window.size().w));
-        if let ColumnWidth::Fixed(w) = &mut width {
-            if !self.options.border.off {
-                *w += self.options.border.width as i32 * 2;
-            }
-        }
+        let placement = self.new_window_placement(&window, width, is_full_width);
 
         match &mut self.monitor_set {
             MonitorSet::Normal {
@@ -468,12 +619,11 @@ impl<W: LayoutElement> Layout<W> {
                     activate = false;
                 }
 
-                mon.add_window(
+                mon.add_window_with_placement(
                     mon.active_workspace_idx,
                     window,
                     activate,
-                    width,
-                    is_full_width,
+                    placement,
                 );
                 Some(&mon.output)
             }
@@ -484,7 +634,13 @@ impl<W: LayoutElement> Layout<W> {
                     workspaces.push(Workspace::new_no_outputs(self.options.clone()));
                     &mut workspaces[0]
                 };
-                ws.add_window(window, true, width, is_full_width);
+                match placement {
+                    WindowPlacement::Column {
+                        width,
+                        is_full_width,
+                    } => ws.add_window(window, true, width, is_full_width),
+                    WindowPlacement::Floating => ws.add_floating_window(window, true),
+                }
                 None
             }
         }
@@ -502,12 +658,7 @@ impl<W: LayoutElement> Layout<W> {
         width: Option<ColumnWidth>,
         is_full_width: bool,
     ) -> Option<&Output> {
-        let mut width = width.unwrap_or_else(|| ColumnWidth::Fixed(window.size().w));
-        if let ColumnWidth::Fixed(w) = &mut width {
-            if !self.options.border.off {
-                *w += self.options.border.width as i32 * 2;
-            }
-        }
+        let placement = self.new_window_placement(&window, width, is_full_width);
 
         match &mut self.monitor_set {
             MonitorSet::Normal { monitors, .. } => {
@@ -516,7 +667,7 @@ impl<W: LayoutElement> Layout<W> {
                     .find(|mon| mon.workspaces.iter().any(|ws| ws.has_window(right_of)))
                     .unwrap();
 
-                mon.add_window_right_of(right_of, window, width, is_full_width);
+                mon.add_window_right_of(right_of, window, placement);
                 Some(&mon.output)
             }
             MonitorSet::NoOutputs { workspaces } => {
@@ -524,26 +675,33 @@ impl<W: LayoutElement> Layout<W> {
                     .iter_mut()
                     .find(|ws| ws.has_window(right_of))
                     .unwrap();
-                ws.add_window_right_of(right_of, window, width, is_full_width);
+                match placement {
+                    WindowPlacement::Column {
+                        width,
+                        is_full_width,
+                    } => ws.add_window_right_of(right_of, window, width, is_full_width),
+                    WindowPlacement::Floating => {
+                        let activate = ws.active_window().is_some_and(|win| win.id() == right_of);
+                        ws.add_floating_window(window, activate);
+                    }
+                }
                 None
             }
         }
     }
 
     /// Adds a new window to the layout on a specific output.
+    ///
+    /// If `workspace_idx` is set, the window is added to that workspace instead of the active one.
     pub fn add_window_on_output(
         &mut self,
         output: &Output,
+        workspace_idx: Option<usize>,
         window: W,
         width: Option<ColumnWidth>,
         is_full_width: bool,
     ) {
-        let mut width = width.unwrap_or_else(|| ColumnWidth::Fixed(window.size().w));
-        if let ColumnWidth::Fixed(w) = &mut width {
-            if !self.options.border.off {
-                *w += self.options.border.width as i32 * 2;
-            }
-        }
+        let placement = self.new_window_placement(&window, width, is_full_width);
 
         let MonitorSet::Normal {
             monitors,
@@ -560,8 +718,14 @@ impl<W: LayoutElement> Layout<W> {
             .find(|(_, mon)| mon.output == *output)
             .unwrap();
 
+        let workspace_idx = workspace_idx
+            .map(|idx| min(idx, mon.workspaces.len() - 1))
+            .unwrap_or(mon.active_workspace_idx);
+
+        // Don't switch away from the active workspace to a window opening in the background.
+        let mut activate = workspace_idx == mon.active_workspace_idx;
+
         // Don't steal focus from an active fullscreen window.
-        let mut activate = true;
         let ws = &mon.workspaces[mon.active_workspace_idx];
         if mon_idx == *active_monitor_idx
             && !ws.columns.is_empty()
@@ -570,16 +734,57 @@ impl<W: LayoutElement> Layout<W> {
             activate = false;
         }
 
-        mon.add_window(
-            mon.active_workspace_idx,
-            window,
-            activate,
+        mon.add_window_with_placement(workspace_idx, window, activate, placement);
+    }
+
+    /// Computes where to put a new window.
+    fn new_window_placement(
+        &self,
+        window: &W,
+        width: Option<ColumnWidth>,
+        is_full_width: bool,
+    ) -> WindowPlacement {
+        if window.rules().open_floating == Some(true) && !window.is_pending_fullscreen() {
+            return WindowPlacement::Floating;
+        }
+
+        let mut width = width.unwrap_or_else(|| ColumnWidth::Fixed(window.size().w));
+        if let ColumnWidth::Fixed(w) = &mut width {
+            if !self.options.border.off {
+                *w += self.options.border.width as i32 * 2;
+            }
+        }
+
+        WindowPlacement::Column {
             width,
             is_full_width,
-        );
+        }
     }
 
     pub fn remove_window(&mut self, window: &W::Id) -> Option<W> {
+        if let Some(idx) = self.scratchpad_idx(window) {
+            return Some(self.scratchpad.remove(idx).window);
+        }
+
+        if self
+            .interactive_resize
+            .as_ref()
+            .is_some_and(|resize| &resize.window_id == window)
+        {
+            self.interactive_resize = None;
+        }
+
+        if let Some(state) = &self.interactive_move {
+            if state.window_id() == window {
+                // If the window wasn't lifted yet, this just cancels the move.
+                if let Some(InteractiveMoveState::Moving(data)) = self.interactive_move.take() {
+                    let window = data.tile.into_window();
+                    window.output_leave(&data.output);
+                    return Some(window);
+                }
+            }
+        }
+
         let mut rv = None;
 
         match &mut self.monitor_set {
@@ -590,7 +795,7 @@ impl<W: LayoutElement> Layout<W> {
                             rv = Some(ws.remove_window(window));
 
                             // Clean up empty workspaces that are not active and not last.
-                            if !ws.has_windows()
+                            if !ws.has_windows_or_name()
                                 && idx != mon.active_workspace_idx
                                 && idx != mon.workspaces.len() - 1
                                 && mon.workspace_switch.is_none()
@@ -613,7 +818,7 @@ impl<W: LayoutElement> Layout<W> {
                         rv = Some(ws.remove_window(window));
 
                         // Clean up empty workspaces.
-                        if !ws.has_windows() {
+                        if !ws.has_windows_or_name() {
                             workspaces.remove(idx);
                         }
 
@@ -627,6 +832,18 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn update_window(&mut self, window: &W::Id) {
+        // Hidden windows have nothing to update.
+        if self.scratchpad_idx(window).is_some() {
+            return;
+        }
+
+        if let Some(data) = self.interactive_moved_mut() {
+            if data.tile.window().id() == window {
+                data.tile.update_window();
+                return;
+            }
+        }
+
         match &mut self.monitor_set {
             MonitorSet::Normal { monitors, .. } => {
                 for mon in monitors {
@@ -650,6 +867,12 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn find_window_and_output(&self, wl_surface: &WlSurface) -> Option<(&W, &Output)> {
+        if let Some(data) = self.interactive_moved() {
+            if data.tile.window().is_wl_surface(wl_surface) {
+                return Some((data.tile.window(), &data.output));
+            }
+        }
+
         if let MonitorSet::Normal { monitors, .. } = &self.monitor_set {
             for mon in monitors {
                 for ws in &mon.workspaces {
@@ -667,6 +890,12 @@ impl<W: LayoutElement> Layout<W> {
         &mut self,
         wl_surface: &WlSurface,
     ) -> Option<(&mut W, Option<&Output>)> {
+        if let Some(InteractiveMoveState::Moving(data)) = &mut self.interactive_move {
+            if data.tile.window().is_wl_surface(wl_surface) {
+                return Some((data.tile.window_mut(), Some(&data.output)));
+            }
+        }
+
         match &mut self.monitor_set {
             MonitorSet::Normal { monitors, .. } => {
                 for mon in monitors {
@@ -694,20 +923,16 @@ impl<W: LayoutElement> Layout<W> {
             MonitorSet::Normal { monitors, .. } => {
                 for mon in monitors {
                     for ws in &mon.workspaces {
-                        for col in &ws.columns {
-                            if let Some(idx) = col.position(window) {
-                                return Some(col.window_y(idx));
-                            }
+                        if let Some(y) = ws.window_y(window) {
+                            return Some(y);
                         }
                     }
                 }
             }
             MonitorSet::NoOutputs { workspaces, .. } => {
                 for ws in workspaces {
-                    for col in &ws.columns {
-                        if let Some(idx) = col.position(window) {
-                            return Some(col.window_y(idx));
-                        }
+                    if let Some(y) = ws.window_y(window) {
+                        return Some(y);
                     }
                 }
             }
@@ -726,9 +951,9 @@ impl<W: LayoutElement> Layout<W> {
         for mon in monitors {
             if &mon.output == output {
                 let view_size = output_size(output);
-                let working_area = compute_working_area(output, self.options.struts);
 
                 for ws in &mut mon.workspaces {
+                    let working_area = compute_working_area(output, ws.options.struts);
                     ws.set_view_size(view_size, working_area);
                     ws.update_output_scale_transform();
                 }
@@ -810,6 +1035,10 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn active_window(&self) -> Option<(&W, &Output)> {
+        if let Some(data) = self.interactive_moved() {
+            return Some((data.tile.window(), &data.output));
+        }
+
         let MonitorSet::Normal {
             monitors,
             active_monitor_idx,
@@ -821,13 +1050,41 @@ impl<W: LayoutElement> Layout<W> {
 
         let mon = &monitors[*active_monitor_idx];
         let ws = &mon.workspaces[mon.active_workspace_idx];
+        Some((ws.active_window()?, &mon.output))
+    }
 
-        if ws.columns.is_empty() {
-            return None;
-        }
+    pub fn workspaces(
+        &self,
+    ) -> impl Iterator<Item = (Option<&Monitor<W>>, usize, &Workspace<W>)> + '_ {
+        let (monitors, workspaces) = match &self.monitor_set {
+            MonitorSet::Normal { monitors, .. } => (&monitors[..], &[][..]),
+            MonitorSet::NoOutputs { workspaces } => (&[][..], &workspaces[..]),
+        };
+
+        let in_monitors = monitors.iter().flat_map(|mon| {
+            mon.workspaces
+                .iter()
+                .enumerate()
+                .map(move |(idx, ws)| (Some(mon), idx, ws))
+        });
+        let without_monitors = workspaces
+            .iter()
+            .enumerate()
+            .map(|(idx, ws)| (None, idx, ws));
+
+        in_monitors.chain(without_monitors)
+    }
 
-        let col = &ws.columns[ws.active_column_idx];
-        Some((col.tiles[col.active_tile_idx].window(), &mon.output))
+    fn workspaces_mut(&mut self) -> impl Iterator<Item = &mut Workspace<W>> + '_ {
+        let (monitors, workspaces) = match &mut self.monitor_set {
+            MonitorSet::Normal { monitors, .. } => (&mut monitors[..], &mut [][..]),
+            MonitorSet::NoOutputs { workspaces } => (&mut [][..], &mut workspaces[..]),
+        };
+
+        monitors
+            .iter_mut()
+            .flat_map(|mon| mon.workspaces.iter_mut())
+            .chain(workspaces)
     }
 
     pub fn windows_for_output(&self, output: &Output) -> impl Iterator<Item = &W> + '_ {
@@ -835,11 +1092,27 @@ impl<W: LayoutElement> Layout<W> {
             panic!()
         };
 
+        let moving = self
+            .interactive_moved()
+            .filter(|data| &data.output == output)
+            .map(|data| data.tile.window());
+
         let mon = monitors.iter().find(|mon| &mon.output == output).unwrap();
-        mon.workspaces.iter().flat_map(|ws| ws.windows())
+        mon.workspaces
+            .iter()
+            .flat_map(|ws| ws.windows())
+            .chain(moving)
     }
 
     pub fn with_windows(&self, mut f: impl FnMut(&W, Option<&Output>)) {
+        if let Some(data) = self.interactive_moved() {
+            f(data.tile.window(), Some(&data.output));
+        }
+
+        for hidden in &self.scratchpad {
+            f(&hidden.window, None);
+        }
+
         match &self.monitor_set {
             MonitorSet::Normal { monitors, .. } => {
                 for mon in monitors {
@@ -861,6 +1134,14 @@ impl<W: LayoutElement> Layout<W> {
     }
 
     pub fn with_windows_mut(&mut self, mut f: impl FnMut(&mut W, Option<&Output>)) {
+        if let Some(InteractiveMoveState::Moving(data)) = &mut self.interactive_move {
+            f(data.tile.window_mut(), Some(&data.output));
+        }
+
+        for hidden in &mut self.scratchpad {
+            f(&mut hidden.window, None);
+        }
+
         match &mut self.monitor_set {
             MonitorSet::Normal { monitors, .. } => {
                 for mon in monitors {
@@ -881,6 +1162,14 @@ impl<W: LayoutElement> Layout<W> {
         }
     }
 
+    /// Returns the interactively moved window, the output that it is on, and whether it came from
+    /// the floating layout.
+    pub fn interactive_moved_window(&self) -> Option<(&W, &Output, bool)> {
+        let data = self.interactive_moved()?;
+        let is_floating = data.placement == WindowPlacement::Floating;
+        Some((data.tile.window(), &data.output, is_floating))
+    }
+
     fn active_monitor(&mut self) -> Option<&mut Monitor<W>> {
         let MonitorSet::Normal {
             monitors,
@@ -894,6 +1183,24 @@ impl<W: LayoutElement> Layout<W> {
         Some(&mut monitors[*active_monitor_idx])
     }
 
+    /// Returns the workspace containing the window, or the active workspace if `None`.
+    fn workspace_for_window(&mut self, window: Option<&W::Id>) -> Option<&mut Workspace<W>> {
+        let Some(window) = window else {
+            let monitor = self.active_monitor()?;
+            return Some(monitor.active_workspace());
+        };
+
+        match &mut self.monitor_set {
+            MonitorSet::Normal { monitors, .. } => monitors
+                .iter_mut()
+                .flat_map(|mon| mon.workspaces.iter_mut())
+                .find(|ws| ws.has_window(window)),
+            MonitorSet::NoOutputs { workspaces } => {
+                workspaces.iter_mut().find(|ws| ws.has_window(window))
+            }
+        }
+    }
+
     pub fn active_monitor_ref(&self) -> Option<&Monitor<W>> {
         let MonitorSet::Normal {
             monitors,
@@ -953,6 +1260,13 @@ impl<W: LayoutElement> Layout<W> {
         monitor.move_column_to_last();
     }
 
+    pub fn move_column_to_index(&mut self, index: usize) {
+        let Some(monitor) = self.active_monitor() else {
+            return;
+        };
+        monitor.move_column_to_index(index);
+    }
+
     pub fn move_down(&mut self) {
         let Some(monitor) = self.active_monitor() else {
             return;
@@ -981,18 +1295,18 @@ impl<W: LayoutElement> Layout<W> {
         monitor.move_up_or_to_workspace_up();
     }
 
-    pub fn consume_or_expel_window_left(&mut self) {
-        let Some(monitor) = self.active_monitor() else {
+    pub fn consume_or_expel_window_left(&mut self, window: Option<&W::Id>) {
+        let Some(workspace) = self.workspace_for_window(window) else {
             return;
         };
-        monitor.consume_or_expel_window_left();
+        workspace.consume_or_expel_window_left(window);
     }
 
-    pub fn consume_or_expel_window_right(&mut self) {
-        let Some(monitor) = self.active_monitor() else {
+    pub fn consume_or_expel_window_right(&mut self, window: Option<&W::Id>) {
+        let Some(workspace) = self.workspace_for_window(window) else {
             return;
         };
-        monitor.consume_or_expel_window_right();
+        workspace.consume_or_expel_window_right(window);
     }
 
     pub fn focus_left(&mut self) {
@@ -1023,6 +1337,13 @@ impl<W: LayoutElement> Layout<W> {
         monitor.focus_column_last();
     }
 
+    pub fn focus_column(&mut self, index: usize) {
+        let Some(monitor) = self.active_monitor() else {
+            return;
+        };
+        monitor.focus_column(index);
+    }
+
     pub fn focus_down(&mut self) {
         let Some(monitor) = self.active_monitor() else {
             return;
@@ -1051,6 +1372,190 @@ impl<W: LayoutElement> Layout<W> {
         monitor.focus_window_or_workspace_up();
     }
 
+    /// Focuses the column to the left, or the output if the leftmost column is already focused.
+    ///
+    /// Returns `true` if the focus moved to the output.
+    pub fn focus_column_left_or_output(&mut self, output: &Output) -> bool {
+        let Some(monitor) = self.active_monitor() else {
+            return false;
+        };
+
+        let workspace = monitor.active_workspace_ref();
+        if workspace.is_floating_focused() || workspace.active_column_idx > 0 {
+            monitor.focus_left();
+            return false;
+        }
+
+        self.focus_output(output);
+        true
+    }
+
+    /// Focuses the column to the right, or the output if the rightmost column is already focused.
+    ///
+    /// Returns `true` if the focus moved to the output.
+    pub fn focus_column_right_or_output(&mut self, output: &Output) -> bool {
+        let Some(monitor) = self.active_monitor() else {
+            return false;
+        };
+
+        let workspace = monitor.active_workspace_ref();
+        if workspace.is_floating_focused()
+            || workspace.active_column_idx + 1 < workspace.columns.len()
+        {
+            monitor.focus_right();
+            return false;
+        }
+
+        self.focus_output(output);
+        true
+    }
+
+    /// Focuses the window below, or the output if the bottom window is already focused.
+    ///
+    /// Returns `true` if the focus moved to the output.
+    pub fn focus_window_down_or_output(&mut self, output: &Output) -> bool {
+        let Some(monitor) = self.active_monitor() else {
+            return false;
+        };
+
+        let workspace = monitor.active_workspace_ref();
+        if workspace.is_floating_focused()
+            || workspace
+                .columns
+                .get(workspace.active_column_idx)
+                .is_some_and(|column| column.active_tile_idx + 1 < column.tiles.len())
+        {
+            monitor.focus_down();
+            return false;
+        }
+
+        self.focus_output(output);
+        true
+    }
+
+    /// Focuses the window above, or the output if the top window is already focused.
+    ///
+    /// Returns `true` if the focus moved to the output.
+    pub fn focus_window_up_or_output(&mut self, output: &Output) -> bool {
+        let Some(monitor) = self.active_monitor() else {
+            return false;
+        };
+
+        let workspace = monitor.active_workspace_ref();
+        if workspace.is_floating_focused()
+            || workspace
+                .columns
+                .get(workspace.active_column_idx)
+                .is_some_and(|column| column.active_tile_idx > 0)
+        {
+            monitor.focus_up();
+            return false;
+        }
+
+        self.focus_output(output);
+        true
+    }
+
+    /// Moves the column to the left, or to the output if it is already the leftmost one.
+    ///
+    /// Returns `true` if the column moved to the output, which then gets focused.
+    pub fn move_column_left_or_to_output(&mut self, output: &Output) -> bool {
+        let Some(monitor) = self.active_monitor() else {
+            return false;
+        };
+
+        let workspace = monitor.active_workspace_ref();
+        if workspace.is_floating_focused() || workspace.active_column_idx > 0 {
+            monitor.move_left();
+            return false;
+        }
+        if workspace.columns.is_empty() {
+            return false;
+        }
+
+        self.move_column_to_output(output, None);
+        self.focus_output(output);
+        true
+    }
+
+    /// Moves the column to the right, or to the output if it is already the rightmost one.
+    ///
+    /// Returns `true` if the column moved to the output, which then gets focused.
+    pub fn move_column_right_or_to_output(&mut self, output: &Output) -> bool {
+        let Some(monitor) = self.active_monitor() else {
+            return false;
+        };
+
+        let workspace = monitor.active_workspace_ref();
+        if workspace.is_floating_focused()
+            || workspace.active_column_idx + 1 < workspace.columns.len()
+        {
+            monitor.move_right();
+            return false;
+        }
+        if workspace.columns.is_empty() {
+            return false;
+        }
+
+        self.move_column_to_output(output, None);
+        self.focus_output(output);
+        true
+    }
+
+    /// Moves the window down, or to the output if it is already at the bottom of its column.
+    ///
+    /// Returns `true` if the window moved to the output, which then gets focused.
+    pub fn move_down_or_to_output(&mut self, output: &Output) -> bool {
+        let Some(monitor) = self.active_monitor() else {
+            return false;
+        };
+
+        let workspace = monitor.active_workspace_ref();
+        if workspace.is_floating_focused()
+            || workspace
+                .columns
+                .get(workspace.active_column_idx)
+                .is_some_and(|column| column.active_tile_idx + 1 < column.tiles.len())
+        {
+            monitor.move_down();
+            return false;
+        }
+        if workspace.columns.is_empty() {
+            return false;
+        }
+
+        self.move_to_output(None, output, None);
+        self.focus_output(output);
+        true
+    }
+
+    /// Moves the window up, or to the output if it is already at the top of its column.
+    ///
+    /// Returns `true` if the window moved to the output, which then gets focused.
+    pub fn move_up_or_to_output(&mut self, output: &Output) -> bool {
+        let Some(monitor) = self.active_monitor() else {
+            return false;
+        };
+
+        let workspace = monitor.active_workspace_ref();
+        if workspace.is_floating_focused()
+            || workspace
+                .columns
+                .get(workspace.active_column_idx)
+                .is_some_and(|column| column.active_tile_idx > 0)
+        {
+            monitor.move_up();
+            return false;
+        }
+        if workspace.columns.is_empty() {
+            return false;
+        }
+
+        self.move_to_output(None, output, None);
+        self.focus_output(output);
+        true
+    }
+
     pub fn move_to_workspace_up(&mut self) {
         let Some(monitor) = self.active_monitor() else {
             return;
@@ -1065,11 +1570,22 @@ impl<W: LayoutElement> Layout<W> {
         monitor.move_to_workspace_down();
     }
 
-    pub fn move_to_workspace(&mut self, idx: usize) {
-        let Some(monitor) = self.active_monitor() else {
+    pub fn move_to_workspace(&mut self, window: Option<&W::Id>, idx: usize) {
+        let monitor = if let Some(window) = window {
+            let MonitorSet::Normal { monitors, .. } = &mut self.monitor_set else {
+                return;
+            };
+
+            monitors
+                .iter_mut()
+                .find(|mon| mon.workspaces.iter().any(|ws| ws.has_window(window)))
+        } else {
+            self.active_monitor()
+        };
+        let Some(monitor) = monitor else {
             return;
         };
-        monitor.move_to_workspace(idx);
+        monitor.move_to_workspace(window, idx);
     }
 
     pub fn move_column_to_workspace_up(&mut self) {
@@ -1142,24 +1658,49 @@ impl<W: LayoutElement> Layout<W> {
         monitor.expel_from_column();
     }
 
-    pub fn center_column(&mut self) {
+    pub fn swap_window_left(&mut self) {
         let Some(monitor) = self.active_monitor() else {
             return;
         };
-        monitor.center_column();
+        monitor.swap_window_left();
     }
 
-    pub fn focus(&self) -> Option<&W> {
-        let MonitorSet::Normal {
-            monitors,
-            active_monitor_idx,
-            ..
-        } = &self.monitor_set
-        else {
-            return None;
+    pub fn swap_window_right(&mut self) {
+        let Some(monitor) = self.active_monitor() else {
+            return;
         };
-
-        monitors[*active_monitor_idx].focus()
+        monitor.swap_window_right();
+    }
+
+    pub fn center_column(&mut self) {
+        let Some(monitor) = self.active_monitor() else {
+            return;
+        };
+        monitor.center_column();
+    }
+
+    pub fn toggle_column_tabbed_display(&mut self) {
+        let Some(monitor) = self.active_monitor() else {
+            return;
+        };
+        monitor.toggle_column_tabbed_display();
+    }
+
+    pub fn focus(&self) -> Option<&W> {
+        if let Some(data) = self.interactive_moved() {
+            return Some(data.tile.window());
+        }
+
+        let MonitorSet::Normal {
+            monitors,
+            active_monitor_idx,
+            ..
+        } = &self.monitor_set
+        else {
+            return None;
+        };
+
+        monitors[*active_monitor_idx].focus()
     }
 
     /// Returns the window under the cursor and the position of its toplevel surface within the
@@ -1190,6 +1731,16 @@ impl<W: LayoutElement> Layout<W> {
 
         let mut seen_workspace_id = HashSet::new();
 
+        let mut seen_workspace_name = HashSet::new();
+        for (_, _, ws) in self.workspaces() {
+            if let Some(name) = &ws.name {
+                assert!(
+                    seen_workspace_name.insert(name.to_ascii_lowercase()),
+                    "workspace name must be unique"
+                );
+            }
+        }
+
         let (monitors, &primary_idx, &active_monitor_idx) = match &self.monitor_set {
             MonitorSet::Normal {
                 monitors,
@@ -1199,12 +1750,12 @@ impl<W: LayoutElement> Layout<W> {
             MonitorSet::NoOutputs { workspaces } => {
                 for workspace in workspaces {
                     assert!(
-                        workspace.has_windows(),
-                        "with no outputs there cannot be empty workspaces"
+                        workspace.has_windows_or_name(),
+                        "with no outputs there cannot be empty unnamed workspaces"
                     );
 
                     assert_eq!(
-                        workspace.options, self.options,
+                        workspace.base_options, self.options,
                         "workspace options must be synchronized with layout"
                     );
 
@@ -1231,7 +1782,8 @@ impl<W: LayoutElement> Layout<W> {
             assert!(monitor.active_workspace_idx < monitor.workspaces.len());
 
             assert_eq!(
-                monitor.options, self.options,
+                monitor.options,
+                self.options_for_output(&monitor.output),
                 "monitor options must be synchronized with layout"
             );
 
@@ -1271,18 +1823,18 @@ impl<W: LayoutElement> Layout<W> {
             }
 
             assert!(
-                monitor.workspaces.last().unwrap().columns.is_empty(),
-                "monitor must have an empty workspace in the end"
+                !monitor.workspaces.last().unwrap().has_windows_or_name(),
+                "monitor must have an empty unnamed workspace in the end"
             );
 
             // If there's no workspace switch in progress, there can't be any non-last non-active
-            // empty workspaces.
+            // empty unnamed workspaces.
             if monitor.workspace_switch.is_none() {
                 for (idx, ws) in monitor.workspaces.iter().enumerate().rev().skip(1) {
                     if idx != monitor.active_workspace_idx {
                         assert!(
-                            !ws.columns.is_empty(),
-                            "non-active workspace can't be empty except the last one"
+                            ws.has_windows_or_name(),
+                            "non-active workspace can't be empty and unnamed except the last one"
                         );
                     }
                 }
@@ -1293,8 +1845,8 @@ impl<W: LayoutElement> Layout<W> {
 
             for workspace in &monitor.workspaces {
                 assert_eq!(
-                    workspace.options, self.options,
-                    "workspace options must be synchronized with layout"
+                    workspace.base_options, monitor.options,
+                    "workspace options must be synchronized with monitor"
                 );
 
                 assert!(
@@ -1310,6 +1862,14 @@ impl<W: LayoutElement> Layout<W> {
     pub fn advance_animations(&mut self, current_time: Duration) {
         let _span = tracy_client::span!("Layout::advance_animations");
 
+        // The interactively moved window has the focus.
+        let is_moving = if let Some(data) = self.interactive_moved_mut() {
+            data.tile.advance_animations(current_time, true);
+            true
+        } else {
+            false
+        };
+
         match &mut self.monitor_set {
             MonitorSet::Normal {
                 monitors,
@@ -1317,7 +1877,8 @@ impl<W: LayoutElement> Layout<W> {
                 ..
             } => {
                 for (idx, mon) in monitors.iter_mut().enumerate() {
-                    mon.advance_animations(current_time, idx == *active_monitor_idx);
+                    let is_active = idx == *active_monitor_idx && !is_moving;
+                    mon.advance_animations(current_time, is_active);
                 }
             }
             MonitorSet::NoOutputs { workspaces, .. } => {
@@ -1331,20 +1892,153 @@ impl<W: LayoutElement> Layout<W> {
     pub fn update_config(&mut self, config: &Config) {
         let options = Rc::new(Options::from_config(config));
 
-        match &mut self.monitor_set {
+        if let Some(data) = self.interactive_moved_mut() {
+            data.tile.update_config(options.clone());
+        }
+
+        self.output_options = output_options_from_config(config, &options);
+        self.options = options;
+
+        let mut monitor_set = mem::take(&mut self.monitor_set);
+        match &mut monitor_set {
             MonitorSet::Normal { monitors, .. } => {
                 for mon in monitors {
-                    mon.update_config(options.clone());
+                    mon.update_config(self.options_for_output(&mon.output));
                 }
             }
             MonitorSet::NoOutputs { workspaces } => {
                 for ws in workspaces {
-                    ws.update_config(options.clone());
+                    ws.update_config(self.options.clone());
+                }
+            }
+        }
+        self.monitor_set = monitor_set;
+
+        // Workspaces removed from the config become regular workspaces.
+        let mut removed = vec![];
+        for (_, _, ws) in self.workspaces() {
+            if let Some(name) = &ws.name {
+                if !config
+                    .workspaces
+                    .iter()
+                    .any(|ws_config| ws_config.name.eq_ignore_ascii_case(name))
+                {
+                    removed.push(name.clone());
                 }
             }
         }
+        for name in removed {
+            self.unname_workspace(&name);
+        }
 
-        self.options = options;
+        for ws_config in &config.workspaces {
+            self.ensure_named_workspace(ws_config);
+        }
+
+        // Named workspaces could've had their layout overrides changed.
+        for ws in self.workspaces_mut() {
+            let layout = ws
+                .name
+                .as_ref()
+                .and_then(|name| {
+                    config
+                        .workspaces
+                        .iter()
+                        .find(|ws_config| ws_config.name.eq_ignore_ascii_case(name))
+                })
+                .and_then(|ws_config| ws_config.layout.clone());
+            ws.set_layout_override(layout.unwrap_or_default());
+        }
+    }
+
+    /// Creates a named workspace from the config, unless one with this name already exists.
+    ///
+    /// The workspace goes to its configured output if it's connected, and to the primary output
+    /// otherwise.
+    pub fn ensure_named_workspace(&mut self, ws_config: &WorkspaceConfig) {
+        if self.find_workspace_by_name(&ws_config.name).is_some() {
+            return;
+        }
+
+        let options = self.options.clone();
+        match &mut self.monitor_set {
+            MonitorSet::Normal {
+                monitors,
+                primary_idx,
+                ..
+            } => {
+                let mon_idx = ws_config
+                    .open_on_output
+                    .as_ref()
+                    .and_then(|name| monitors.iter().position(|mon| mon.output.name() == *name))
+                    .unwrap_or(*primary_idx);
+                let mon = &mut monitors[mon_idx];
+
+                let mut ws = Workspace::new(mon.output.clone(), mon.options.clone());
+                ws.name = Some(ws_config.name.clone());
+                if let Some(name) = &ws_config.open_on_output {
+                    ws.original_output = OutputId::from_name(name);
+                }
+                ws.set_layout_override(ws_config.layout.clone().unwrap_or_default());
+
+                mon.add_workspace(ws);
+            }
+            MonitorSet::NoOutputs { workspaces } => {
+                let mut ws = Workspace::new_no_outputs(options);
+                ws.name = Some(ws_config.name.clone());
+                if let Some(name) = &ws_config.open_on_output {
+                    ws.original_output = OutputId::from_name(name);
+                }
+                ws.set_layout_override(ws_config.layout.clone().unwrap_or_default());
+
+                workspaces.push(ws);
+            }
+        }
+    }
+
+    /// Turns the named workspace into a regular one, which is cleaned up once it's empty.
+    pub fn unname_workspace(&mut self, workspace_name: &str) {
+        match &mut self.monitor_set {
+            MonitorSet::Normal { monitors, .. } => {
+                for mon in monitors {
+                    if let Some(ws) = mon
+                        .workspaces
+                        .iter_mut()
+                        .find(|ws| ws.has_name(workspace_name))
+                    {
+                        ws.name = None;
+
+                        if mon.workspace_switch.is_none() {
+                            mon.clean_up_workspaces();
+                        }
+                        return;
+                    }
+                }
+            }
+            MonitorSet::NoOutputs { workspaces } => {
+                if let Some(ws) = workspaces.iter_mut().find(|ws| ws.has_name(workspace_name)) {
+                    ws.name = None;
+                }
+                workspaces.retain(|ws| ws.has_windows_or_name());
+            }
+        }
+    }
+
+    /// Finds the named workspace, along with its monitor and its index on that monitor.
+    pub fn find_workspace_by_name(
+        &self,
+        workspace_name: &str,
+    ) -> Option<(Option<&Monitor<W>>, usize, &Workspace<W>)> {
+        self.workspaces()
+            .find(|(_, _, ws)| ws.has_name(workspace_name))
+    }
+
+    /// Sets the runtime layout overrides of the active workspace.
+    pub fn set_workspace_layout(&mut self, layout: LayoutOverride) {
+        let Some(monitor) = self.active_monitor() else {
+            return;
+        };
+        monitor.set_workspace_layout(layout);
     }
 
     pub fn toggle_width(&mut self) {
@@ -1361,18 +2055,46 @@ impl<W: LayoutElement> Layout<W> {
         monitor.toggle_full_width();
     }
 
-    pub fn set_column_width(&mut self, change: SizeChange) {
-        let Some(monitor) = self.active_monitor() else {
+    pub fn set_column_width(&mut self, window: Option<&W::Id>, change: SizeChange) {
+        let Some(workspace) = self.workspace_for_window(window) else {
+            return;
+        };
+        workspace.set_column_width(window, change);
+    }
+
+    pub fn set_window_height(&mut self, window: Option<&W::Id>, change: SizeChange) {
+        let Some(workspace) = self.workspace_for_window(window) else {
+            return;
+        };
+        workspace.set_window_height(window, change);
+    }
+
+    pub fn toggle_window_height(&mut self, window: Option<&W::Id>) {
+        let Some(workspace) = self.workspace_for_window(window) else {
+            return;
+        };
+        workspace.toggle_window_height(window);
+    }
+
+    pub fn reset_window_height(&mut self, window: Option<&W::Id>) {
+        let Some(workspace) = self.workspace_for_window(window) else {
+            return;
+        };
+        workspace.reset_window_height(window);
+    }
+
+    pub fn toggle_window_floating(&mut self, window: Option<&W::Id>) {
+        let Some(workspace) = self.workspace_for_window(window) else {
             return;
         };
-        monitor.set_column_width(change);
+        workspace.toggle_window_floating(window);
     }
 
-    pub fn set_window_height(&mut self, change: SizeChange) {
+    pub fn switch_focus_floating_tiling(&mut self) {
         let Some(monitor) = self.active_monitor() else {
             return;
         };
-        monitor.set_window_height(change);
+        monitor.switch_focus_floating_tiling();
     }
 
     pub fn focus_output(&mut self, output: &Output) {
@@ -1391,7 +2113,48 @@ impl<W: LayoutElement> Layout<W> {
         }
     }
 
-    pub fn move_to_output(&mut self, output: &Output) {
+    /// Moves the window to the output, onto its active workspace or onto the workspace at
+    /// `target_ws_idx` if set.
+    pub fn move_to_output(
+        &mut self,
+        window: Option<&W::Id>,
+        output: &Output,
+        target_ws_idx: Option<usize>,
+    ) {
+        if let Some(window) = window {
+            let MonitorSet::Normal { monitors, .. } = &self.monitor_set else {
+                return;
+            };
+
+            let Some(placement) = monitors
+                .iter()
+                .flat_map(|mon| &mon.workspaces)
+                .find_map(|ws| ws.window_placement(window))
+            else {
+                return;
+            };
+
+            // Only follow the window if it was focused.
+            let activate = self.focus().map(|win| win.id()) == Some(window);
+
+            let window = self.remove_window(window).unwrap();
+
+            let MonitorSet::Normal { monitors, .. } = &self.monitor_set else {
+                unreachable!()
+            };
+            let new_idx = monitors
+                .iter()
+                .position(|mon| &mon.output == output)
+                .unwrap();
+            let mon = &monitors[new_idx];
+            let workspace_idx = target_ws_idx
+                .map(|idx| min(idx, mon.workspaces.len() - 1))
+                .unwrap_or(mon.active_workspace_idx);
+
+            self.add_window_by_idx(new_idx, workspace_idx, window, activate, placement);
+            return;
+        }
+
         if let MonitorSet::Normal {
             monitors,
             active_monitor_idx,
@@ -1405,20 +2168,21 @@ impl<W: LayoutElement> Layout<W> {
 
             let current = &mut monitors[*active_monitor_idx];
             let ws = current.active_workspace();
-            if !ws.has_windows() {
+            let Some((window, placement)) = ws.take_window(None) else {
                 return;
-            }
-            let column = &ws.columns[ws.active_column_idx];
-            let width = column.width;
-            let is_full_width = column.is_full_width;
-            let window = ws.remove_window_by_idx(ws.active_column_idx, column.active_tile_idx);
+            };
 
-            let workspace_idx = monitors[new_idx].active_workspace_idx;
-            self.add_window_by_idx(new_idx, workspace_idx, window, true, width, is_full_width);
+            let mon = &monitors[new_idx];
+            let workspace_idx = target_ws_idx
+                .map(|idx| min(idx, mon.workspaces.len() - 1))
+                .unwrap_or(mon.active_workspace_idx);
+            self.add_window_by_idx(new_idx, workspace_idx, window, true, placement);
         }
     }
 
-    pub fn move_column_to_output(&mut self, output: &Output) {
+    /// Moves the active column to the output, onto its active workspace or onto the workspace at
+    /// `target_ws_idx` if set.
+    pub fn move_column_to_output(&mut self, output: &Output, target_ws_idx: Option<usize>) {
         if let MonitorSet::Normal {
             monitors,
             active_monitor_idx,
@@ -1432,35 +2196,30 @@ impl<W: LayoutElement> Layout<W> {
 
             let current = &mut monitors[*active_monitor_idx];
             let ws = current.active_workspace();
-            if !ws.has_windows() {
+            if ws.columns.is_empty() || ws.is_floating_focused() {
                 return;
             }
             let column = ws.remove_column_by_idx(ws.active_column_idx);
 
-            let workspace_idx = monitors[new_idx].active_workspace_idx;
+            let mon = &monitors[new_idx];
+            let workspace_idx = target_ws_idx
+                .map(|idx| min(idx, mon.workspaces.len() - 1))
+                .unwrap_or(mon.active_workspace_idx);
             self.add_column_by_idx(new_idx, workspace_idx, column, true);
         }
     }
 
     pub fn move_window_to_output(&mut self, window: &W::Id, output: &Output) {
-        let mut width = None;
-        let mut is_full_width = false;
+        let mut placement = None;
 
         if let MonitorSet::Normal { monitors, .. } = &mut self.monitor_set {
-            for mon in &*monitors {
-                for ws in &mon.workspaces {
-                    for col in &ws.columns {
-                        if col.contains(window) {
-                            width = Some(col.width);
-                            is_full_width = col.is_full_width;
-                            break;
-                        }
-                    }
-                }
-            }
+            placement = monitors
+                .iter()
+                .flat_map(|mon| &mon.workspaces)
+                .find_map(|ws| ws.window_placement(window));
         }
 
-        let Some(width) = width else { return };
+        let Some(placement) = placement else { return };
 
         let window = self.remove_window(window).unwrap();
 
@@ -1472,7 +2231,7 @@ impl<W: LayoutElement> Layout<W> {
 
             let workspace_idx = monitors[new_idx].active_workspace_idx;
             // FIXME: activate only if it was already active and focused.
-            self.add_window_by_idx(new_idx, workspace_idx, window, true, width, is_full_width);
+            self.add_window_by_idx(new_idx, workspace_idx, window, true, placement);
         }
     }
 
@@ -1497,15 +2256,16 @@ impl<W: LayoutElement> Layout<W> {
         current.workspace_switch = None;
         current.clean_up_workspaces();
 
-        ws.set_output(Some(output.clone()));
-        ws.original_output = OutputId::new(output);
-
         let target_idx = monitors
             .iter()
             .position(|mon| &mon.output == output)
             .unwrap();
         let target = &mut monitors[target_idx];
 
+        ws.update_config(target.options.clone());
+        ws.set_output(Some(output.clone()));
+        ws.original_output = OutputId::new(output);
+
         target.previous_workspace_id = Some(target.workspaces[target.active_workspace_idx].id());
 
         // Insert the workspace after the currently active one. Unless the currently active one is
@@ -1695,48 +2455,551 @@ impl<W: LayoutElement> Layout<W> {
         monitor.move_workspace_up();
     }
 
+    pub fn toggle_overview(&mut self) {
+        let Some(monitor) = self.active_monitor() else {
+            return;
+        };
+        monitor.toggle_overview();
+    }
+
+    pub fn set_overview_open(&mut self, output: &Output, open: bool) {
+        let MonitorSet::Normal { monitors, .. } = &mut self.monitor_set else {
+            return;
+        };
+
+        if let Some(monitor) = monitors.iter_mut().find(|mon| &mon.output == output) {
+            monitor.set_overview_open(open);
+        }
+    }
+
+    /// Returns whether the overview is open on the active monitor.
+    pub fn is_overview_open(&self) -> bool {
+        self.active_monitor_ref()
+            .is_some_and(|monitor| monitor.is_overview_open())
+    }
+
+    /// Picks the window or the workspace under the position in the overview on the output, and
+    /// closes the overview.
+    pub fn pick_in_overview(&mut self, output: &Output, pos_within_output: Point<f64, Logical>) {
+        let MonitorSet::Normal {
+            monitors,
+            active_monitor_idx,
+            ..
+        } = &mut self.monitor_set
+        else {
+            return;
+        };
+
+        let Some((idx, monitor)) = monitors
+            .iter_mut()
+            .enumerate()
+            .find(|(_, mon)| &mon.output == output)
+        else {
+            return;
+        };
+
+        *active_monitor_idx = idx;
+        monitor.pick_in_overview(pos_within_output);
+    }
+
+    /// Returns the windows hidden in the scratchpad.
+    pub fn scratchpad_windows(&self) -> impl Iterator<Item = &W> + '_ {
+        self.scratchpad.iter().map(|hidden| &hidden.window)
+    }
+
+    /// Returns the window hidden in the scratchpad with this surface, if any.
+    pub fn find_scratchpad_window(&self, wl_surface: &WlSurface) -> Option<&W> {
+        self.scratchpad
+            .iter()
+            .map(|hidden| &hidden.window)
+            .find(|win| win.is_wl_surface(wl_surface))
+    }
+
+    fn scratchpad_idx(&self, window: &W::Id) -> Option<usize> {
+        self.scratchpad
+            .iter()
+            .position(|hidden| hidden.window.id() == window)
+    }
+
+    /// Hides the window, or the focused window if `None`, in the scratchpad.
+    pub fn move_to_scratchpad(&mut self, window: Option<&W::Id>) {
+        // The interactively moved window has the focus and can't be hidden.
+        if let Some(state) = &self.interactive_move {
+            if window.is_none() || window == Some(state.window_id()) {
+                return;
+            }
+        }
+
+        let Some(workspace) = self.workspace_for_window(window) else {
+            return;
+        };
+        let Some((window, _)) = workspace.take_window(window) else {
+            return;
+        };
+
+        if self
+            .interactive_resize
+            .as_ref()
+            .is_some_and(|resize| &resize.window_id == window.id())
+        {
+            self.interactive_resize = None;
+        }
+
+        let size = window.size();
+        self.scratchpad.push(ScratchpadWindow { window, size });
+
+        self.clean_up_workspaces();
+    }
+
+    /// Hides the windows summoned from the scratchpad, or summons the hidden ones onto the active
+    /// workspace if none are shown.
+    pub fn toggle_scratchpad(&mut self) {
+        let MonitorSet::Normal {
+            monitors,
+            active_monitor_idx,
+            ..
+        } = &mut self.monitor_set
+        else {
+            return;
+        };
+
+        let mut summoned = vec![];
+        for mon in monitors.iter_mut() {
+            for ws in &mut mon.workspaces {
+                summoned.extend(ws.take_scratchpad_windows());
+            }
+        }
+
+        if !summoned.is_empty() {
+            for window in summoned {
+                if self
+                    .interactive_resize
+                    .as_ref()
+                    .is_some_and(|resize| &resize.window_id == window.id())
+                {
+                    self.interactive_resize = None;
+                }
+
+                let size = window.size();
+                self.scratchpad.push(ScratchpadWindow { window, size });
+            }
+
+            self.clean_up_workspaces();
+            return;
+        }
+
+        let mon = &mut monitors[*active_monitor_idx];
+        for hidden in self.scratchpad.drain(..) {
+            hidden.window.request_size(hidden.size);
+            mon.add_scratchpad_window(hidden.window);
+        }
+    }
+
+    fn clean_up_workspaces(&mut self) {
+        match &mut self.monitor_set {
+            MonitorSet::Normal { monitors, .. } => {
+                for mon in monitors {
+                    if mon.workspace_switch.is_none() {
+                        mon.clean_up_workspaces();
+                    }
+                }
+            }
+            MonitorSet::NoOutputs { workspaces } => {
+                workspaces.retain(|ws| ws.has_windows_or_name());
+            }
+        }
+    }
+
     pub fn start_open_animation_for_window(&mut self, window: &W::Id) {
         match &mut self.monitor_set {
             MonitorSet::Normal { monitors, .. } => {
                 for mon in monitors {
                     for ws in &mut mon.workspaces {
-                        for col in &mut ws.columns {
-                            for tile in &mut col.tiles {
-                                if tile.window().id() == window {
-                                    tile.start_open_animation();
-                                    return;
-                                }
-                            }
+                        if ws.start_open_animation(window) {
+                            return;
                         }
                     }
                 }
             }
             MonitorSet::NoOutputs { workspaces, .. } => {
                 for ws in workspaces {
-                    for col in &mut ws.columns {
-                        for tile in &mut col.tiles {
-                            if tile.window().id() == window {
-                                tile.start_open_animation();
-                                return;
-                            }
-                        }
+                    if ws.start_open_animation(window) {
+                        return;
                     }
                 }
             }
         }
     }
 
-    pub fn refresh(&mut self) {
-        let _span = tracy_client::span!("Layout::refresh");
+    /// Starts an interactive move of the window with the pointer.
+    ///
+    /// The window is lifted out of the layout once the pointer moves far enough. Returns `false` if
+    /// the window can't be moved.
+    pub fn interactive_move_begin(
+        &mut self,
+        window: W::Id,
+        output: &Output,
+        pointer_pos_within_output: Point<f64, Logical>,
+    ) -> bool {
+        if self.interactive_move.is_some() {
+            return false;
+        }
 
-        match &mut self.monitor_set {
-            MonitorSet::Normal {
-                monitors,
-                active_monitor_idx,
-                ..
-            } => {
+        let MonitorSet::Normal { monitors, .. } = &self.monitor_set else {
+            return false;
+        };
+
+        let Some(win) = monitors
+            .iter()
+            .flat_map(|mon| &mon.workspaces)
+            .flat_map(|ws| ws.windows())
+            .find(|win| win.id() == &window)
+        else {
+            return false;
+        };
+
+        if win.is_pending_fullscreen() {
+            return false;
+        }
+
+        // Keep the same point of the tile under the pointer as it moves.
+        let pointer_ratio_within_tile = monitors
+            .iter()
+            .find(|mon| &mon.output == output)
+            .and_then(|mon| mon.active_workspace_ref().tile_rect(&window))
+            .map(|rect| {
+                let pos = pointer_pos_within_output - rect.loc.to_f64();
+                (
+                    (pos.x / f64::from(rect.size.w)).clamp(0., 1.),
+                    (pos.y / f64::from(rect.size.h)).clamp(0., 1.),
+                )
+            })
+            .unwrap_or((0.5, 0.));
+
+        self.interactive_move = Some(InteractiveMoveState::Starting {
+            window_id: window,
+            pointer_delta: Point::from((0., 0.)),
+            pointer_ratio_within_tile,
+        });
+
+        true
+    }
+
+    /// Updates the interactive move of the window with the pointer motion.
+    ///
+    /// Returns `false` if the window is not being moved anymore.
+    pub fn interactive_move_update(
+        &mut self,
+        window: &W::Id,
+        delta: Point<f64, Logical>,
+        output: Output,
+        pointer_pos_within_output: Point<f64, Logical>,
+    ) -> bool {
+        match &mut self.interactive_move {
+            Some(InteractiveMoveState::Starting {
+                window_id,
+                pointer_delta,
+                pointer_ratio_within_tile,
+            }) => {
+                if window_id != window {
+                    return false;
+                }
+
+                *pointer_delta += delta;
+                let distance = pointer_delta.x.hypot(pointer_delta.y);
+                if distance < INTERACTIVE_MOVE_START_THRESHOLD {
+                    return true;
+                }
+
+                let pointer_ratio_within_tile = *pointer_ratio_within_tile;
+                self.interactive_move = None;
+
+                // Lift the window out of the layout.
+                let Some(placement) = self
+                    .workspaces()
+                    .find_map(|(_, _, ws)| ws.window_placement(window))
+                else {
+                    return false;
+                };
+                let Some(win) = self.remove_window(window) else {
+                    return false;
+                };
+                win.output_enter(&output);
+
+                self.interactive_move = Some(InteractiveMoveState::Moving(InteractiveMoveData {
+                    tile: Tile::new(win, self.options.clone()),
+                    placement,
+                    output,
+                    pointer_pos_within_output,
+                    pointer_ratio_within_tile,
+                    insert_hint: SolidColorBuffer::default(),
+                    insert_hint_area: None,
+                }));
+            }
+            Some(InteractiveMoveState::Moving(data)) => {
+                if data.tile.window().id() != window {
+                    return false;
+                }
+
+                if data.output != output {
+                    data.tile.window().output_leave(&data.output);
+                    data.tile.window().output_enter(&output);
+                    data.output = output;
+                }
+                data.pointer_pos_within_output = pointer_pos_within_output;
+            }
+            None => return false,
+        }
+
+        self.update_insert_hint();
+        true
+    }
+
+    /// Ends the interactive move of the window, inserting it back into the layout.
+    pub fn interactive_move_end(&mut self, window: &W::Id) {
+        if !self
+            .interactive_move
+            .as_ref()
+            .is_some_and(|state| state.window_id() == window)
+        {
+            return;
+        }
+
+        // If the window was never lifted, there's nothing to put back.
+        let Some(InteractiveMoveState::Moving(data)) = self.interactive_move.take() else {
+            return;
+        };
+
+        match &mut self.monitor_set {
+            MonitorSet::Normal {
+                monitors,
+                active_monitor_idx,
+                ..
+            } => {
+                // The output could have been disconnected during the move.
+                let mon_idx = monitors
+                    .iter()
+                    .position(|mon| mon.output == data.output)
+                    .unwrap_or(*active_monitor_idx);
+                let mon = &mut monitors[mon_idx];
+
+                let position = data.insert_position(mon.active_workspace_ref());
+                let window = data.tile.into_window();
+                window.output_leave(&data.output);
+
+                mon.insert_window(mon.active_workspace_idx, window, position, data.placement);
+                *active_monitor_idx = mon_idx;
+            }
+            MonitorSet::NoOutputs { workspaces } => {
+                if workspaces.is_empty() {
+                    workspaces.push(Workspace::new_no_outputs(self.options.clone()));
+                }
+                let ws = &mut workspaces[0];
+
+                let position = data.insert_position(ws);
+                let window = data.tile.into_window();
+                window.output_leave(&data.output);
+
+                ws.insert_window(position, window, data.placement);
+            }
+        }
+    }
+
+    /// Starts an interactive resize of the window by dragging the given edges with the pointer.
+    ///
+    /// Returns `false` if the window can't be resized.
+    pub fn interactive_resize_begin(&mut self, window: W::Id, edges: ResizeEdge) -> bool {
+        if self.interactive_resize.is_some() || edges.is_empty() {
+            return false;
+        }
+
+        let Some(win) = self
+            .workspaces()
+            .flat_map(|(_, _, ws)| ws.windows())
+            .find(|win| win.id() == &window)
+        else {
+            return false;
+        };
+
+        if win.is_pending_fullscreen() {
+            return false;
+        }
+
+        let size = win.size();
+        self.interactive_resize = Some(InteractiveResizeData {
+            window_id: window,
+            edges,
+            original_window_size: size,
+            last_window_size: size,
+            pointer_delta: Point::from((0., 0.)),
+        });
+
+        true
+    }
+
+    /// Updates the interactive resize of the window with the pointer motion.
+    ///
+    /// Returns `false` if the window is not being resized anymore.
+    pub fn interactive_resize_update(
+        &mut self,
+        window: &W::Id,
+        delta: Point<f64, Logical>,
+    ) -> bool {
+        let Some(resize) = &mut self.interactive_resize else {
+            return false;
+        };
+        if &resize.window_id != window {
+            return false;
+        }
+
+        resize.pointer_delta += delta;
+        let delta = resize.pointer_delta.to_i32_round::<i32>();
+
+        let edges = resize.edges;
+        let mut new_size = resize.original_window_size;
+        if edges.contains(ResizeEdge::LEFT) {
+            new_size.w -= delta.x;
+        } else if edges.contains(ResizeEdge::RIGHT) {
+            new_size.w += delta.x;
+        }
+        if edges.contains(ResizeEdge::TOP) {
+            new_size.h -= delta.y;
+        } else if edges.contains(ResizeEdge::BOTTOM) {
+            new_size.h += delta.y;
+        }
+
+        let old_size = resize.last_window_size;
+        if new_size == old_size {
+            return true;
+        }
+
+        let Some(ws) = self.workspace_for_window(Some(window)) else {
+            self.interactive_resize = None;
+            return false;
+        };
+        let size = ws.resize_window_edges(window, edges, old_size, new_size);
+
+        if let Some(resize) = &mut self.interactive_resize {
+            resize.last_window_size = size;
+        }
+
+        true
+    }
+
+    /// Ends the interactive resize of the window.
+    pub fn interactive_resize_end(&mut self, window: &W::Id) {
+        if self
+            .interactive_resize
+            .as_ref()
+            .is_some_and(|resize| &resize.window_id == window)
+        {
+            self.interactive_resize = None;
+        }
+    }
+
+    fn interactive_moved(&self) -> Option<&InteractiveMoveData<W>> {
+        match &self.interactive_move {
+            Some(InteractiveMoveState::Moving(data)) => Some(data),
+            _ => None,
+        }
+    }
+
+    fn interactive_moved_mut(&mut self) -> Option<&mut InteractiveMoveData<W>> {
+        match &mut self.interactive_move {
+            Some(InteractiveMoveState::Moving(data)) => Some(data),
+            _ => None,
+        }
+    }
+
+    fn update_insert_hint(&mut self) {
+        let Some(InteractiveMoveState::Moving(data)) = &mut self.interactive_move else {
+            return;
+        };
+
+        data.insert_hint_area = None;
+
+        let MonitorSet::Normal { monitors, .. } = &self.monitor_set else {
+            return;
+        };
+        let Some(mon) = monitors.iter().find(|mon| mon.output == data.output) else {
+            return;
+        };
+
+        let ws = mon.active_workspace_ref();
+        let position = data.insert_position(ws);
+        data.insert_hint_area = ws.insert_hint_area(position);
+
+        if let Some(area) = data.insert_hint_area {
+            let color = self.options.focus_ring.active_color;
+            data.insert_hint.update(area.size, color.into());
+        }
+    }
+
+    /// Renders the interactively moved window and its insert hint, if they are on the output.
+    pub fn render_interactive_move_for_output<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        output: &Output,
+        target: RenderTarget,
+    ) -> Vec<InteractiveMoveRenderElement<R>> {
+        let Some(data) = self.interactive_moved() else {
+            return vec![];
+        };
+        if &data.output != output {
+            return vec![];
+        }
+
+        let scale = Scale::from(output.current_scale().fractional_scale());
+        let tile_pos = data.tile_pos().to_i32_round();
+
+        let mut rv: Vec<_> = data
+            .tile
+            .render(renderer, tile_pos, scale, output_size(output), true, target)
+            .map(InteractiveMoveRenderElement::from)
+            .collect();
+
+        if let Some(area) = data.insert_hint_area {
+            let elem = SolidColorRenderElement::from_buffer(
+                &data.insert_hint,
+                area.loc.to_physical_precise_round(scale),
+                scale,
+                1.,
+                Kind::Unspecified,
+            );
+            rv.push(elem.into());
+        }
+
+        rv
+    }
+
+    pub fn refresh(&mut self) {
+        let _span = tracy_client::span!("Layout::refresh");
+
+        // The interactively moved window has the focus.
+        let is_moving = if let Some(data) = self.interactive_moved_mut() {
+            let win = data.tile.window_mut();
+            win.set_activated(true);
+            win.send_pending_configure();
+            win.refresh();
+            true
+        } else {
+            false
+        };
+
+        for hidden in &mut self.scratchpad {
+            hidden.window.set_activated(false);
+            hidden.window.send_pending_configure();
+            hidden.window.refresh();
+        }
+
+        match &mut self.monitor_set {
+            MonitorSet::Normal {
+                monitors,
+                active_monitor_idx,
+                ..
+            } => {
                 for (idx, mon) in monitors.iter_mut().enumerate() {
-                    let is_active = idx == *active_monitor_idx;
+                    let is_active = idx == *active_monitor_idx && !is_moving;
                     for (ws_idx, ws) in mon.workspaces.iter_mut().enumerate() {
                         ws.refresh(is_active);
 
@@ -1757,6 +3020,32 @@ impl<W: LayoutElement> Layout<W> {
     }
 }
 
+impl<W: LayoutElement> InteractiveMoveState<W> {
+    fn window_id(&self) -> &W::Id {
+        match self {
+            InteractiveMoveState::Starting { window_id, .. } => window_id,
+            InteractiveMoveState::Moving(data) => data.tile.window().id(),
+        }
+    }
+}
+
+impl<W: LayoutElement> InteractiveMoveData<W> {
+    /// Returns the position of the tile within the output.
+    fn tile_pos(&self) -> Point<f64, Logical> {
+        let size = self.tile.tile_size().to_f64();
+        let (x, y) = self.pointer_ratio_within_tile;
+        self.pointer_pos_within_output - Point::from((size.w * x, size.h * y))
+    }
+
+    /// Computes where the window would be inserted into the workspace if dropped now.
+    fn insert_position(&self, ws: &Workspace<W>) -> InsertPosition {
+        match self.placement {
+            WindowPlacement::Floating => InsertPosition::Floating(self.tile_pos().to_i32_round()),
+            WindowPlacement::Column { .. } => ws.insert_position(self.pointer_pos_within_output),
+        }
+    }
+}
+
 impl<W: LayoutElement> Default for MonitorSet<W> {
     fn default() -> Self {
         Self::NoOutputs { workspaces: vec![] }
@@ -1769,9 +3058,11 @@ mod tests {
 
     use proptest::prelude::*;
     use proptest_derive::Arbitrary;
+    use smithay::desktop::layer_map_for_output;
     use smithay::output::{Mode, PhysicalProperties, Subpixel};
     use smithay::utils::Rectangle;
 
+    use super::workspace::WindowHeight;
     use super::*;
 
     impl<W: LayoutElement> Default for Layout<W> {
@@ -1982,27 +3273,58 @@ mod tests {
             #[proptest(strategy = "arbitrary_min_max_size()")]
             min_max_size: (Size<i32, Logical>, Size<i32, Logical>),
         },
+        AddWindowOnOutput {
+            #[proptest(strategy = "1..=5usize")]
+            id: usize,
+            #[proptest(strategy = "1..=5usize")]
+            output_id: usize,
+            #[proptest(strategy = "proptest::option::of(0..=4usize)")]
+            ws_idx: Option<usize>,
+            #[proptest(strategy = "arbitrary_bbox()")]
+            bbox: Rectangle<i32, Logical>,
+            #[proptest(strategy = "arbitrary_min_max_size()")]
+            min_max_size: (Size<i32, Logical>, Size<i32, Logical>),
+        },
         CloseWindow(#[proptest(strategy = "1..=5usize")] usize),
         FullscreenWindow(#[proptest(strategy = "1..=5usize")] usize),
         FocusColumnLeft,
         FocusColumnRight,
         FocusColumnFirst,
         FocusColumnLast,
+        FocusColumn(#[proptest(strategy = "0..=5usize")] usize),
         FocusWindowDown,
         FocusWindowUp,
         FocusWindowOrWorkspaceDown,
         FocusWindowOrWorkspaceUp,
+        FocusColumnLeftOrOutput(#[proptest(strategy = "1..=5u8")] u8),
+        FocusColumnRightOrOutput(#[proptest(strategy = "1..=5u8")] u8),
+        FocusWindowDownOrOutput(#[proptest(strategy = "1..=5u8")] u8),
+        FocusWindowUpOrOutput(#[proptest(strategy = "1..=5u8")] u8),
         MoveColumnLeft,
         MoveColumnRight,
         MoveColumnToFirst,
         MoveColumnToLast,
+        MoveColumnToIndex(#[proptest(strategy = "0..=5usize")] usize),
         MoveWindowDown,
         MoveWindowUp,
         MoveWindowDownOrToWorkspaceDown,
         MoveWindowUpOrToWorkspaceUp,
-        ConsumeOrExpelWindowLeft,
-        ConsumeOrExpelWindowRight,
+        MoveColumnLeftOrToOutput(#[proptest(strategy = "1..=5u8")] u8),
+        MoveColumnRightOrToOutput(#[proptest(strategy = "1..=5u8")] u8),
+        MoveWindowDownOrToOutput(#[proptest(strategy = "1..=5u8")] u8),
+        MoveWindowUpOrToOutput(#[proptest(strategy = "1..=5u8")] u8),
+        ConsumeOrExpelWindowLeft {
+            #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+            id: Option<usize>,
+        },
+        ConsumeOrExpelWindowRight {
+            #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+            id: Option<usize>,
+        },
+        SwapWindowLeft,
+        SwapWindowRight,
         ConsumeWindowIntoColumn,
+        ToggleColumnTabbedDisplay,
         ExpelWindowFromColumn,
         CenterColumn,
         FocusWorkspaceDown,
@@ -2012,20 +3334,86 @@ mod tests {
         FocusWorkspacePrevious,
         MoveWindowToWorkspaceDown,
         MoveWindowToWorkspaceUp,
-        MoveWindowToWorkspace(#[proptest(strategy = "0..=4usize")] usize),
+        MoveWindowToWorkspace {
+            #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+            window_id: Option<usize>,
+            #[proptest(strategy = "0..=4usize")]
+            workspace_idx: usize,
+        },
         MoveColumnToWorkspaceDown,
         MoveColumnToWorkspaceUp,
         MoveColumnToWorkspace(#[proptest(strategy = "0..=4usize")] usize),
         MoveWorkspaceDown,
         MoveWorkspaceUp,
-        MoveWindowToOutput(#[proptest(strategy = "1..=5u8")] u8),
+        ToggleOverview,
+        PickInOverview {
+            #[proptest(strategy = "1..=5usize")]
+            output_id: usize,
+            #[proptest(strategy = "-100f64..1400f64")]
+            x: f64,
+            #[proptest(strategy = "-100f64..800f64")]
+            y: f64,
+        },
+        MoveWindowToOutput {
+            #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+            window_id: Option<usize>,
+            #[proptest(strategy = "1..=5u8")]
+            output_id: u8,
+            #[proptest(strategy = "proptest::option::of(0..=4usize)")]
+            target_ws_idx: Option<usize>,
+        },
         MoveColumnToOutput(#[proptest(strategy = "1..=5u8")] u8),
         SwitchPresetColumnWidth,
         MaximizeColumn,
-        SetColumnWidth(#[proptest(strategy = "arbitrary_size_change()")] SizeChange),
-        SetWindowHeight(#[proptest(strategy = "arbitrary_size_change()")] SizeChange),
+        SetColumnWidth {
+            #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+            id: Option<usize>,
+            #[proptest(strategy = "arbitrary_size_change()")]
+            change: SizeChange,
+        },
+        SetWindowHeight {
+            #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+            id: Option<usize>,
+            #[proptest(strategy = "arbitrary_size_change()")]
+            change: SizeChange,
+        },
+        SwitchPresetWindowHeight {
+            #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+            id: Option<usize>,
+        },
+        ResetWindowHeight {
+            #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+            id: Option<usize>,
+        },
+        ToggleWindowFloating {
+            #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+            id: Option<usize>,
+        },
+        MoveWindowToScratchpad {
+            #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+            id: Option<usize>,
+        },
+        ToggleScratchpad,
+        SwitchFocusFloatingTiling,
         Communicate(#[proptest(strategy = "1..=5usize")] usize),
         MoveWorkspaceToOutput(#[proptest(strategy = "1..=5u8")] u8),
+        AddNamedWorkspace {
+            #[proptest(strategy = "1..=5usize")]
+            ws_name: usize,
+            #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+            output_name: Option<usize>,
+        },
+        UnnameWorkspace {
+            #[proptest(strategy = "1..=5usize")]
+            ws_name: usize,
+        },
+        SetWorkspaceLayout {
+            #[proptest(strategy = "proptest::option::of(arbitrary_spacing())")]
+            gaps: Option<u16>,
+            #[proptest(strategy = "proptest::option::of(arbitrary_struts())")]
+            struts: Option<Struts>,
+            single_preset_width: bool,
+        },
         ViewOffsetGestureBegin {
             #[proptest(strategy = "1..=5usize")]
             output_idx: usize,
@@ -2048,6 +3436,52 @@ mod tests {
         WorkspaceSwitchGestureEnd {
             cancelled: bool,
         },
+        InteractiveMoveBegin {
+            #[proptest(strategy = "1..=5usize")]
+            window: usize,
+            #[proptest(strategy = "1..=5usize")]
+            output_idx: usize,
+            #[proptest(strategy = "-20000f64..20000f64")]
+            px: f64,
+            #[proptest(strategy = "-20000f64..20000f64")]
+            py: f64,
+        },
+        InteractiveMoveUpdate {
+            #[proptest(strategy = "1..=5usize")]
+            window: usize,
+            #[proptest(strategy = "-20000f64..20000f64")]
+            dx: f64,
+            #[proptest(strategy = "-20000f64..20000f64")]
+            dy: f64,
+            #[proptest(strategy = "1..=5usize")]
+            output_idx: usize,
+            #[proptest(strategy = "-20000f64..20000f64")]
+            px: f64,
+            #[proptest(strategy = "-20000f64..20000f64")]
+            py: f64,
+        },
+        InteractiveMoveEnd {
+            #[proptest(strategy = "1..=5usize")]
+            window: usize,
+        },
+        InteractiveResizeBegin {
+            #[proptest(strategy = "1..=5usize")]
+            window: usize,
+            #[proptest(strategy = "0..16u32")]
+            edges: u32,
+        },
+        InteractiveResizeUpdate {
+            #[proptest(strategy = "1..=5usize")]
+            window: usize,
+            #[proptest(strategy = "-20000f64..20000f64")]
+            dx: f64,
+            #[proptest(strategy = "-20000f64..20000f64")]
+            dy: f64,
+        },
+        InteractiveResizeEnd {
+            #[proptest(strategy = "1..=5usize")]
+            window: usize,
+        },
     }
 
     impl Op {
@@ -2100,6 +3534,16 @@ mod tests {
                     bbox,
                     min_max_size,
                 } => {
+                    if let Some(data) = layout.interactive_moved() {
+                        if data.tile.window().0.id == id {
+                            return;
+                        }
+                    }
+
+                    if layout.scratchpad_idx(&id).is_some() {
+                        return;
+                    }
+
                     match &mut layout.monitor_set {
                         MonitorSet::Normal { monitors, .. } => {
                             for mon in monitors {
@@ -2132,6 +3576,16 @@ mod tests {
                     bbox,
                     min_max_size,
                 } => {
+                    if let Some(data) = layout.interactive_moved() {
+                        if data.tile.window().0.id == id {
+                            return;
+                        }
+                    }
+
+                    if layout.scratchpad_idx(&id).is_some() {
+                        return;
+                    }
+
                     let mut found_right_of = false;
 
                     match &mut layout.monitor_set {
@@ -2172,6 +3626,54 @@ mod tests {
                     let win = TestWindow::new(id, bbox, min_max_size.0, min_max_size.1);
                     layout.add_window_right_of(&right_of_id, win, None, false);
                 }
+                Op::AddWindowOnOutput {
+                    id,
+                    output_id,
+                    ws_idx,
+                    bbox,
+                    min_max_size,
+                } => {
+                    if let Some(data) = layout.interactive_moved() {
+                        if data.tile.window().0.id == id {
+                            return;
+                        }
+                    }
+
+                    if layout.scratchpad_idx(&id).is_some() {
+                        return;
+                    }
+
+                    match &mut layout.monitor_set {
+                        MonitorSet::Normal { monitors, .. } => {
+                            for mon in monitors {
+                                for ws in &mut mon.workspaces {
+                                    for win in ws.windows() {
+                                        if win.0.id == id {
+                                            return;
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        MonitorSet::NoOutputs { workspaces, .. } => {
+                            for ws in workspaces {
+                                for win in ws.windows() {
+                                    if win.0.id == id {
+                                        return;
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    let name = format!("output{output_id}");
+                    let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
+                        return;
+                    };
+
+                    let win = TestWindow::new(id, bbox, min_max_size.0, min_max_size.1);
+                    layout.add_window_on_output(&output, ws_idx, win, None, false);
+                }
                 Op::CloseWindow(id) => {
                     layout.remove_window(&id);
                 }
@@ -2182,21 +3684,94 @@ mod tests {
                 Op::FocusColumnRight => layout.focus_right(),
                 Op::FocusColumnFirst => layout.focus_column_first(),
                 Op::FocusColumnLast => layout.focus_column_last(),
+                Op::FocusColumn(index) => layout.focus_column(index),
                 Op::FocusWindowDown => layout.focus_down(),
                 Op::FocusWindowUp => layout.focus_up(),
                 Op::FocusWindowOrWorkspaceDown => layout.focus_window_or_workspace_down(),
                 Op::FocusWindowOrWorkspaceUp => layout.focus_window_or_workspace_up(),
-                Op::MoveColumnLeft => layout.move_left(),
-                Op::MoveColumnRight => layout.move_right(),
-                Op::MoveColumnToFirst => layout.move_column_to_first(),
-                Op::MoveColumnToLast => layout.move_column_to_last(),
-                Op::MoveWindowDown => layout.move_down(),
-                Op::MoveWindowUp => layout.move_up(),
-                Op::MoveWindowDownOrToWorkspaceDown => layout.move_down_or_to_workspace_down(),
-                Op::MoveWindowUpOrToWorkspaceUp => layout.move_up_or_to_workspace_up(),
-                Op::ConsumeOrExpelWindowLeft => layout.consume_or_expel_window_left(),
-                Op::ConsumeOrExpelWindowRight => layout.consume_or_expel_window_right(),
-                Op::ConsumeWindowIntoColumn => layout.consume_into_column(),
+                Op::FocusColumnLeftOrOutput(id) => {
+                    let name = format!("output{id}");
+                    let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
+                        return;
+                    };
+
+                    layout.focus_column_left_or_output(&output);
+                }
+                Op::FocusColumnRightOrOutput(id) => {
+                    let name = format!("output{id}");
+                    let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
+                        return;
+                    };
+
+                    layout.focus_column_right_or_output(&output);
+                }
+                Op::FocusWindowDownOrOutput(id) => {
+                    let name = format!("output{id}");
+                    let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
+                        return;
+                    };
+
+                    layout.focus_window_down_or_output(&output);
+                }
+                Op::FocusWindowUpOrOutput(id) => {
+                    let name = format!("output{id}");
+                    let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
+                        return;
+                    };
+
+                    layout.focus_window_up_or_output(&output);
+                }
+                Op::MoveColumnLeft => layout.move_left(),
+                Op::MoveColumnRight => layout.move_right(),
+                Op::MoveColumnToFirst => layout.move_column_to_first(),
+                Op::MoveColumnToLast => layout.move_column_to_last(),
+                Op::MoveColumnToIndex(index) => layout.move_column_to_index(index),
+                Op::MoveWindowDown => layout.move_down(),
+                Op::MoveWindowUp => layout.move_up(),
+                Op::MoveWindowDownOrToWorkspaceDown => layout.move_down_or_to_workspace_down(),
+                Op::MoveWindowUpOrToWorkspaceUp => layout.move_up_or_to_workspace_up(),
+                Op::MoveColumnLeftOrToOutput(id) => {
+                    let name = format!("output{id}");
+                    let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
+                        return;
+                    };
+
+                    layout.move_column_left_or_to_output(&output);
+                }
+                Op::MoveColumnRightOrToOutput(id) => {
+                    let name = format!("output{id}");
+                    let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
+                        return;
+                    };
+
+                    layout.move_column_right_or_to_output(&output);
+                }
+                Op::MoveWindowDownOrToOutput(id) => {
+                    let name = format!("output{id}");
+                    let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
+                        return;
+                    };
+
+                    layout.move_down_or_to_output(&output);
+                }
+                Op::MoveWindowUpOrToOutput(id) => {
+                    let name = format!("output{id}");
+                    let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
+                        return;
+                    };
+
+                    layout.move_up_or_to_output(&output);
+                }
+                Op::ConsumeOrExpelWindowLeft { id } => {
+                    layout.consume_or_expel_window_left(id.as_ref())
+                }
+                Op::ConsumeOrExpelWindowRight { id } => {
+                    layout.consume_or_expel_window_right(id.as_ref())
+                }
+                Op::SwapWindowLeft => layout.swap_window_left(),
+                Op::SwapWindowRight => layout.swap_window_right(),
+                Op::ConsumeWindowIntoColumn => layout.consume_into_column(),
+                Op::ToggleColumnTabbedDisplay => layout.toggle_column_tabbed_display(),
                 Op::ExpelWindowFromColumn => layout.expel_from_column(),
                 Op::CenterColumn => layout.center_column(),
                 Op::FocusWorkspaceDown => layout.switch_workspace_down(),
@@ -2208,17 +3783,24 @@ mod tests {
                 Op::FocusWorkspacePrevious => layout.switch_workspace_previous(),
                 Op::MoveWindowToWorkspaceDown => layout.move_to_workspace_down(),
                 Op::MoveWindowToWorkspaceUp => layout.move_to_workspace_up(),
-                Op::MoveWindowToWorkspace(idx) => layout.move_to_workspace(idx),
+                Op::MoveWindowToWorkspace {
+                    window_id,
+                    workspace_idx,
+                } => layout.move_to_workspace(window_id.as_ref(), workspace_idx),
                 Op::MoveColumnToWorkspaceDown => layout.move_column_to_workspace_down(),
                 Op::MoveColumnToWorkspaceUp => layout.move_column_to_workspace_up(),
                 Op::MoveColumnToWorkspace(idx) => layout.move_column_to_workspace(idx),
-                Op::MoveWindowToOutput(id) => {
+                Op::MoveWindowToOutput {
+                    window_id,
+                    output_id: id,
+                    target_ws_idx,
+                } => {
                     let name = format!("output{id}");
                     let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
                         return;
                     };
 
-                    layout.move_to_output(&output);
+                    layout.move_to_output(window_id.as_ref(), &output, target_ws_idx);
                 }
                 Op::MoveColumnToOutput(id) => {
                     let name = format!("output{id}");
@@ -2226,14 +3808,29 @@ mod tests {
                         return;
                     };
 
-                    layout.move_column_to_output(&output);
+                    layout.move_column_to_output(&output, None);
                 }
                 Op::MoveWorkspaceDown => layout.move_workspace_down(),
                 Op::MoveWorkspaceUp => layout.move_workspace_up(),
+                Op::ToggleOverview => layout.toggle_overview(),
+                Op::PickInOverview { output_id, x, y } => {
+                    let name = format!("output{output_id}");
+                    let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
+                        return;
+                    };
+
+                    layout.pick_in_overview(&output, Point::from((x, y)));
+                }
                 Op::SwitchPresetColumnWidth => layout.toggle_width(),
                 Op::MaximizeColumn => layout.toggle_full_width(),
-                Op::SetColumnWidth(change) => layout.set_column_width(change),
-                Op::SetWindowHeight(change) => layout.set_window_height(change),
+                Op::SetColumnWidth { id, change } => layout.set_column_width(id.as_ref(), change),
+                Op::SetWindowHeight { id, change } => layout.set_window_height(id.as_ref(), change),
+                Op::SwitchPresetWindowHeight { id } => layout.toggle_window_height(id.as_ref()),
+                Op::ResetWindowHeight { id } => layout.reset_window_height(id.as_ref()),
+                Op::ToggleWindowFloating { id } => layout.toggle_window_floating(id.as_ref()),
+                Op::MoveWindowToScratchpad { id } => layout.move_to_scratchpad(id.as_ref()),
+                Op::ToggleScratchpad => layout.toggle_scratchpad(),
+                Op::SwitchFocusFloatingTiling => layout.switch_focus_floating_tiling(),
                 Op::Communicate(id) => {
                     let mut update = false;
                     match &mut layout.monitor_set {
@@ -2277,6 +3874,32 @@ mod tests {
 
                     layout.move_workspace_to_output(&output);
                 }
+                Op::AddNamedWorkspace {
+                    ws_name,
+                    output_name,
+                } => {
+                    layout.ensure_named_workspace(&WorkspaceConfig {
+                        name: format!("ws{ws_name}"),
+                        open_on_output: output_name.map(|name| format!("output{name}")),
+                        layout: None,
+                    });
+                }
+                Op::UnnameWorkspace { ws_name } => {
+                    layout.unname_workspace(&format!("ws{ws_name}"));
+                }
+                Op::SetWorkspaceLayout {
+                    gaps,
+                    struts,
+                    single_preset_width,
+                } => {
+                    layout.set_workspace_layout(LayoutOverride {
+                        preset_column_widths: single_preset_width
+                            .then(|| vec![PresetSize::Proportion(0.5)]),
+                        gaps,
+                        struts,
+                        ..Default::default()
+                    });
+                }
                 Op::ViewOffsetGestureBegin { output_idx: id } => {
                     let name = format!("output{id}");
                     let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
@@ -2306,6 +3929,51 @@ mod tests {
                 Op::WorkspaceSwitchGestureEnd { cancelled } => {
                     layout.workspace_switch_gesture_end(cancelled);
                 }
+                Op::InteractiveMoveBegin {
+                    window,
+                    output_idx: id,
+                    px,
+                    py,
+                } => {
+                    let name = format!("output{id}");
+                    let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
+                        return;
+                    };
+
+                    layout.interactive_move_begin(window, &output, Point::from((px, py)));
+                }
+                Op::InteractiveMoveUpdate {
+                    window,
+                    dx,
+                    dy,
+                    output_idx: id,
+                    px,
+                    py,
+                } => {
+                    let name = format!("output{id}");
+                    let Some(output) = layout.outputs().find(|o| o.name() == name).cloned() else {
+                        return;
+                    };
+
+                    layout.interactive_move_update(
+                        &window,
+                        Point::from((dx, dy)),
+                        output,
+                        Point::from((px, py)),
+                    );
+                }
+                Op::InteractiveMoveEnd { window } => {
+                    layout.interactive_move_end(&window);
+                }
+                Op::InteractiveResizeBegin { window, edges } => {
+                    layout.interactive_resize_begin(window, ResizeEdge::from_bits_truncate(edges));
+                }
+                Op::InteractiveResizeUpdate { window, dx, dy } => {
+                    layout.interactive_resize_update(&window, Point::from((dx, dy)));
+                }
+                Op::InteractiveResizeEnd { window } => {
+                    layout.interactive_resize_end(&window);
+                }
             }
         }
     }
@@ -2368,6 +4036,13 @@ mod tests {
                 bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
                 min_max_size: Default::default(),
             },
+            Op::AddWindowOnOutput {
+                id: 5,
+                output_id: 1,
+                ws_idx: Some(1),
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
             Op::CloseWindow(0),
             Op::CloseWindow(1),
             Op::CloseWindow(2),
@@ -2376,13 +4051,22 @@ mod tests {
             Op::FullscreenWindow(3),
             Op::FocusColumnLeft,
             Op::FocusColumnRight,
+            Op::FocusColumn(1),
+            Op::FocusColumn(3),
             Op::FocusWindowUp,
             Op::FocusWindowOrWorkspaceUp,
             Op::FocusWindowDown,
             Op::FocusWindowOrWorkspaceDown,
+            Op::FocusColumnLeftOrOutput(1),
+            Op::FocusColumnRightOrOutput(2),
+            Op::FocusWindowDownOrOutput(2),
+            Op::FocusWindowUpOrOutput(1),
             Op::MoveColumnLeft,
             Op::MoveColumnRight,
+            Op::MoveColumnToIndex(1),
+            Op::MoveColumnToIndex(3),
             Op::ConsumeWindowIntoColumn,
+            Op::ToggleColumnTabbedDisplay,
             Op::ExpelWindowFromColumn,
             Op::CenterColumn,
             Op::FocusWorkspaceDown,
@@ -2392,9 +4076,22 @@ mod tests {
             Op::FocusWorkspace(3),
             Op::MoveWindowToWorkspaceDown,
             Op::MoveWindowToWorkspaceUp,
-            Op::MoveWindowToWorkspace(1),
-            Op::MoveWindowToWorkspace(2),
-            Op::MoveWindowToWorkspace(3),
+            Op::MoveWindowToWorkspace {
+                window_id: None,
+                workspace_idx: 1,
+            },
+            Op::MoveWindowToWorkspace {
+                window_id: None,
+                workspace_idx: 2,
+            },
+            Op::MoveWindowToWorkspace {
+                window_id: None,
+                workspace_idx: 3,
+            },
+            Op::MoveWindowToWorkspace {
+                window_id: Some(1),
+                workspace_idx: 2,
+            },
             Op::MoveColumnToWorkspaceDown,
             Op::MoveColumnToWorkspaceUp,
             Op::MoveColumnToWorkspace(1),
@@ -2404,9 +4101,67 @@ mod tests {
             Op::MoveWindowDownOrToWorkspaceDown,
             Op::MoveWindowUp,
             Op::MoveWindowUpOrToWorkspaceUp,
-            Op::ConsumeOrExpelWindowLeft,
-            Op::ConsumeOrExpelWindowRight,
+            Op::MoveColumnLeftOrToOutput(1),
+            Op::MoveColumnRightOrToOutput(2),
+            Op::MoveWindowDownOrToOutput(2),
+            Op::MoveWindowUpOrToOutput(1),
+            Op::ConsumeOrExpelWindowLeft { id: None },
+            Op::ConsumeOrExpelWindowRight { id: None },
+            Op::ConsumeOrExpelWindowLeft { id: Some(1) },
+            Op::ConsumeOrExpelWindowRight { id: Some(2) },
+            Op::SwapWindowLeft,
+            Op::SwapWindowRight,
             Op::MoveWorkspaceToOutput(1),
+            Op::ToggleWindowFloating { id: None },
+            Op::ToggleWindowFloating { id: Some(1) },
+            Op::SwitchFocusFloatingTiling,
+            Op::MoveWindowToScratchpad { id: None },
+            Op::MoveWindowToScratchpad { id: Some(1) },
+            Op::ToggleScratchpad,
+            Op::SwitchPresetWindowHeight { id: None },
+            Op::SwitchPresetWindowHeight { id: Some(1) },
+            Op::ResetWindowHeight { id: None },
+            Op::ToggleOverview,
+            Op::PickInOverview {
+                output_id: 1,
+                x: 640.,
+                y: 600.,
+            },
+            Op::InteractiveMoveBegin {
+                window: 0,
+                output_idx: 1,
+                px: 0.,
+                py: 0.,
+            },
+            Op::InteractiveMoveUpdate {
+                window: 0,
+                dx: 1000.,
+                dy: 0.,
+                output_idx: 1,
+                px: 1000.,
+                py: 0.,
+            },
+            Op::InteractiveMoveEnd { window: 0 },
+            Op::InteractiveResizeBegin {
+                window: 0,
+                edges: ResizeEdge::BOTTOM_RIGHT.bits(),
+            },
+            Op::InteractiveResizeUpdate {
+                window: 0,
+                dx: 100.,
+                dy: -100.,
+            },
+            Op::InteractiveResizeEnd { window: 0 },
+            Op::AddNamedWorkspace {
+                ws_name: 1,
+                output_name: Some(1),
+            },
+            Op::UnnameWorkspace { ws_name: 1 },
+            Op::SetWorkspaceLayout {
+                gaps: Some(0),
+                struts: None,
+                single_preset_width: true,
+            },
         ];
 
         for third in every_op {
@@ -2467,7 +4222,11 @@ mod tests {
                 bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
                 min_max_size: Default::default(),
             },
-            Op::MoveWindowToOutput(2),
+            Op::MoveWindowToOutput {
+                window_id: None,
+                output_id: 2,
+                target_ws_idx: None,
+            },
             Op::FocusOutput(1),
             Op::Communicate(1),
             Op::Communicate(2),
@@ -2513,6 +4272,13 @@ mod tests {
                 bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
                 min_max_size: Default::default(),
             },
+            Op::AddWindowOnOutput {
+                id: 8,
+                output_id: 1,
+                ws_idx: Some(1),
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
             Op::CloseWindow(0),
             Op::CloseWindow(1),
             Op::CloseWindow(2),
@@ -2521,13 +4287,22 @@ mod tests {
             Op::FullscreenWindow(3),
             Op::FocusColumnLeft,
             Op::FocusColumnRight,
+            Op::FocusColumn(1),
+            Op::FocusColumn(3),
             Op::FocusWindowUp,
             Op::FocusWindowOrWorkspaceUp,
             Op::FocusWindowDown,
             Op::FocusWindowOrWorkspaceDown,
+            Op::FocusColumnLeftOrOutput(1),
+            Op::FocusColumnRightOrOutput(2),
+            Op::FocusWindowDownOrOutput(2),
+            Op::FocusWindowUpOrOutput(1),
             Op::MoveColumnLeft,
             Op::MoveColumnRight,
+            Op::MoveColumnToIndex(1),
+            Op::MoveColumnToIndex(3),
             Op::ConsumeWindowIntoColumn,
+            Op::ToggleColumnTabbedDisplay,
             Op::ExpelWindowFromColumn,
             Op::CenterColumn,
             Op::FocusWorkspaceDown,
@@ -2537,9 +4312,22 @@ mod tests {
             Op::FocusWorkspace(3),
             Op::MoveWindowToWorkspaceDown,
             Op::MoveWindowToWorkspaceUp,
-            Op::MoveWindowToWorkspace(1),
-            Op::MoveWindowToWorkspace(2),
-            Op::MoveWindowToWorkspace(3),
+            Op::MoveWindowToWorkspace {
+                window_id: None,
+                workspace_idx: 1,
+            },
+            Op::MoveWindowToWorkspace {
+                window_id: None,
+                workspace_idx: 2,
+            },
+            Op::MoveWindowToWorkspace {
+                window_id: None,
+                workspace_idx: 3,
+            },
+            Op::MoveWindowToWorkspace {
+                window_id: Some(1),
+                workspace_idx: 2,
+            },
             Op::MoveColumnToWorkspaceDown,
             Op::MoveColumnToWorkspaceUp,
             Op::MoveColumnToWorkspace(1),
@@ -2549,8 +4337,66 @@ mod tests {
             Op::MoveWindowDownOrToWorkspaceDown,
             Op::MoveWindowUp,
             Op::MoveWindowUpOrToWorkspaceUp,
-            Op::ConsumeOrExpelWindowLeft,
-            Op::ConsumeOrExpelWindowRight,
+            Op::MoveColumnLeftOrToOutput(1),
+            Op::MoveColumnRightOrToOutput(2),
+            Op::MoveWindowDownOrToOutput(2),
+            Op::MoveWindowUpOrToOutput(1),
+            Op::ConsumeOrExpelWindowLeft { id: None },
+            Op::ConsumeOrExpelWindowRight { id: None },
+            Op::ConsumeOrExpelWindowLeft { id: Some(1) },
+            Op::ConsumeOrExpelWindowRight { id: Some(2) },
+            Op::SwapWindowLeft,
+            Op::SwapWindowRight,
+            Op::ToggleWindowFloating { id: None },
+            Op::ToggleWindowFloating { id: Some(1) },
+            Op::SwitchFocusFloatingTiling,
+            Op::MoveWindowToScratchpad { id: None },
+            Op::MoveWindowToScratchpad { id: Some(1) },
+            Op::ToggleScratchpad,
+            Op::SwitchPresetWindowHeight { id: None },
+            Op::SwitchPresetWindowHeight { id: Some(1) },
+            Op::ResetWindowHeight { id: None },
+            Op::ToggleOverview,
+            Op::PickInOverview {
+                output_id: 1,
+                x: 640.,
+                y: 600.,
+            },
+            Op::InteractiveMoveBegin {
+                window: 0,
+                output_idx: 1,
+                px: 0.,
+                py: 0.,
+            },
+            Op::InteractiveMoveUpdate {
+                window: 0,
+                dx: 1000.,
+                dy: 0.,
+                output_idx: 1,
+                px: 1000.,
+                py: 0.,
+            },
+            Op::InteractiveMoveEnd { window: 0 },
+            Op::InteractiveResizeBegin {
+                window: 0,
+                edges: ResizeEdge::BOTTOM_RIGHT.bits(),
+            },
+            Op::InteractiveResizeUpdate {
+                window: 0,
+                dx: 100.,
+                dy: -100.,
+            },
+            Op::InteractiveResizeEnd { window: 0 },
+            Op::AddNamedWorkspace {
+                ws_name: 1,
+                output_name: Some(1),
+            },
+            Op::UnnameWorkspace { ws_name: 1 },
+            Op::SetWorkspaceLayout {
+                gaps: Some(0),
+                struts: None,
+                single_preset_width: true,
+            },
         ];
 
         for third in every_op {
@@ -2575,32 +4421,38 @@ mod tests {
     }
 
     #[test]
-    fn primary_active_workspace_idx_not_updated_on_output_add() {
+    fn floating_window_keeps_focus_when_moved_to_workspace() {
         let ops = [
             Op::AddOutput(1),
-            Op::AddOutput(2),
-            Op::FocusOutput(1),
             Op::AddWindow {
                 id: 0,
                 bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
                 min_max_size: Default::default(),
             },
-            Op::FocusOutput(2),
             Op::AddWindow {
                 id: 1,
                 bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
                 min_max_size: Default::default(),
             },
-            Op::RemoveOutput(2),
-            Op::FocusWorkspace(3),
-            Op::AddOutput(2),
+            Op::ToggleWindowFloating { id: None },
+            Op::MoveWindowToWorkspaceDown,
         ];
 
-        check_ops(&ops);
+        let mut layout = Layout::default();
+        for op in ops {
+            op.apply(&mut layout);
+            layout.verify_invariants();
+        }
+
+        assert_eq!(layout.focus().map(|win| win.0.id), Some(1));
+
+        let ws = layout.active_workspace().unwrap();
+        assert!(ws.is_floating(&1));
+        assert!(ws.is_floating_focused());
     }
 
     #[test]
-    fn window_closed_on_previous_workspace() {
+    fn floating_window_stays_reachable_when_view_shrinks() {
         let ops = [
             Op::AddOutput(1),
             Op::AddWindow {
@@ -2608,15 +4460,37 @@ mod tests {
                 bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
                 min_max_size: Default::default(),
             },
-            Op::FocusWorkspaceDown,
-            Op::CloseWindow(0),
+            Op::ToggleWindowFloating { id: None },
         ];
 
-        check_ops(&ops);
+        let mut layout = Layout::default();
+        for op in ops {
+            op.apply(&mut layout);
+            layout.verify_invariants();
+        }
+
+        // Shrink the output so that the centered floating window ends up outside of it.
+        let output = layout.outputs().next().unwrap().clone();
+        output.change_current_state(
+            Some(Mode {
+                size: Size::from((400, 300)),
+                refresh: 60000,
+            }),
+            None,
+            None,
+            None,
+        );
+        layer_map_for_output(&output).arrange();
+        layout.update_output_size(&output);
+        layout.verify_invariants();
+
+        let ws = layout.active_workspace().unwrap();
+        assert!(ws.is_floating(&0));
+        assert!(ws.active_tile_visual_rectangle().is_some());
     }
 
     #[test]
-    fn removing_output_must_keep_empty_focus_on_primary() {
+    fn tabbed_column_gives_every_tab_full_height() {
         let ops = [
             Op::AddOutput(1),
             Op::AddWindow {
@@ -2624,26 +4498,44 @@ mod tests {
                 bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
                 min_max_size: Default::default(),
             },
-            Op::AddOutput(2),
-            Op::RemoveOutput(1),
+            Op::AddWindow {
+                id: 1,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::ConsumeOrExpelWindowLeft { id: None },
         ];
 
         let mut layout = Layout::default();
         for op in ops {
             op.apply(&mut layout);
+            layout.verify_invariants();
         }
 
-        let MonitorSet::Normal { monitors, .. } = layout.monitor_set else {
-            unreachable!()
+        let requested_heights = |layout: &Layout<TestWindow>| {
+            let mut heights = vec![];
+            layout.with_windows(|win, _| heights.push(win.0.requested_size.get().unwrap().h));
+            heights
         };
 
-        // The workspace from the removed output was inserted at position 0, so the active workspace
-        // must change to 1 to keep the focus on the empty workspace.
-        assert_eq!(monitors[0].active_workspace_idx, 1);
+        let stacked = requested_heights(&layout);
+
+        Op::ToggleColumnTabbedDisplay.apply(&mut layout);
+        layout.verify_invariants();
+
+        let tabbed = requested_heights(&layout);
+        assert_eq!(tabbed.len(), 2);
+        assert_eq!(tabbed[0], tabbed[1]);
+        assert!(tabbed[0] > stacked[0]);
+
+        // Focusing up and down switches tabs.
+        assert_eq!(layout.focus().map(|win| win.0.id), Some(1));
+        layout.focus_up();
+        assert_eq!(layout.focus().map(|win| win.0.id), Some(0));
     }
 
     #[test]
-    fn move_to_workspace_by_idx_does_not_leave_empty_workspaces() {
+    fn interactive_move_inserts_new_column() {
         let ops = [
             Op::AddOutput(1),
             Op::AddWindow {
@@ -2651,23 +4543,61 @@ mod tests {
                 bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
                 min_max_size: Default::default(),
             },
-            Op::MoveWindowToWorkspace(2),
+            Op::AddWindow {
+                id: 1,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::AddWindow {
+                id: 2,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::FocusColumnLeft,
+            // Drag the last window to the left edge of the output.
+            Op::InteractiveMoveBegin {
+                window: 2,
+                output_idx: 1,
+                px: 300.,
+                py: 100.,
+            },
+            Op::InteractiveMoveUpdate {
+                window: 2,
+                dx: -300.,
+                dy: 0.,
+                output_idx: 1,
+                px: 0.,
+                py: 100.,
+            },
         ];
 
         let mut layout = Layout::default();
         for op in ops {
             op.apply(&mut layout);
+            layout.verify_invariants();
         }
 
-        let MonitorSet::Normal { monitors, .. } = layout.monitor_set else {
+        // The lifted window keeps the focus.
+        assert_eq!(layout.focus().map(|win| win.0.id), Some(2));
+
+        Op::InteractiveMoveEnd { window: 2 }.apply(&mut layout);
+        layout.verify_invariants();
+
+        let MonitorSet::Normal { monitors, .. } = &layout.monitor_set else {
             unreachable!()
         };
-
-        assert!(monitors[0].workspaces[0].has_windows());
+        let ws = monitors[0].active_workspace_ref();
+        let ids: Vec<_> = ws
+            .columns
+            .iter()
+            .map(|col| col.tiles[0].window().0.id)
+            .collect();
+        assert_eq!(ids, [2, 0, 1]);
+        assert_eq!(layout.focus().map(|win| win.0.id), Some(2));
     }
 
     #[test]
-    fn focus_workspace_by_idx_does_not_leave_empty_workspaces() {
+    fn interactive_resize_sets_column_width() {
         let ops = [
             Op::AddOutput(1),
             Op::AddWindow {
@@ -2675,93 +4605,258 @@ mod tests {
                 bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
                 min_max_size: Default::default(),
             },
-            Op::FocusWorkspaceDown,
-            Op::AddWindow {
-                id: 1,
-                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
-                min_max_size: Default::default(),
+            Op::InteractiveResizeBegin {
+                window: 0,
+                edges: ResizeEdge::RIGHT.bits(),
             },
-            Op::FocusWorkspaceUp,
-            Op::CloseWindow(0),
-            Op::FocusWorkspace(3),
+            Op::InteractiveResizeUpdate {
+                window: 0,
+                dx: 50.,
+                dy: 30.,
+            },
+            Op::InteractiveResizeEnd { window: 0 },
         ];
 
         let mut layout = Layout::default();
         for op in ops {
             op.apply(&mut layout);
+            layout.verify_invariants();
         }
 
-        let MonitorSet::Normal { monitors, .. } = layout.monitor_set else {
-            unreachable!()
-        };
-
-        assert!(monitors[0].workspaces[0].has_windows());
+        let mut widths = vec![];
+        layout.with_windows(|win, _| widths.push(win.0.requested_size.get().unwrap().w));
+        assert_eq!(widths, [150]);
     }
 
     #[test]
-    fn empty_workspaces_dont_move_back_to_original_output() {
+    fn primary_active_workspace_idx_not_updated_on_output_add() {
         let ops = [
             Op::AddOutput(1),
+            Op::AddOutput(2),
+            Op::FocusOutput(1),
             Op::AddWindow {
-                id: 1,
+                id: 0,
                 bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
                 min_max_size: Default::default(),
             },
-            Op::FocusWorkspaceDown,
+            Op::FocusOutput(2),
             Op::AddWindow {
-                id: 2,
+                id: 1,
                 bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
                 min_max_size: Default::default(),
             },
+            Op::RemoveOutput(2),
+            Op::FocusWorkspace(3),
             Op::AddOutput(2),
-            Op::RemoveOutput(1),
-            Op::FocusWorkspace(1),
-            Op::CloseWindow(1),
-            Op::AddOutput(1),
         ];
 
         check_ops(&ops);
     }
 
     #[test]
-    fn large_negative_height_change() {
+    fn window_closed_on_previous_workspace() {
         let ops = [
             Op::AddOutput(1),
             Op::AddWindow {
-                id: 1,
+                id: 0,
                 bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
                 min_max_size: Default::default(),
             },
-            Op::SetWindowHeight(SizeChange::AdjustProportion(-1e129)),
+            Op::FocusWorkspaceDown,
+            Op::CloseWindow(0),
         ];
 
-        let mut options = Options::default();
-        options.border.off = false;
-        options.border.width = 1;
-
-        check_ops_with_options(options, &ops);
+        check_ops(&ops);
     }
 
     #[test]
-    fn large_max_size() {
+    fn removing_output_must_keep_empty_focus_on_primary() {
         let ops = [
             Op::AddOutput(1),
             Op::AddWindow {
-                id: 1,
+                id: 0,
                 bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
-                min_max_size: (Size::from((0, 0)), Size::from((i32::MAX, i32::MAX))),
+                min_max_size: Default::default(),
             },
+            Op::AddOutput(2),
+            Op::RemoveOutput(1),
         ];
 
-        let mut options = Options::default();
-        options.border.off = false;
-        options.border.width = 1;
+        let mut layout = Layout::default();
+        for op in ops {
+            op.apply(&mut layout);
+        }
 
-        check_ops_with_options(options, &ops);
+        let MonitorSet::Normal { monitors, .. } = layout.monitor_set else {
+            unreachable!()
+        };
+
+        // The workspace from the removed output was inserted at position 0, so the active workspace
+        // must change to 1 to keep the focus on the empty workspace.
+        assert_eq!(monitors[0].active_workspace_idx, 1);
     }
 
     #[test]
-    fn workspace_cleanup_during_switch() {
+    fn move_to_workspace_by_idx_does_not_leave_empty_workspaces() {
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddWindow {
+                id: 0,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::MoveWindowToWorkspace {
+                window_id: None,
+                workspace_idx: 2,
+            },
+        ];
+
+        let mut layout = Layout::default();
+        for op in ops {
+            op.apply(&mut layout);
+        }
+
+        let MonitorSet::Normal { monitors, .. } = layout.monitor_set else {
+            unreachable!()
+        };
+
+        assert!(monitors[0].workspaces[0].has_windows());
+    }
+
+    #[test]
+    fn move_unfocused_window_to_workspace_keeps_focus() {
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddWindow {
+                id: 0,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::AddWindow {
+                id: 1,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::MoveWindowToWorkspace {
+                window_id: Some(0),
+                workspace_idx: 1,
+            },
+        ];
+
+        let mut layout = Layout::default();
+        for op in ops {
+            op.apply(&mut layout);
+        }
+
+        assert_eq!(layout.focus().map(|win| win.0.id), Some(1));
+
+        let MonitorSet::Normal { monitors, .. } = layout.monitor_set else {
+            unreachable!()
+        };
+
+        assert_eq!(monitors[0].active_workspace_idx, 0);
+        assert!(monitors[0].workspaces[1].has_window(&0));
+    }
+
+    #[test]
+    fn focus_workspace_by_idx_does_not_leave_empty_workspaces() {
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddWindow {
+                id: 0,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::FocusWorkspaceDown,
+            Op::AddWindow {
+                id: 1,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::FocusWorkspaceUp,
+            Op::CloseWindow(0),
+            Op::FocusWorkspace(3),
+        ];
+
+        let mut layout = Layout::default();
+        for op in ops {
+            op.apply(&mut layout);
+        }
+
+        let MonitorSet::Normal { monitors, .. } = layout.monitor_set else {
+            unreachable!()
+        };
+
+        assert!(monitors[0].workspaces[0].has_windows());
+    }
+
+    #[test]
+    fn empty_workspaces_dont_move_back_to_original_output() {
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddWindow {
+                id: 1,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::FocusWorkspaceDown,
+            Op::AddWindow {
+                id: 2,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::AddOutput(2),
+            Op::RemoveOutput(1),
+            Op::FocusWorkspace(1),
+            Op::CloseWindow(1),
+            Op::AddOutput(1),
+        ];
+
+        check_ops(&ops);
+    }
+
+    #[test]
+    fn large_negative_height_change() {
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddWindow {
+                id: 1,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::SetWindowHeight {
+                id: None,
+                change: SizeChange::AdjustProportion(-1e129),
+            },
+        ];
+
+        let mut options = Options::default();
+        options.border.off = false;
+        options.border.width = 1;
+
+        check_ops_with_options(options, &ops);
+    }
+
+    #[test]
+    fn large_max_size() {
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddWindow {
+                id: 1,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: (Size::from((0, 0)), Size::from((i32::MAX, i32::MAX))),
+            },
+        ];
+
+        let mut options = Options::default();
+        options.border.off = false;
+        options.border.width = 1;
+
+        check_ops_with_options(options, &ops);
+    }
+
+    #[test]
+    fn workspace_cleanup_during_switch() {
         let ops = [
             Op::AddOutput(1),
             Op::AddWindow {
@@ -2874,6 +4969,506 @@ mod tests {
         assert!(monitors[1].workspaces[0].has_windows());
     }
 
+    #[test]
+    fn named_workspace_is_not_cleaned_up() {
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddNamedWorkspace {
+                ws_name: 1,
+                output_name: None,
+            },
+            Op::AddWindow {
+                id: 0,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::FocusWorkspace(1),
+            Op::FocusWorkspace(0),
+            Op::CloseWindow(0),
+            Op::FocusWorkspace(2),
+        ];
+
+        let mut layout = Layout::default();
+        for op in ops {
+            op.apply(&mut layout);
+            layout.verify_invariants();
+        }
+
+        let (_, idx, ws) = layout.find_workspace_by_name("WS1").unwrap();
+        assert_eq!(idx, 0);
+        assert!(!ws.has_windows());
+
+        Op::UnnameWorkspace { ws_name: 1 }.apply(&mut layout);
+        layout.verify_invariants();
+        assert!(layout.find_workspace_by_name("ws1").is_none());
+    }
+
+    #[test]
+    fn named_workspace_follows_its_output() {
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddNamedWorkspace {
+                ws_name: 1,
+                output_name: Some(2),
+            },
+        ];
+
+        let mut layout = Layout::default();
+        for op in ops {
+            op.apply(&mut layout);
+            layout.verify_invariants();
+        }
+
+        let output_of_ws1 = |layout: &Layout<TestWindow>| {
+            let (mon, _, _) = layout.find_workspace_by_name("ws1").unwrap();
+            mon.map(|mon| mon.output.name())
+        };
+
+        // The configured output is missing, so the workspace opens on the primary output.
+        assert_eq!(output_of_ws1(&layout).as_deref(), Some("output1"));
+
+        Op::AddOutput(2).apply(&mut layout);
+        layout.verify_invariants();
+        assert_eq!(output_of_ws1(&layout).as_deref(), Some("output2"));
+
+        Op::RemoveOutput(2).apply(&mut layout);
+        layout.verify_invariants();
+        assert_eq!(output_of_ws1(&layout).as_deref(), Some("output1"));
+
+        Op::RemoveOutput(1).apply(&mut layout);
+        layout.verify_invariants();
+        assert_eq!(output_of_ws1(&layout), None);
+
+        Op::AddOutput(2).apply(&mut layout);
+        layout.verify_invariants();
+        assert_eq!(output_of_ws1(&layout).as_deref(), Some("output2"));
+    }
+
+    #[test]
+    fn pick_workspace_in_overview() {
+        let mut options = Options::default();
+        options.animations.overview_open_close.off = true;
+        let mut layout = Layout::with_options(options);
+
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddWindow {
+                id: 0,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::ToggleOverview,
+        ];
+
+        for op in ops {
+            op.apply(&mut layout);
+            layout.verify_invariants();
+        }
+
+        assert!(layout.is_overview_open());
+        assert_eq!(layout.active_monitor_ref().unwrap().active_workspace_idx, 0);
+
+        // The overview shows two workspaces stacked on top of each other, pick the bottom one.
+        Op::PickInOverview {
+            output_id: 1,
+            x: 640.,
+            y: 600.,
+        }
+        .apply(&mut layout);
+        layout.verify_invariants();
+
+        assert!(!layout.is_overview_open());
+        assert_eq!(layout.active_monitor_ref().unwrap().active_workspace_idx, 1);
+    }
+
+    #[test]
+    fn window_on_background_workspace_does_not_switch() {
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddWindow {
+                id: 0,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::AddWindowOnOutput {
+                id: 1,
+                output_id: 1,
+                ws_idx: Some(1),
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+        ];
+
+        let mut layout = Layout::default();
+        for op in ops {
+            op.apply(&mut layout);
+            layout.verify_invariants();
+        }
+
+        let MonitorSet::Normal { monitors, .. } = &layout.monitor_set else {
+            unreachable!()
+        };
+
+        let mon = &monitors[0];
+        assert_eq!(mon.active_workspace_idx, 0);
+        assert_eq!(mon.workspaces.len(), 3);
+        assert!(mon.workspaces[1].has_window(&1));
+    }
+
+    #[test]
+    fn preset_window_heights_removed_from_config() {
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddWindow {
+                id: 0,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::AddWindow {
+                id: 1,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::ConsumeOrExpelWindowLeft { id: None },
+        ];
+
+        let mut layout = Layout::default();
+        for op in ops {
+            op.apply(&mut layout);
+            layout.verify_invariants();
+        }
+
+        let requested_heights = |layout: &Layout<TestWindow>| {
+            let mut heights = vec![];
+            layout.with_windows(|win, _| heights.push(win.0.requested_size.get().unwrap().h));
+            heights
+        };
+        let column_heights = |layout: &Layout<TestWindow>| {
+            layout.active_workspace().unwrap().columns[0]
+                .heights()
+                .to_vec()
+        };
+
+        // The working area is 720 px high with 16 px gaps, so the presets of 1/3, 1/2 and 2/3
+        // resolve to 219, 336 and 453 px. The test windows stay at their initial 200 px height,
+        // so cycling starts from the first preset, and the other window takes the rest.
+        let expected = [(0, [453, 219]), (1, [336, 336]), (2, [219, 453])];
+        for (preset, heights) in expected {
+            Op::SwitchPresetWindowHeight { id: None }.apply(&mut layout);
+            layout.verify_invariants();
+
+            assert_eq!(
+                column_heights(&layout),
+                [WindowHeight::Auto, WindowHeight::Preset(preset)]
+            );
+            assert_eq!(requested_heights(&layout), heights);
+        }
+
+        let mut config = Config::default();
+        config.layout.preset_window_heights = vec![PresetSize::Fixed(300)];
+        layout.update_config(&config);
+        layout.verify_invariants();
+
+        // The tile on a removed preset keeps its height.
+        assert_eq!(
+            column_heights(&layout),
+            [WindowHeight::Auto, WindowHeight::Fixed(453)]
+        );
+        assert_eq!(requested_heights(&layout), [219, 453]);
+
+        Op::SwitchPresetWindowHeight { id: None }.apply(&mut layout);
+        layout.verify_invariants();
+        assert_eq!(
+            column_heights(&layout),
+            [WindowHeight::Auto, WindowHeight::Preset(0)]
+        );
+        assert_eq!(requested_heights(&layout), [372, 300]);
+
+        Op::ResetWindowHeight { id: None }.apply(&mut layout);
+        layout.verify_invariants();
+        assert_eq!(
+            column_heights(&layout),
+            [WindowHeight::Auto, WindowHeight::Auto]
+        );
+        assert_eq!(requested_heights(&layout), [336, 336]);
+    }
+
+    #[test]
+    fn output_layout_override() {
+        let mut config = Config::default();
+        config.outputs.push(niri_config::Output {
+            name: String::from("output1"),
+            layout: Some(LayoutOverride {
+                preset_column_widths: Some(vec![PresetSize::Fixed(500)]),
+                gaps: Some(0),
+                struts: Some(Struts {
+                    left: 50,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddOutput(2),
+            Op::AddWindow {
+                id: 0,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::SwitchPresetColumnWidth,
+            Op::MoveColumnToOutput(2),
+            Op::SwitchPresetColumnWidth,
+            Op::MoveWorkspaceToOutput(1),
+            Op::RemoveOutput(1),
+            Op::AddOutput(1),
+        ];
+
+        let mut layout = Layout::new(&config);
+        for op in ops {
+            op.apply(&mut layout);
+            layout.verify_invariants();
+        }
+
+        layout.update_config(&Config::default());
+        layout.verify_invariants();
+    }
+
+    #[test]
+    fn workspace_layout_override() {
+        let mut config = Config::default();
+        config.workspaces.push(WorkspaceConfig {
+            name: String::from("ws1"),
+            open_on_output: None,
+            layout: Some(LayoutOverride {
+                center_focused_column: Some(CenterFocusedColumn::Always),
+                gaps: Some(0),
+                ..Default::default()
+            }),
+        });
+
+        let mut layout = Layout::new(&config);
+        Op::AddOutput(1).apply(&mut layout);
+        layout.verify_invariants();
+
+        let (_, _, ws) = layout.find_workspace_by_name("ws1").unwrap();
+        assert_eq!(
+            ws.options.center_focused_column,
+            CenterFocusedColumn::Always
+        );
+        assert_eq!(ws.options.gaps, 0);
+        let ws_id = ws.id();
+
+        layout.set_workspace_layout(LayoutOverride {
+            gaps: Some(8),
+            ..Default::default()
+        });
+        layout.verify_invariants();
+
+        // The runtime override goes on top of the config one and survives config reloads.
+        layout.update_config(&config);
+        layout.verify_invariants();
+        let (_, _, ws) = layout.find_workspace_by_name("ws1").unwrap();
+        assert_eq!(
+            ws.options.center_focused_column,
+            CenterFocusedColumn::Always
+        );
+        assert_eq!(ws.options.gaps, 8);
+
+        // Removing the workspace from the config removes its config override.
+        layout.update_config(&Config::default());
+        layout.verify_invariants();
+        let (_, _, ws) = layout
+            .workspaces()
+            .find(|(_, _, ws)| ws.id() == ws_id)
+            .unwrap();
+        assert_eq!(ws.options.center_focused_column, CenterFocusedColumn::Never);
+        assert_eq!(ws.options.gaps, 8);
+    }
+
+    #[test]
+    fn focus_and_move_across_outputs() {
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddOutput(2),
+            Op::AddWindow {
+                id: 0,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::AddWindow {
+                id: 1,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+        ];
+
+        let mut layout = Layout::default();
+        for op in ops {
+            op.apply(&mut layout);
+            layout.verify_invariants();
+        }
+
+        // Window 1 is in the rightmost column, so the focus moves to the other output.
+        Op::FocusColumnLeftOrOutput(2).apply(&mut layout);
+        assert_eq!(layout.active_output().unwrap().name(), "output1");
+        Op::FocusColumnRightOrOutput(2).apply(&mut layout);
+        assert_eq!(layout.active_output().unwrap().name(), "output1");
+        Op::FocusColumnRightOrOutput(2).apply(&mut layout);
+        assert_eq!(layout.active_output().unwrap().name(), "output2");
+
+        Op::FocusOutput(1).apply(&mut layout);
+        Op::MoveColumnLeftOrToOutput(2).apply(&mut layout);
+        layout.verify_invariants();
+        assert_eq!(layout.active_output().unwrap().name(), "output1");
+        Op::MoveColumnLeftOrToOutput(2).apply(&mut layout);
+        layout.verify_invariants();
+        assert_eq!(layout.active_output().unwrap().name(), "output2");
+        assert_eq!(layout.focus().unwrap().0.id, 1);
+
+        Op::MoveWindowDownOrToOutput(1).apply(&mut layout);
+        layout.verify_invariants();
+        assert_eq!(layout.active_output().unwrap().name(), "output1");
+        assert_eq!(layout.focus().unwrap().0.id, 1);
+    }
+
+    #[test]
+    fn move_column_to_index() {
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddWindow {
+                id: 0,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::AddWindow {
+                id: 1,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::AddWindow {
+                id: 2,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::MoveColumnToIndex(1),
+            Op::FocusColumn(10),
+        ];
+
+        let mut layout = Layout::default();
+        for op in ops {
+            op.apply(&mut layout);
+            layout.verify_invariants();
+        }
+
+        let ws = layout.active_workspace().unwrap();
+        let ids = ws
+            .columns
+            .iter()
+            .map(|col| *col.tiles[0].window().id())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [2, 0, 1]);
+        assert_eq!(ws.active_column_idx, 2);
+    }
+
+    #[test]
+    fn scratchpad_hide_and_summon() {
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddWindow {
+                id: 0,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::AddWindow {
+                id: 1,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::MoveWindowToScratchpad { id: None },
+        ];
+
+        let mut layout = Layout::default();
+        for op in ops {
+            op.apply(&mut layout);
+            layout.verify_invariants();
+        }
+
+        assert!(!layout.active_workspace().unwrap().has_window(&1));
+        assert_eq!(layout.scratchpad.len(), 1);
+
+        Op::ToggleScratchpad.apply(&mut layout);
+        layout.verify_invariants();
+        assert!(layout.active_workspace().unwrap().is_floating(&1));
+        assert!(layout.scratchpad.is_empty());
+
+        Op::ToggleScratchpad.apply(&mut layout);
+        layout.verify_invariants();
+        assert!(!layout.active_workspace().unwrap().has_window(&1));
+        assert_eq!(layout.scratchpad.len(), 1);
+
+        Op::CloseWindow(1).apply(&mut layout);
+        layout.verify_invariants();
+        assert!(layout.scratchpad.is_empty());
+    }
+
+    #[test]
+    fn swap_window_keeps_column_shapes() {
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddWindow {
+                id: 0,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::AddWindow {
+                id: 1,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::FocusColumnLeft,
+            Op::ConsumeWindowIntoColumn,
+            Op::FocusWindowDown,
+            Op::AddWindow {
+                id: 2,
+                bbox: Rectangle::from_loc_and_size((0, 0), (100, 200)),
+                min_max_size: Default::default(),
+            },
+            Op::SetColumnWidth {
+                id: None,
+                change: SizeChange::SetFixed(500),
+            },
+        ];
+
+        let mut layout = Layout::default();
+        for op in ops {
+            op.apply(&mut layout);
+            layout.verify_invariants();
+        }
+
+        let ws = layout.active_workspace().unwrap();
+        let widths = ws.columns.iter().map(|col| col.width).collect::<Vec<_>>();
+
+        Op::SwapWindowLeft.apply(&mut layout);
+        layout.verify_invariants();
+
+        let ws = layout.active_workspace().unwrap();
+        let ids = ws
+            .columns
+            .iter()
+            .map(|col| col.tiles.iter().map(|tile| *tile.window().id()).collect())
+            .collect::<Vec<Vec<_>>>();
+        assert_eq!(ids, [vec![0, 2], vec![1]]);
+        assert_eq!(
+            ws.columns.iter().map(|col| col.width).collect::<Vec<_>>(),
+            widths
+        );
+        assert_eq!(ws.active_column_idx, 0);
+        assert_eq!(ws.columns[0].active_tile_idx, 1);
+    }
+
     #[test]
     fn fullscreen() {
         let ops = [
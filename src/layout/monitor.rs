@@ -2,19 +2,20 @@ use std::cmp::min;
 use std::rc::Rc;
 use std::time::Duration;
 
-use niri_ipc::SizeChange;
+use niri_config::LayoutOverride;
 use smithay::backend::renderer::element::utils::{
-    CropRenderElement, Relocate, RelocateRenderElement,
+    CropRenderElement, Relocate, RelocateRenderElement, RescaleRenderElement,
 };
 use smithay::output::Output;
 use smithay::utils::{Logical, Point, Rectangle, Scale};
 
 use super::workspace::{
-    compute_working_area, Column, ColumnWidth, OutputId, Workspace, WorkspaceId,
+    Column, ColumnWidth, InsertPosition, OutputId, WindowPlacement, Workspace, WorkspaceId,
     WorkspaceRenderElement,
 };
 use super::{LayoutElement, Options};
 use crate::animation::Animation;
+use crate::niri_render_elements;
 use crate::render_helpers::renderer::NiriRenderer;
 use crate::render_helpers::RenderTarget;
 use crate::rubber_band::RubberBand;
@@ -29,6 +30,15 @@ const WORKSPACE_GESTURE_RUBBER_BAND: RubberBand = RubberBand {
     limit: 0.05,
 };
 
+/// Largest zoom of the workspaces in the overview.
+const OVERVIEW_MAX_ZOOM: f64 = 0.5;
+
+/// Smallest zoom of the workspaces in the overview, for when there are a lot of them.
+const OVERVIEW_MIN_ZOOM: f64 = 0.05;
+
+/// Gap between the workspaces in the overview.
+const OVERVIEW_GAP: f64 = 16.;
+
 #[derive(Debug)]
 pub struct Monitor<W: LayoutElement> {
     /// Output for this monitor.
@@ -41,6 +51,10 @@ pub struct Monitor<W: LayoutElement> {
     pub previous_workspace_id: Option<WorkspaceId>,
     /// In-progress switch between workspaces.
     pub workspace_switch: Option<WorkspaceSwitch>,
+    /// Whether the overview of all workspaces is open.
+    pub overview_open: bool,
+    /// In-progress animation of the overview opening or closing.
+    pub overview_anim: Option<Animation>,
     /// Configurable properties of the layout.
     pub options: Rc<Options>,
 }
@@ -60,8 +74,14 @@ pub struct WorkspaceSwitchGesture {
     pub tracker: SwipeTracker,
 }
 
-pub type MonitorRenderElement<R> =
-    RelocateRenderElement<CropRenderElement<WorkspaceRenderElement<R>>>;
+niri_render_elements! {
+    MonitorRenderElement<R> => {
+        Workspace = RelocateRenderElement<CropRenderElement<WorkspaceRenderElement<R>>>,
+        Overview = RelocateRenderElement<
+            RescaleRenderElement<CropRenderElement<WorkspaceRenderElement<R>>>
+        >,
+    }
+}
 
 impl WorkspaceSwitch {
     pub fn current_idx(&self) -> f64 {
@@ -95,6 +115,8 @@ impl<W: LayoutElement> Monitor<W> {
             active_workspace_idx: 0,
             previous_workspace_id: None,
             workspace_switch: None,
+            overview_open: false,
+            overview_anim: None,
             options,
         }
     }
@@ -139,13 +161,36 @@ impl<W: LayoutElement> Monitor<W> {
         activate: bool,
         width: ColumnWidth,
         is_full_width: bool,
+    ) {
+        let placement = WindowPlacement::Column {
+            width,
+            is_full_width,
+        };
+        self.add_window_with_placement(workspace_idx, window, activate, placement);
+    }
+
+    pub fn add_window_with_placement(
+        &mut self,
+        workspace_idx: usize,
+        window: W,
+        activate: bool,
+        placement: WindowPlacement,
     ) {
         let workspace = &mut self.workspaces[workspace_idx];
 
-        workspace.add_window(window, activate, width, is_full_width);
+        match placement {
+            WindowPlacement::Column {
+                width,
+                is_full_width,
+            } => workspace.add_window(window, activate, width, is_full_width),
+            WindowPlacement::Floating => workspace.add_floating_window(window, activate),
+        }
 
-        // After adding a new window, workspace becomes this output's own.
-        workspace.original_output = OutputId::new(&self.output);
+        // After adding a new window, workspace becomes this output's own. Named workspaces keep
+        // their original output.
+        if workspace.name.is_none() {
+            workspace.original_output = OutputId::new(&self.output);
+        }
 
         if workspace_idx == self.workspaces.len() - 1 {
             // Insert a new empty workspace.
@@ -158,13 +203,7 @@ impl<W: LayoutElement> Monitor<W> {
         }
     }
 
-    pub fn add_window_right_of(
-        &mut self,
-        right_of: &W::Id,
-        window: W,
-        width: ColumnWidth,
-        is_full_width: bool,
-    ) {
+    pub fn add_window_right_of(&mut self, right_of: &W::Id, window: W, placement: WindowPlacement) {
         let workspace_idx = self
             .workspaces
             .iter_mut()
@@ -172,10 +211,72 @@ impl<W: LayoutElement> Monitor<W> {
             .unwrap();
         let workspace = &mut self.workspaces[workspace_idx];
 
-        workspace.add_window_right_of(right_of, window, width, is_full_width);
+        match placement {
+            WindowPlacement::Column {
+                width,
+                is_full_width,
+            } => workspace.add_window_right_of(right_of, window, width, is_full_width),
+            WindowPlacement::Floating => {
+                // Activate the new window if right_of was active.
+                let activate = workspace
+                    .active_window()
+                    .is_some_and(|win| win.id() == right_of);
+                workspace.add_floating_window(window, activate);
+            }
+        }
+
+        // After adding a new window, workspace becomes this output's own. Named workspaces keep
+        // their original output.
+        if workspace.name.is_none() {
+            workspace.original_output = OutputId::new(&self.output);
+        }
+    }
+
+    /// Inserts an interactively moved window into the workspace and activates it.
+    pub fn insert_window(
+        &mut self,
+        workspace_idx: usize,
+        window: W,
+        position: InsertPosition,
+        placement: WindowPlacement,
+    ) {
+        let workspace = &mut self.workspaces[workspace_idx];
+
+        workspace.insert_window(position, window, placement);
+
+        // After adding a new window, workspace becomes this output's own. Named workspaces keep
+        // their original output.
+        if workspace.name.is_none() {
+            workspace.original_output = OutputId::new(&self.output);
+        }
+
+        if workspace_idx == self.workspaces.len() - 1 {
+            // Insert a new empty workspace.
+            let ws = Workspace::new(self.output.clone(), self.options.clone());
+            self.workspaces.push(ws);
+        }
+
+        self.activate_workspace(workspace_idx);
+    }
+
+    /// Adds a window summoned from the scratchpad to the active workspace.
+    pub fn add_scratchpad_window(&mut self, window: W) {
+        let workspace_idx = self.active_workspace_idx;
+        let workspace = &mut self.workspaces[workspace_idx];
+
+        workspace.add_scratchpad_window(window);
 
-        // After adding a new window, workspace becomes this output's own.
-        workspace.original_output = OutputId::new(&self.output);
+        // After adding a new window, workspace becomes this output's own. Named workspaces keep
+        // their original output.
+        if workspace.name.is_none() {
+            workspace.original_output = OutputId::new(&self.output);
+        }
+
+        if workspace_idx == self.workspaces.len() - 1 {
+            // Insert a new empty workspace.
+            let ws = Workspace::new(self.output.clone(), self.options.clone());
+            self.workspaces.push(ws);
+        }
     }
 
     pub fn add_column(&mut self, workspace_idx: usize, column: Column<W>, activate: bool) {
@@ -183,8 +284,11 @@ impl<W: LayoutElement> Monitor<W> {
 
         workspace.add_column(column, activate);
 
-        // After adding a new window, workspace becomes this output's own.
-        workspace.original_output = OutputId::new(&self.output);
+        // After adding a new window, workspace becomes this output's own. Named workspaces keep
+        // their original output.
+        if workspace.name.is_none() {
+            workspace.original_output = OutputId::new(&self.output);
+        }
 
         if workspace_idx == self.workspaces.len() - 1 {
             // Insert a new empty workspace.
@@ -197,6 +301,21 @@ impl<W: LayoutElement> Monitor<W> {
         }
     }
 
+    /// Adds a workspace right before the last, empty, workspace.
+    pub fn add_workspace(&mut self, workspace: Workspace<W>) {
+        let idx = self.workspaces.len() - 1;
+        self.workspaces.insert(idx, workspace);
+
+        if idx <= self.active_workspace_idx {
+            self.active_workspace_idx += 1;
+        }
+
+        // The indices of an ongoing workspace switch no longer match.
+        if self.workspace_switch.take().is_some() {
+            self.clean_up_workspaces();
+        }
+    }
+
     pub fn clean_up_workspaces(&mut self) {
         assert!(self.workspace_switch.is_none());
 
@@ -205,7 +324,7 @@ impl<W: LayoutElement> Monitor<W> {
                 continue;
             }
 
-            if !self.workspaces[idx].has_windows() {
+            if !self.workspaces[idx].has_windows_or_name() {
                 self.workspaces.remove(idx);
                 if self.active_workspace_idx > idx {
                     self.active_workspace_idx -= 1;
@@ -230,6 +349,10 @@ impl<W: LayoutElement> Monitor<W> {
         self.active_workspace().move_column_to_last();
     }
 
+    pub fn move_column_to_index(&mut self, index: usize) {
+        self.active_workspace().move_column_to_index(index);
+    }
+
     pub fn move_down(&mut self) {
         self.active_workspace().move_down();
     }
@@ -240,6 +363,10 @@ impl<W: LayoutElement> Monitor<W> {
 
     pub fn move_down_or_to_workspace_down(&mut self) {
         let workspace = self.active_workspace();
+        if workspace.is_floating_focused() {
+            workspace.move_down();
+            return;
+        }
         if workspace.columns.is_empty() {
             return;
         }
@@ -255,6 +382,10 @@ impl<W: LayoutElement> Monitor<W> {
 
     pub fn move_up_or_to_workspace_up(&mut self) {
         let workspace = self.active_workspace();
+        if workspace.is_floating_focused() {
+            workspace.move_up();
+            return;
+        }
         if workspace.columns.is_empty() {
             return;
         }
@@ -267,14 +398,6 @@ impl<W: LayoutElement> Monitor<W> {
         }
     }
 
-    pub fn consume_or_expel_window_left(&mut self) {
-        self.active_workspace().consume_or_expel_window_left();
-    }
-
-    pub fn consume_or_expel_window_right(&mut self) {
-        self.active_workspace().consume_or_expel_window_right();
-    }
-
     pub fn focus_left(&mut self) {
         self.active_workspace().focus_left();
     }
@@ -291,6 +414,10 @@ impl<W: LayoutElement> Monitor<W> {
         self.active_workspace().focus_column_last();
     }
 
+    pub fn focus_column(&mut self, index: usize) {
+        self.active_workspace().focus_column(index);
+    }
+
     pub fn focus_down(&mut self) {
         self.active_workspace().focus_down();
     }
@@ -301,7 +428,9 @@ impl<W: LayoutElement> Monitor<W> {
 
     pub fn focus_window_or_workspace_down(&mut self) {
         let workspace = self.active_workspace();
-        if workspace.columns.is_empty() {
+        if workspace.is_floating_focused() {
+            workspace.focus_down();
+        } else if workspace.columns.is_empty() {
             self.switch_workspace_down();
         } else {
             let column = &workspace.columns[workspace.active_column_idx];
@@ -317,7 +446,9 @@ impl<W: LayoutElement> Monitor<W> {
 
     pub fn focus_window_or_workspace_up(&mut self) {
         let workspace = self.active_workspace();
-        if workspace.columns.is_empty() {
+        if workspace.is_floating_focused() {
+            workspace.focus_up();
+        } else if workspace.columns.is_empty() {
             self.switch_workspace_up();
         } else {
             let curr_idx = workspace.columns[workspace.active_column_idx].active_tile_idx;
@@ -339,17 +470,11 @@ impl<W: LayoutElement> Monitor<W> {
         }
 
         let workspace = &mut self.workspaces[source_workspace_idx];
-        if workspace.columns.is_empty() {
+        let Some((window, placement)) = workspace.take_window(None) else {
             return;
-        }
-
-        let column = &workspace.columns[workspace.active_column_idx];
-        let width = column.width;
-        let is_full_width = column.is_full_width;
-        let window =
-            workspace.remove_window_by_idx(workspace.active_column_idx, column.active_tile_idx);
+        };
 
-        self.add_window(new_idx, window, true, width, is_full_width);
+        self.add_window_with_placement(new_idx, window, true, placement);
     }
 
     pub fn move_to_workspace_down(&mut self) {
@@ -361,44 +486,52 @@ impl<W: LayoutElement> Monitor<W> {
         }
 
         let workspace = &mut self.workspaces[source_workspace_idx];
-        if workspace.columns.is_empty() {
+        let Some((window, placement)) = workspace.take_window(None) else {
             return;
-        }
-
-        let column = &workspace.columns[workspace.active_column_idx];
-        let width = column.width;
-        let is_full_width = column.is_full_width;
-        let window =
-            workspace.remove_window_by_idx(workspace.active_column_idx, column.active_tile_idx);
+        };
 
-        self.add_window(new_idx, window, true, width, is_full_width);
+        self.add_window_with_placement(new_idx, window, true, placement);
     }
 
-    pub fn move_to_workspace(&mut self, idx: usize) {
-        let source_workspace_idx = self.active_workspace_idx;
+    pub fn move_to_workspace(&mut self, window: Option<&W::Id>, idx: usize) {
+        let source_workspace_idx = if let Some(window) = window {
+            self.workspaces
+                .iter()
+                .position(|ws| ws.has_window(window))
+                .unwrap()
+        } else {
+            self.active_workspace_idx
+        };
 
         let new_idx = min(idx, self.workspaces.len() - 1);
         if new_idx == source_workspace_idx {
             return;
         }
 
+        // Only follow the window if it was focused.
+        let activate = match window {
+            Some(window) => {
+                source_workspace_idx == self.active_workspace_idx
+                    && self.focus().map(|win| win.id()) == Some(window)
+            }
+            None => true,
+        };
+
         let workspace = &mut self.workspaces[source_workspace_idx];
-        if workspace.columns.is_empty() {
+        let Some((window, placement)) = workspace.take_window(window) else {
             return;
-        }
-
-        let column = &workspace.columns[workspace.active_column_idx];
-        let width = column.width;
-        let is_full_width = column.is_full_width;
-        let window =
-            workspace.remove_window_by_idx(workspace.active_column_idx, column.active_tile_idx);
+        };
 
-        self.add_window(new_idx, window, true, width, is_full_width);
+        self.add_window_with_placement(new_idx, window, activate, placement);
 
-        // Don't animate this action.
-        self.workspace_switch = None;
+        if activate {
+            // Don't animate this action.
+            self.workspace_switch = None;
+        }
 
-        self.clean_up_workspaces();
+        if self.workspace_switch.is_none() {
+            self.clean_up_workspaces();
+        }
     }
 
     pub fn move_column_to_workspace_up(&mut self) {
@@ -410,7 +543,7 @@ impl<W: LayoutElement> Monitor<W> {
         }
 
         let workspace = &mut self.workspaces[source_workspace_idx];
-        if workspace.columns.is_empty() {
+        if workspace.columns.is_empty() || workspace.is_floating_focused() {
             return;
         }
 
@@ -427,7 +560,7 @@ impl<W: LayoutElement> Monitor<W> {
         }
 
         let workspace = &mut self.workspaces[source_workspace_idx];
-        if workspace.columns.is_empty() {
+        if workspace.columns.is_empty() || workspace.is_floating_focused() {
             return;
         }
 
@@ -444,7 +577,7 @@ impl<W: LayoutElement> Monitor<W> {
         }
 
         let workspace = &mut self.workspaces[source_workspace_idx];
-        if workspace.columns.is_empty() {
+        if workspace.columns.is_empty() || workspace.is_floating_focused() {
             return;
         }
 
@@ -507,18 +640,28 @@ impl<W: LayoutElement> Monitor<W> {
         self.active_workspace().expel_from_column();
     }
 
+    pub fn swap_window_left(&mut self) {
+        self.active_workspace().swap_window_left();
+    }
+
+    pub fn swap_window_right(&mut self) {
+        self.active_workspace().swap_window_right();
+    }
+
     pub fn center_column(&mut self) {
         self.active_workspace().center_column();
     }
 
+    pub fn toggle_column_tabbed_display(&mut self) {
+        self.active_workspace().toggle_column_tabbed_display();
+    }
+
     pub fn focus(&self) -> Option<&W> {
-        let workspace = &self.workspaces[self.active_workspace_idx];
-        if !workspace.has_windows() {
-            return None;
-        }
+        self.workspaces[self.active_workspace_idx].active_window()
+    }
 
-        let column = &workspace.columns[workspace.active_column_idx];
-        Some(column.tiles[column.active_tile_idx].window())
+    pub fn switch_focus_floating_tiling(&mut self) {
+        self.active_workspace().switch_focus_floating_tiling();
     }
 
     pub fn advance_animations(&mut self, current_time: Duration, is_active: bool) {
@@ -530,6 +673,13 @@ impl<W: LayoutElement> Monitor<W> {
             }
         }
 
+        if let Some(anim) = &mut self.overview_anim {
+            anim.set_current_time(current_time);
+            if anim.is_done() {
+                self.overview_anim = None;
+            }
+        }
+
         for ws in &mut self.workspaces {
             ws.advance_animations(current_time, is_active);
         }
@@ -539,31 +689,29 @@ impl<W: LayoutElement> Monitor<W> {
         self.workspace_switch
             .as_ref()
             .is_some_and(|s| s.is_animation())
+            || self.overview_anim.is_some()
             || self.workspaces.iter().any(|ws| ws.are_animations_ongoing())
     }
 
     pub fn are_transitions_ongoing(&self) -> bool {
         self.workspace_switch.is_some()
+            || self.overview_anim.is_some()
             || self.workspaces.iter().any(|ws| ws.are_animations_ongoing())
     }
 
     pub fn update_config(&mut self, options: Rc<Options>) {
+        // Workspaces update their working area themselves, as they can override the struts.
         for ws in &mut self.workspaces {
             ws.update_config(options.clone());
         }
 
-        if self.options.struts != options.struts {
-            let view_size = output_size(&self.output);
-            let working_area = compute_working_area(&self.output, options.struts);
-
-            for ws in &mut self.workspaces {
-                ws.set_view_size(view_size, working_area);
-            }
-        }
-
         self.options = options;
     }
 
+    pub fn set_workspace_layout(&mut self, layout: LayoutOverride) {
+        self.active_workspace().set_runtime_layout_override(layout);
+    }
+
     pub fn toggle_width(&mut self) {
         self.active_workspace().toggle_width();
     }
@@ -572,14 +720,6 @@ impl<W: LayoutElement> Monitor<W> {
         self.active_workspace().toggle_full_width();
     }
 
-    pub fn set_column_width(&mut self, change: SizeChange) {
-        self.active_workspace().set_column_width(change);
-    }
-
-    pub fn set_window_height(&mut self, change: SizeChange) {
-        self.active_workspace().set_window_height(change);
-    }
-
     pub fn move_workspace_down(&mut self) {
         let new_idx = min(self.active_workspace_idx + 1, self.workspaces.len() - 1);
         if new_idx == self.active_workspace_idx {
@@ -624,6 +764,109 @@ impl<W: LayoutElement> Monitor<W> {
         self.clean_up_workspaces();
     }
 
+    pub fn is_overview_open(&self) -> bool {
+        self.overview_open
+    }
+
+    /// Returns whether the overview is open or is animating.
+    pub fn is_overview_visible(&self) -> bool {
+        self.overview_open || self.overview_anim.is_some()
+    }
+
+    pub fn set_overview_open(&mut self, open: bool) {
+        if self.overview_open == open {
+            return;
+        }
+
+        let current = self.overview_progress();
+        self.overview_open = open;
+        self.overview_anim = Some(Animation::new(
+            current,
+            if open { 1. } else { 0. },
+            0.,
+            self.options.animations.overview_open_close,
+            niri_config::Animation::default_overview_open_close(),
+        ));
+    }
+
+    pub fn toggle_overview(&mut self) {
+        self.set_overview_open(!self.overview_open);
+    }
+
+    /// Returns how far the overview is open, from 0 (closed) to 1 (open).
+    fn overview_progress(&self) -> f64 {
+        match &self.overview_anim {
+            Some(anim) => anim.value(),
+            None if self.overview_open => 1.,
+            None => 0.,
+        }
+    }
+
+    /// Returns the current, fractional index of the workspace in view.
+    fn workspace_render_idx(&self) -> f64 {
+        self.workspace_switch
+            .as_ref()
+            .map(|s| s.current_idx())
+            .unwrap_or(self.active_workspace_idx as f64)
+    }
+
+    /// Computes the location and the zoom of a workspace in the overview.
+    ///
+    /// The geometry is interpolated between the regular stacking of the workspaces (closed
+    /// overview) and all workspaces zoomed out to fit the output (open overview).
+    fn overview_workspace_geometry(&self, idx: usize) -> (Point<f64, Logical>, f64) {
+        let size = output_size(&self.output).to_f64();
+        let progress = self.overview_progress();
+
+        let closed_loc = Point::from((0., (idx as f64 - self.workspace_render_idx()) * size.h));
+
+        let count = self.workspaces.len() as f64;
+        let zoom = (size.h - OVERVIEW_GAP * (count + 1.)) / (count * size.h);
+        let zoom = zoom.clamp(OVERVIEW_MIN_ZOOM, OVERVIEW_MAX_ZOOM);
+
+        let total_height = count * size.h * zoom + (count - 1.) * OVERVIEW_GAP;
+        let open_loc = Point::from((
+            (size.w - size.w * zoom) / 2.,
+            (size.h - total_height) / 2. + idx as f64 * (size.h * zoom + OVERVIEW_GAP),
+        ));
+
+        let loc = closed_loc + (open_loc - closed_loc).upscale(progress);
+        let zoom = 1. + (zoom - 1.) * progress;
+        (loc, zoom)
+    }
+
+    /// Returns the index of the workspace under the position in the overview, and the position
+    /// within that workspace.
+    pub fn overview_workspace_under(
+        &self,
+        pos_within_output: Point<f64, Logical>,
+    ) -> Option<(usize, Point<f64, Logical>)> {
+        let size = output_size(&self.output).to_f64();
+
+        (0..self.workspaces.len()).find_map(|idx| {
+            let (loc, zoom) = self.overview_workspace_geometry(idx);
+            let geo = Rectangle::from_loc_and_size(loc, size.upscale(zoom));
+            geo.contains(pos_within_output)
+                .then(|| (idx, (pos_within_output - loc).downscale(zoom)))
+        })
+    }
+
+    /// Activates the window or the workspace under the position in the overview, then closes the
+    /// overview.
+    pub fn pick_in_overview(&mut self, pos_within_output: Point<f64, Logical>) {
+        if let Some((idx, pos_within_ws)) = self.overview_workspace_under(pos_within_output) {
+            let ws = &mut self.workspaces[idx];
+            if let Some((win, _)) = ws.window_under(pos_within_ws) {
+                let id = win.id().clone();
+                ws.activate_window(&id);
+            }
+
+            self.activate_workspace(idx);
+        }
+
+        self.set_overview_open(false);
+    }
+
     /// Returns the geometry of the active tile relative to and clamped to the output.
     ///
     /// During animations, assumes the final view position.
@@ -647,6 +890,13 @@ impl<W: LayoutElement> Monitor<W> {
         &self,
         pos_within_output: Point<f64, Logical>,
     ) -> Option<(&W, Option<Point<i32, Logical>>)> {
+        if self.is_overview_visible() {
+            let (idx, pos_within_ws) = self.overview_workspace_under(pos_within_output)?;
+            let (win, _) = self.workspaces[idx].window_under(pos_within_ws)?;
+            // Zoomed out windows don't receive pointer input, they can only be picked.
+            return Some((win, None));
+        }
+
         match &self.workspace_switch {
             Some(switch) => {
                 let size = output_size(&self.output);
@@ -689,6 +939,11 @@ impl<W: LayoutElement> Monitor<W> {
     }
 
     pub fn render_above_top_layer(&self) -> bool {
+        // The overview covers the top layer.
+        if self.is_overview_visible() {
+            return true;
+        }
+
         // Render above the top layer only if the view is stationary.
         if self.workspace_switch.is_some() {
             return false;
@@ -710,6 +965,10 @@ impl<W: LayoutElement> Monitor<W> {
         let output_mode = self.output.current_mode().unwrap();
         let size = output_transform.transform_size(output_mode.size);
 
+        if self.is_overview_visible() {
+            return self.render_overview_elements(renderer, target);
+        }
+
         match &self.workspace_switch {
             Some(switch) => {
                 let render_idx = switch.current_idx();
@@ -745,7 +1004,7 @@ impl<W: LayoutElement> Monitor<W> {
                     });
 
                     if before_idx < 0. {
-                        return after.collect();
+                        return after.map(MonitorRenderElement::from).collect();
                     }
 
                     Some(after)
@@ -769,7 +1028,10 @@ impl<W: LayoutElement> Monitor<W> {
                         Relocate::Relative,
                     ))
                 });
-                before.chain(after.into_iter().flatten()).collect()
+                before
+                    .chain(after.into_iter().flatten())
+                    .map(MonitorRenderElement::from)
+                    .collect()
             }
             None => {
                 let elements =
@@ -794,11 +1056,56 @@ impl<W: LayoutElement> Monitor<W> {
                             Relocate::Relative,
                         ))
                     })
+                    .map(MonitorRenderElement::from)
                     .collect()
             }
         }
     }
 
+    fn render_overview_elements<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        target: RenderTarget,
+    ) -> Vec<MonitorRenderElement<R>> {
+        let output_scale = Scale::from(self.output.current_scale().fractional_scale());
+        let output_transform = self.output.current_transform();
+        let output_mode = self.output.current_mode().unwrap();
+        let size = output_transform.transform_size(output_mode.size);
+        let output_rect =
+            Rectangle::from_loc_and_size((0., 0.), output_size(&self.output).to_f64());
+
+        let mut rv = vec![];
+
+        for (idx, ws) in self.workspaces.iter().enumerate() {
+            let (loc, zoom) = self.overview_workspace_geometry(idx);
+
+            // Skip workspaces that are entirely outside the output.
+            let geo = Rectangle::from_loc_and_size(loc, output_rect.size.upscale(zoom));
+            if !geo.overlaps(output_rect) {
+                continue;
+            }
+
+            let elements = ws.render_elements(renderer, target);
+            rv.extend(elements.into_iter().filter_map(|elem| {
+                // Crop to the workspace so that the windows don't spill over to its neighbors.
+                let elem = CropRenderElement::from_element(
+                    elem,
+                    output_scale,
+                    Rectangle::from_loc_and_size((0, 0), size),
+                )?;
+                let elem = RescaleRenderElement::from_element(elem, (0, 0).into(), zoom);
+                let elem = RelocateRenderElement::from_element(
+                    elem,
+                    loc.to_physical_precise_round(output_scale),
+                    Relocate::Relative,
+                );
+                Some(MonitorRenderElement::from(elem))
+            }));
+        }
+
+        rv
+    }
+
     pub fn workspace_switch_gesture_begin(&mut self) {
         let center_idx = self.active_workspace_idx;
         let current_idx = self
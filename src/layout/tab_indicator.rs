@@ -0,0 +1,98 @@
+use std::cmp::max;
+
+use smithay::backend::renderer::element::solid::{SolidColorBuffer, SolidColorRenderElement};
+use smithay::backend::renderer::element::Kind;
+use smithay::utils::{Logical, Point, Scale};
+
+/// Width of the tab indicator.
+const WIDTH: i32 = 4;
+
+/// Gap between the tab segments.
+const GAP: i32 = 4;
+
+/// Opacity of the segments of the inactive tabs.
+const INACTIVE_TAB_ALPHA: f32 = 0.5;
+
+/// Indicator of the tabs of a column in the tabbed display mode.
+///
+/// It's drawn as a vertical strip in the gap to the left of the column, with one segment per tab.
+#[derive(Debug)]
+pub struct TabIndicator {
+    buffers: Vec<SolidColorBuffer>,
+    locations: Vec<Point<i32, Logical>>,
+    active_idx: usize,
+    config: niri_config::FocusRing,
+}
+
+impl TabIndicator {
+    pub fn new(config: niri_config::FocusRing) -> Self {
+        Self {
+            buffers: vec![],
+            locations: vec![],
+            active_idx: 0,
+            config,
+        }
+    }
+
+    pub fn update_config(&mut self, config: niri_config::FocusRing) {
+        self.config = config;
+    }
+
+    pub fn update(
+        &mut self,
+        tab_count: usize,
+        active_idx: usize,
+        height: i32,
+        gaps: i32,
+        is_active: bool,
+    ) {
+        self.buffers.resize_with(tab_count, Default::default);
+        self.locations.resize(tab_count, Point::default());
+        self.active_idx = active_idx;
+
+        let count = max(tab_count as i32, 1);
+        let segment_height = max((height - GAP * (count - 1)) / count, 1);
+
+        // Center the indicator in the gap.
+        let x = -(gaps + WIDTH) / 2;
+
+        let color = if is_active {
+            self.config.active_color.into()
+        } else {
+            self.config.inactive_color.into()
+        };
+
+        let mut y = 0;
+        for (buf, loc) in self.buffers.iter_mut().zip(&mut self.locations) {
+            buf.update((WIDTH, segment_height), color);
+            *loc = Point::from((x, y));
+            y += segment_height + GAP;
+        }
+    }
+
+    pub fn render(
+        &self,
+        location: Point<i32, Logical>,
+        scale: Scale<f64>,
+    ) -> impl Iterator<Item = SolidColorRenderElement> + '_ {
+        self.buffers
+            .iter()
+            .zip(&self.locations)
+            .enumerate()
+            .map(move |(idx, (buf, loc))| {
+                let alpha = if idx == self.active_idx {
+                    1.
+                } else {
+                    INACTIVE_TAB_ALPHA
+                };
+
+                SolidColorRenderElement::from_buffer(
+                    buf,
+                    (location + *loc).to_physical_precise_round(scale),
+                    scale,
+                    alpha,
+                    Kind::Unspecified,
+                )
+            })
+    }
+}
@@ -45,10 +45,19 @@ pub struct Tile<W: LayoutElement> {
     /// The animation upon opening a window.
     open_animation: Option<Animation>,
 
+    /// The animation of the tile moving to a new position.
+    move_animation: Option<MoveAnimation>,
+
     /// Configurable properties of the layout.
     options: Rc<Options>,
 }
 
+#[derive(Debug)]
+struct MoveAnimation {
+    anim: Animation,
+    from: Point<i32, Logical>,
+}
+
 niri_render_elements! {
     TileRenderElement<R> => {
         LayoutElement = LayoutElementRenderElement<R>,
@@ -68,6 +77,7 @@ impl<W: LayoutElement> Tile<W> {
             fullscreen_backdrop: SolidColorBuffer::new((0, 0), [0., 0., 0., 1.]),
             fullscreen_size: Default::default(),
             open_animation: None,
+            move_animation: None,
             options,
         }
     }
@@ -113,10 +123,49 @@ impl<W: LayoutElement> Tile<W> {
             }
             None => (),
         }
+
+        if let Some(move_) = &mut self.move_animation {
+            move_.anim.set_current_time(current_time);
+            if move_.anim.is_done() {
+                self.move_animation = None;
+            }
+        }
     }
 
     pub fn are_animations_ongoing(&self) -> bool {
-        self.open_animation.is_some()
+        self.open_animation.is_some() || self.move_animation.is_some()
+    }
+
+    /// Starts an animation of the tile moving from `from` to its current position.
+    ///
+    /// `from` is relative to the new tile position.
+    pub fn animate_move_from(&mut self, from: Point<i32, Logical>) {
+        // Continue from wherever an ongoing move animation currently is.
+        let from = from + self.render_offset();
+
+        self.move_animation = Some(MoveAnimation {
+            anim: Animation::new(
+                1.,
+                0.,
+                0.,
+                self.options.animations.window_movement,
+                niri_config::Animation::default_window_movement(),
+            ),
+            from,
+        });
+    }
+
+    /// Returns the offset of the tile from its real position for rendering.
+    pub fn render_offset(&self) -> Point<i32, Logical> {
+        let Some(move_) = &self.move_animation else {
+            return Point::from((0, 0));
+        };
+
+        let v = move_.anim.value();
+        Point::from((
+            (f64::from(move_.from.x) * v).round() as i32,
+            (f64::from(move_.from.y) * v).round() as i32,
+        ))
     }
 
     pub fn start_open_animation(&mut self) {
@@ -380,6 +429,8 @@ impl<W: LayoutElement> Tile<W> {
         focus_ring: bool,
         target: RenderTarget,
     ) -> impl Iterator<Item = TileRenderElement<R>> {
+        let location = location + self.render_offset();
+
         if let Some(anim) = &self.open_animation {
             let renderer = renderer.as_gles_renderer();
             let elements =
@@ -1,10 +1,12 @@
 use std::cmp::{max, min};
 use std::iter::{self, zip};
+use std::mem;
 use std::rc::Rc;
 use std::time::Duration;
 
-use niri_config::{CenterFocusedColumn, PresetWidth, Struts};
+use niri_config::{CenterFocusedColumn, LayoutOverride, PresetSize, Struts};
 use niri_ipc::SizeChange;
+use smithay::backend::renderer::element::solid::SolidColorRenderElement;
 use smithay::desktop::{layer_map_for_output, Window};
 use smithay::output::Output;
 use smithay::reexports::wayland_protocols::xdg::shell::server::xdg_toplevel;
@@ -12,6 +14,7 @@ use smithay::reexports::wayland_server::protocol::wl_surface::WlSurface;
 use smithay::utils::{Logical, Point, Rectangle, Scale, Size};
 use smithay::wayland::compositor::send_surface_state;
 
+use super::tab_indicator::TabIndicator;
 use super::tile::{Tile, TileRenderElement};
 use super::{LayoutElement, Options};
 use crate::animation::Animation;
@@ -20,11 +23,20 @@ use crate::render_helpers::renderer::NiriRenderer;
 use crate::render_helpers::RenderTarget;
 use crate::swipe_tracker::SwipeTracker;
 use crate::utils::id::IdCounter;
-use crate::utils::output_size;
+use crate::utils::{output_size, ResizeEdge};
 
 /// Amount of touchpad movement to scroll the view for the width of one working area.
 const VIEW_GESTURE_WORKING_AREA_MOVEMENT: f64 = 1200.;
 
+/// Amount to move a floating window by with the directional move actions.
+const FLOATING_MOVE_AMOUNT: i32 = 50;
+
+/// Part of a floating window that is kept inside the working area when the view shrinks.
+const FLOATING_MIN_VISIBLE: i32 = 50;
+
+/// Thickness of the hint showing where an interactively moved window will be inserted.
+const INSERT_HINT_THICKNESS: i32 = 6;
+
 #[derive(Debug)]
 pub struct Workspace<W: LayoutElement> {
     /// The original output of this workspace.
@@ -33,6 +45,11 @@ pub struct Workspace<W: LayoutElement> {
     /// disconnection, it may remain pointing to the disconnected output.
     pub original_output: OutputId,
 
+    /// Name of this workspace, if it was declared in the config.
+    ///
+    /// Named workspaces are never cleaned up, even when they have no windows.
+    pub name: Option<String>,
+
     /// Current output of this workspace.
     output: Option<Output>,
 
@@ -54,6 +71,16 @@ pub struct Workspace<W: LayoutElement> {
     /// Index of the currently active column, if any.
     pub active_column_idx: usize,
 
+    /// Floating windows on this workspace, rendered above the columns.
+    ///
+    /// Ordered from bottom to top. The topmost floating window is the active one.
+    floating: Vec<FloatingTile<W>>,
+
+    /// Whether the floating windows, rather than the columns, have focus.
+    ///
+    /// See [`Workspace::is_floating_focused()`] for the effective value.
+    floating_is_active: bool,
+
     /// Offset of the view computed from the active column.
     ///
     /// Any gaps, including left padding from work area left exclusive zone, is handled
@@ -76,13 +103,61 @@ pub struct Workspace<W: LayoutElement> {
     /// The value is the view offset that the previous column had before, to restore it.
     activate_prev_column_on_removal: Option<i32>,
 
-    /// Configurable properties of the layout.
+    /// Configurable properties of the layout, with the overrides of this workspace applied.
     pub options: Rc<Options>,
 
+    /// Configurable properties of the layout as received from the monitor.
+    pub base_options: Rc<Options>,
+
+    /// Layout overrides of this named workspace from the config.
+    layout_override: LayoutOverride,
+
+    /// Layout overrides set at runtime, applied on top of the ones from the config.
+    runtime_layout_override: LayoutOverride,
+
     /// Unique ID of this workspace.
     id: WorkspaceId,
 }
 
+/// Tile in the floating layout of a workspace.
+#[derive(Debug)]
+struct FloatingTile<W: LayoutElement> {
+    tile: Tile<W>,
+
+    /// Position of the tile relative to the view.
+    pos: Point<i32, Logical>,
+
+    /// Whether the window was summoned from the scratchpad.
+    ///
+    /// Such windows go back into the scratchpad when it's toggled again.
+    is_scratchpad: bool,
+}
+
+/// Where a window was on its workspace.
+///
+/// Used to put a window in the same kind of place when moving it to another workspace.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WindowPlacement {
+    /// In a column with the given properties.
+    Column {
+        width: ColumnWidth,
+        is_full_width: bool,
+    },
+    /// In the floating layout.
+    Floating,
+}
+
+/// Where to insert an interactively moved window into a workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertPosition {
+    /// As a new column at this index.
+    NewColumn(usize),
+    /// Into the column at this index, as the tile at this index.
+    InColumn(usize, usize),
+    /// Into the floating layout, with the tile at this position relative to the view.
+    Floating(Point<i32, Logical>),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OutputId(String);
 
@@ -95,11 +170,16 @@ impl WorkspaceId {
     fn next() -> WorkspaceId {
         WorkspaceId(WORKSPACE_ID_COUNTER.next())
     }
+
+    pub fn get(self) -> u32 {
+        self.0
+    }
 }
 
 niri_render_elements! {
     WorkspaceRenderElement<R> => {
         Tile = TileRenderElement<R>,
+        TabIndicator = SolidColorRenderElement,
     }
 }
 
@@ -144,12 +224,27 @@ pub enum ColumnWidth {
 /// This does not preclude the usual set of binds to set or resize a window proportionally. Just,
 /// they are converted to, and stored as fixed height right away, so that once you resize a window
 /// to fit the desired content, it can never become smaller than that when moving between monitors.
+///
+/// The one exception are the height presets, which are stored as is in order to be able to
+/// reliably cycle between them.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WindowHeight {
     /// Automatically computed height, evenly distributed across the column.
     Auto,
     /// Fixed height in logical pixels.
     Fixed(i32),
+    /// One of the height presets.
+    Preset(usize),
+}
+
+/// How a column displays its tiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnDisplay {
+    /// Tiles are stacked vertically, sharing the column height.
+    #[default]
+    Normal,
+    /// Only the active tile is visible, at the full column height.
+    Tabbed,
 }
 
 #[derive(Debug)]
@@ -181,6 +276,12 @@ pub struct Column<W: LayoutElement> {
     /// Whether this column contains a single full-screened window.
     pub is_fullscreen: bool,
 
+    /// How this column displays its tiles.
+    pub display: ColumnDisplay,
+
+    /// Indicator of the tabs in the tabbed display.
+    tab_indicator: TabIndicator,
+
     /// Latest known view size for this column's workspace.
     view_size: Size<i32, Logical>,
 
@@ -195,6 +296,10 @@ impl OutputId {
     pub fn new(output: &Output) -> Self {
         Self(output.name())
     }
+
+    pub fn from_name(name: &str) -> Self {
+        Self(name.to_owned())
+    }
 }
 
 impl ViewOffsetAdjustment {
@@ -218,11 +323,11 @@ impl ColumnWidth {
     }
 }
 
-impl From<PresetWidth> for ColumnWidth {
-    fn from(value: PresetWidth) -> Self {
+impl From<PresetSize> for ColumnWidth {
+    fn from(value: PresetSize) -> Self {
         match value {
-            PresetWidth::Proportion(p) => Self::Proportion(p.clamp(0., 10000.)),
-            PresetWidth::Fixed(f) => Self::Fixed(f.clamp(1, 100000)),
+            PresetSize::Proportion(p) => Self::Proportion(p.clamp(0., 10000.)),
+            PresetSize::Fixed(f) => Self::Fixed(f.clamp(1, 100000)),
         }
     }
 }
@@ -232,15 +337,21 @@ impl<W: LayoutElement> Workspace<W> {
         let working_area = compute_working_area(&output, options.struts);
         Self {
             original_output: OutputId::new(&output),
+            name: None,
             view_size: output_size(&output),
             working_area,
             output: Some(output),
             columns: vec![],
             active_column_idx: 0,
+            floating: vec![],
+            floating_is_active: false,
             view_offset: 0,
             view_offset_adj: None,
             activate_prev_column_on_removal: None,
+            base_options: options.clone(),
             options,
+            layout_override: LayoutOverride::default(),
+            runtime_layout_override: LayoutOverride::default(),
             id: WorkspaceId::next(),
         }
     }
@@ -249,14 +360,20 @@ impl<W: LayoutElement> Workspace<W> {
         Self {
             output: None,
             original_output: OutputId(String::new()),
+            name: None,
             view_size: Size::from((1280, 720)),
             working_area: Rectangle::from_loc_and_size((0, 0), (1280, 720)),
             columns: vec![],
             active_column_idx: 0,
+            floating: vec![],
+            floating_is_active: false,
             view_offset: 0,
             view_offset_adj: None,
             activate_prev_column_on_removal: None,
+            base_options: options.clone(),
             options,
+            layout_override: LayoutOverride::default(),
+            runtime_layout_override: LayoutOverride::default(),
             id: WorkspaceId::next(),
         }
     }
@@ -276,28 +393,92 @@ impl<W: LayoutElement> Workspace<W> {
             self.view_offset = gesture.current_view_offset.round() as i32;
         }
 
+        let is_floating_focused = self.is_floating_focused();
+
         for (col_idx, col) in self.columns.iter_mut().enumerate() {
-            let is_active = is_active && col_idx == self.active_column_idx;
+            let is_active = is_active && !is_floating_focused && col_idx == self.active_column_idx;
             col.advance_animations(current_time, is_active);
         }
+
+        let top_idx = self.floating.len().saturating_sub(1);
+        for (idx, floating) in self.floating.iter_mut().enumerate() {
+            let is_active = is_active && is_floating_focused && idx == top_idx;
+            floating.tile.advance_animations(current_time, is_active);
+        }
     }
 
     pub fn are_animations_ongoing(&self) -> bool {
-        self.view_offset_adj.is_some() || self.columns.iter().any(Column::are_animations_ongoing)
+        self.view_offset_adj.is_some()
+            || self.columns.iter().any(Column::are_animations_ongoing)
+            || self
+                .floating
+                .iter()
+                .any(|floating| floating.tile.are_animations_ongoing())
     }
 
-    pub fn update_config(&mut self, options: Rc<Options>) {
+    pub fn update_config(&mut self, base_options: Rc<Options>) {
+        self.base_options = base_options;
+        let options = self.options_with_overrides();
+
         for column in &mut self.columns {
             column.update_config(options.clone());
         }
 
+        for floating in &mut self.floating {
+            floating.tile.update_config(options.clone());
+        }
+
+        let struts_changed = self.options.struts != options.struts;
         self.options = options;
+
+        if struts_changed {
+            if let Some(output) = &self.output {
+                let working_area = compute_working_area(output, self.options.struts);
+                self.set_view_size(self.view_size, working_area);
+            }
+        }
+    }
+
+    /// Sets the layout overrides of this workspace coming from the config.
+    pub fn set_layout_override(&mut self, layout: LayoutOverride) {
+        if self.layout_override == layout {
+            return;
+        }
+
+        self.layout_override = layout;
+        self.update_config(self.base_options.clone());
+    }
+
+    /// Sets the layout overrides of this workspace coming from IPC.
+    ///
+    /// These are kept across config reloads.
+    pub fn set_runtime_layout_override(&mut self, layout: LayoutOverride) {
+        if self.runtime_layout_override == layout {
+            return;
+        }
+
+        self.runtime_layout_override = layout;
+        self.update_config(self.base_options.clone());
+    }
+
+    fn options_with_overrides(&self) -> Rc<Options> {
+        let none = LayoutOverride::default();
+        if self.layout_override == none && self.runtime_layout_override == none {
+            return self.base_options.clone();
+        }
+
+        let options = self
+            .base_options
+            .with_override(&self.layout_override)
+            .with_override(&self.runtime_layout_override);
+        Rc::new(options)
     }
 
     pub fn windows(&self) -> impl Iterator<Item = &W> + '_ {
         self.columns
             .iter()
             .flat_map(|col| col.tiles.iter())
+            .chain(self.floating.iter().map(|floating| &floating.tile))
             .map(Tile::window)
     }
 
@@ -305,9 +486,51 @@ impl<W: LayoutElement> Workspace<W> {
         self.columns
             .iter_mut()
             .flat_map(|col| col.tiles.iter_mut())
+            .chain(self.floating.iter_mut().map(|floating| &mut floating.tile))
             .map(Tile::window_mut)
     }
 
+    /// Returns the floating windows, from bottom to top.
+    pub fn floating_windows(&self) -> impl Iterator<Item = &W> + '_ {
+        self.floating.iter().map(|floating| floating.tile.window())
+    }
+
+    pub fn is_floating(&self, window: &W::Id) -> bool {
+        self.floating_idx(window).is_some()
+    }
+
+    fn floating_idx(&self, window: &W::Id) -> Option<usize> {
+        self.floating
+            .iter()
+            .position(|floating| floating.tile.window().id() == window)
+    }
+
+    /// Returns the floating index of the window, or of the active window if `None`, if it is
+    /// floating.
+    fn floating_target_idx(&self, window: Option<&W::Id>) -> Option<usize> {
+        match window {
+            Some(window) => self.floating_idx(window),
+            None => self.is_floating_focused().then(|| self.floating.len() - 1),
+        }
+    }
+
+    /// Whether the floating windows have focus.
+    ///
+    /// The floating windows also get focus when there are no columns.
+    pub fn is_floating_focused(&self) -> bool {
+        !self.floating.is_empty() && (self.floating_is_active || self.columns.is_empty())
+    }
+
+    /// Returns the focused window on this workspace, if any.
+    pub fn active_window(&self) -> Option<&W> {
+        if self.is_floating_focused() {
+            return self.floating.last().map(|floating| floating.tile.window());
+        }
+
+        let col = self.active_column_ref()?;
+        Some(col.active_tile_ref().window())
+    }
+
     pub fn set_output(&mut self, output: Option<Output>) {
         if self.output == output {
             return;
@@ -353,6 +576,12 @@ impl<W: LayoutElement> Workspace<W> {
         for col in &mut self.columns {
             col.set_view_size(self.view_size, self.working_area);
         }
+
+        // Don't leave floating windows out of reach when the working area shrinks or moves.
+        for floating in &mut self.floating {
+            let size = floating.tile.tile_size();
+            floating.pos = clamp_floating_pos(floating.pos, size, self.working_area);
+        }
     }
 
     pub fn view_size(&self) -> Size<i32, Logical> {
@@ -414,7 +643,15 @@ impl<W: LayoutElement> Workspace<W> {
         Size::from((width, max(height, 1)))
     }
 
-    pub fn configure_new_window(&self, window: &Window, width: Option<ColumnWidth>) {
+    /// Sets the initial configure state for a new window.
+    ///
+    /// Floating windows pick their own size.
+    pub fn configure_new_window(
+        &self,
+        window: &Window,
+        width: Option<ColumnWidth>,
+        is_floating: bool,
+    ) {
         if let Some(output) = self.output.as_ref() {
             let scale = output.current_scale().integer_scale();
             let transform = output.current_transform();
@@ -429,6 +666,8 @@ impl<W: LayoutElement> Workspace<W> {
             .with_pending_state(|state| {
                 if state.states.contains(xdg_toplevel::State::Fullscreen) {
                     state.size = Some(self.view_size);
+                } else if is_floating {
+                    state.size = None;
                 } else {
                     state.size = Some(self.new_window_size(width));
                 }
@@ -589,6 +828,18 @@ impl<W: LayoutElement> Workspace<W> {
         self.windows().next().is_some()
     }
 
+    /// Returns whether this workspace must be kept around: it has windows or a name.
+    pub fn has_windows_or_name(&self) -> bool {
+        self.name.is_some() || self.has_windows()
+    }
+
+    /// Returns whether this workspace has the given name, compared case-insensitively.
+    pub fn has_name(&self, name: &str) -> bool {
+        self.name
+            .as_ref()
+            .is_some_and(|ws_name| ws_name.eq_ignore_ascii_case(name))
+    }
+
     pub fn has_window(&self, window: &W::Id) -> bool {
         self.windows().any(|win| win.id() == window)
     }
@@ -669,6 +920,7 @@ impl<W: LayoutElement> Workspace<W> {
 
             self.activate_column(idx);
             self.activate_prev_column_on_removal = prev_offset;
+            self.floating_is_active = false;
         }
     }
 
@@ -721,6 +973,8 @@ impl<W: LayoutElement> Workspace<W> {
             self.active_column_idx + 1
         };
 
+        // The column could be coming from a workspace with different options.
+        column.update_config(self.options.clone());
         column.set_view_size(self.view_size, self.working_area);
         let width = column.width();
         self.columns.insert(idx, column);
@@ -744,7 +998,74 @@ impl<W: LayoutElement> Workspace<W> {
 
             self.activate_column(idx);
             self.activate_prev_column_on_removal = prev_offset;
+            self.floating_is_active = false;
+        }
+    }
+
+    /// Adds a window to the floating layout, centered in the working area.
+    ///
+    /// When not activating, the window is put right below the active floating window.
+    pub fn add_floating_window(&mut self, window: W, activate: bool) {
+        self.enter_output_for_window(&window);
+
+        let tile = Tile::new(window, self.options.clone());
+        let size = tile.tile_size();
+        let area = self.working_area;
+        let pos = Point::from((
+            area.loc.x + (area.size.w - size.w) / 2,
+            area.loc.y + (area.size.h - size.h) / 2,
+        ));
+        let floating = FloatingTile {
+            tile,
+            pos,
+            is_scratchpad: false,
+        };
+
+        if activate || !self.is_floating_focused() {
+            self.floating.push(floating);
+        } else {
+            self.floating.insert(self.floating.len() - 1, floating);
+        }
+
+        if activate {
+            self.floating_is_active = true;
+        }
+    }
+
+    /// Adds a window summoned from the scratchpad to the floating layout and activates it.
+    pub fn add_scratchpad_window(&mut self, window: W) {
+        self.add_floating_window(window, true);
+        self.floating.last_mut().unwrap().is_scratchpad = true;
+    }
+
+    /// Removes the windows summoned from the scratchpad, from bottom to top.
+    pub fn take_scratchpad_windows(&mut self) -> Vec<W> {
+        let mut windows = vec![];
+
+        let mut idx = 0;
+        while idx < self.floating.len() {
+            if self.floating[idx].is_scratchpad {
+                windows.push(self.remove_floating_window_by_idx(idx));
+            } else {
+                idx += 1;
+            }
+        }
+
+        windows
+    }
+
+    fn remove_floating_window_by_idx(&mut self, idx: usize) -> W {
+        let window = self.floating.remove(idx).tile.into_window();
+
+        if let Some(output) = &self.output {
+            window.output_leave(output);
         }
+
+        if self.floating.is_empty() {
+            self.floating_is_active = false;
+        }
+
+        window
     }
 
     pub fn remove_window_by_idx(&mut self, column_idx: usize, window_idx: usize) -> W {
@@ -797,6 +1118,10 @@ impl<W: LayoutElement> Workspace<W> {
             return window;
         }
 
+        // Keep the same tile active if a tile above it was removed.
+        if window_idx < column.active_tile_idx {
+            column.active_tile_idx -= 1;
+        }
         column.active_tile_idx = min(column.active_tile_idx, column.tiles.len() - 1);
         column.update_tile_sizes();
 
@@ -852,6 +1177,10 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn remove_window(&mut self, window: &W::Id) -> W {
+        if let Some(idx) = self.floating_idx(window) {
+            return self.remove_floating_window_by_idx(idx);
+        }
+
         let column_idx = self
             .columns
             .iter()
@@ -864,6 +1193,11 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn update_window(&mut self, window: &W::Id) {
+        if let Some(idx) = self.floating_idx(window) {
+            self.floating[idx].tile.update_window();
+            return;
+        }
+
         let (idx, column) = self
             .columns
             .iter_mut()
@@ -886,6 +1220,14 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn activate_window(&mut self, window: &W::Id) {
+        if let Some(idx) = self.floating_idx(window) {
+            // Raise the window to the top.
+            let floating = self.floating.remove(idx);
+            self.floating.push(floating);
+            self.floating_is_active = true;
+            return;
+        }
+
         let column_idx = self
             .columns
             .iter()
@@ -895,6 +1237,298 @@ impl<W: LayoutElement> Workspace<W> {
 
         column.activate_window(window);
         self.activate_column(column_idx);
+        self.floating_is_active = false;
+    }
+
+    /// Returns the placement of the window, if it is on this workspace.
+    pub fn window_placement(&self, window: &W::Id) -> Option<WindowPlacement> {
+        if self.is_floating(window) {
+            return Some(WindowPlacement::Floating);
+        }
+
+        let column = self.columns.iter().find(|col| col.contains(window))?;
+        Some(WindowPlacement::Column {
+            width: column.placement_width(),
+            is_full_width: column.is_full_width,
+        })
+    }
+
+    /// Removes the window, or the active window if `None`, returning it along with its placement.
+    ///
+    /// Returns `None` if there are no windows.
+    pub fn take_window(&mut self, window: Option<&W::Id>) -> Option<(W, WindowPlacement)> {
+        let floating_idx = self.floating_target_idx(window);
+        if let Some(idx) = floating_idx {
+            let window = self.remove_floating_window_by_idx(idx);
+            return Some((window, WindowPlacement::Floating));
+        }
+
+        if self.columns.is_empty() {
+            return None;
+        }
+
+        let (col_idx, tile_idx) = self.window_position(window);
+        let column = &self.columns[col_idx];
+        let placement = WindowPlacement::Column {
+            width: column.placement_width(),
+            is_full_width: column.is_full_width,
+        };
+        let window = self.remove_window_by_idx(col_idx, tile_idx);
+        Some((window, placement))
+    }
+
+    /// Moves the window, or the active window if `None`, between the floating and the tiling
+    /// layout.
+    pub fn toggle_window_floating(&mut self, window: Option<&W::Id>) {
+        let floating_idx = self.floating_target_idx(window);
+
+        if let Some(idx) = floating_idx {
+            let was_focused = self.is_floating_focused() && idx == self.floating.len() - 1;
+
+            let tile_width = self.floating[idx].tile.tile_size().w;
+            let width = self
+                .options
+                .default_width
+                .unwrap_or(ColumnWidth::Fixed(tile_width));
+
+            let window = self.remove_floating_window_by_idx(idx);
+            self.add_window(window, was_focused, width, false);
+            return;
+        }
+
+        if self.columns.is_empty() {
+            return;
+        }
+
+        let (col_idx, tile_idx) = self.window_position(window);
+        if self.columns[col_idx].is_fullscreen {
+            return;
+        }
+
+        let was_focused =
+            !self.is_floating_focused() && self.is_window_position_focused(col_idx, tile_idx);
+
+        let window = self.remove_window_by_idx(col_idx, tile_idx);
+
+        // Keep the size that the window had in the column.
+        window.request_size(window.size());
+
+        self.add_floating_window(window, was_focused);
+    }
+
+    /// Moves the focus between the floating and the tiling layout.
+    pub fn switch_focus_floating_tiling(&mut self) {
+        if self.is_floating_focused() {
+            // Keep the focus on the floating windows if there's nothing tiled.
+            if !self.columns.is_empty() {
+                self.floating_is_active = false;
+            }
+        } else if !self.floating.is_empty() {
+            self.floating_is_active = true;
+        }
+    }
+
+    /// Focuses the nearest floating window in the given direction from the active one.
+    fn focus_floating_towards(&mut self, dir: Point<i32, Logical>) {
+        let Some(active) = self.floating.last() else {
+            return;
+        };
+
+        let center = |floating: &FloatingTile<W>| {
+            let size = floating.tile.tile_size();
+            floating.pos + Point::from((size.w / 2, size.h / 2))
+        };
+
+        let from = center(active);
+        let target = self.floating[..self.floating.len() - 1]
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, floating)| {
+                let delta = center(floating) - from;
+                let along = delta.x * dir.x + delta.y * dir.y;
+                (along > 0).then(|| (idx, delta.x.abs() + delta.y.abs()))
+            })
+            .min_by_key(|(_, distance)| *distance);
+
+        if let Some((idx, _)) = target {
+            let floating = self.floating.remove(idx);
+            self.floating.push(floating);
+        }
+    }
+
+    fn move_floating_by(&mut self, delta: Point<i32, Logical>) {
+        if let Some(floating) = self.floating.last_mut() {
+            floating.pos += delta;
+        }
+    }
+
+    /// Returns the Y position of the window relative to the top of its column or, for floating
+    /// windows, to the view.
+    pub fn window_y(&self, window: &W::Id) -> Option<i32> {
+        if let Some(idx) = self.floating_idx(window) {
+            let floating = &self.floating[idx];
+            return Some(floating.pos.y + floating.tile.window_loc().y);
+        }
+
+        self.columns.iter().find_map(|col| {
+            col.tiles
+                .iter()
+                .position(|tile| tile.window().id() == window)
+                .map(|idx| col.window_y(idx))
+        })
+    }
+
+    /// Starts the open animation for the window, returning `false` if it wasn't found.
+    pub fn start_open_animation(&mut self, window: &W::Id) -> bool {
+        self.columns
+            .iter_mut()
+            .flat_map(|col| col.tiles.iter_mut())
+            .chain(self.floating.iter_mut().map(|floating| &mut floating.tile))
+            .find(|tile| tile.window().id() == window)
+            .map(|tile| tile.start_open_animation())
+            .is_some()
+    }
+
+    /// Returns the visual rectangle of the window's tile relative to the view.
+    ///
+    /// Returns `None` if the window is not on this workspace or is not visible, like an inactive
+    /// tab.
+    pub fn tile_rect(&self, window: &W::Id) -> Option<Rectangle<i32, Logical>> {
+        self.tiles_in_render_order()
+            .find(|(tile, _)| tile.window().id() == window)
+            .map(|(tile, pos)| Rectangle::from_loc_and_size(pos, tile.tile_size()))
+    }
+
+    /// Computes where to insert a tiled window dropped at the given position relative to the
+    /// view.
+    ///
+    /// Dropping near the top or the bottom edge of a tile inserts the window into that tile's
+    /// column. Dropping elsewhere inserts a new column on the nearest side.
+    pub fn insert_position(&self, pos: Point<f64, Logical>) -> InsertPosition {
+        if self.columns.is_empty() {
+            return InsertPosition::NewColumn(0);
+        }
+
+        let view_pos = self.visual_column_x(self.active_column_idx) + self.view_offset;
+
+        let mut x = -view_pos;
+        for (col_idx, col) in self.columns.iter().enumerate() {
+            if pos.x < f64::from(x) {
+                return InsertPosition::NewColumn(col_idx);
+            }
+
+            let width = col.visual_width();
+            if pos.x < f64::from(x + width) {
+                if let Some(tile_idx) = col.insert_tile_idx(pos.y) {
+                    return InsertPosition::InColumn(col_idx, tile_idx);
+                }
+
+                return if pos.x < f64::from(x) + f64::from(width) / 2. {
+                    InsertPosition::NewColumn(col_idx)
+                } else {
+                    InsertPosition::NewColumn(col_idx + 1)
+                };
+            }
+
+            x += width + self.options.gaps;
+        }
+
+        InsertPosition::NewColumn(self.columns.len())
+    }
+
+    /// Returns the area relative to the view where to show the hint for the insert position.
+    pub fn insert_hint_area(&self, position: InsertPosition) -> Option<Rectangle<i32, Logical>> {
+        let gaps = self.options.gaps;
+        let area = self.working_area;
+        let view_pos = self.visual_column_x(self.active_column_idx) + self.view_offset;
+
+        match position {
+            InsertPosition::NewColumn(col_idx) => {
+                let x = if self.columns.is_empty() {
+                    area.loc.x
+                } else {
+                    self.visual_column_x(col_idx) - view_pos - gaps
+                };
+                let x = x + (gaps - INSERT_HINT_THICKNESS) / 2;
+                Some(Rectangle::from_loc_and_size(
+                    (x, area.loc.y + gaps),
+                    (INSERT_HINT_THICKNESS, max(area.size.h - gaps * 2, 1)),
+                ))
+            }
+            InsertPosition::InColumn(col_idx, tile_idx) => {
+                let col = self.columns.get(col_idx)?;
+                let x = self.visual_column_x(col_idx) - view_pos;
+
+                let y = if col.display == ColumnDisplay::Tabbed {
+                    let tile_y = col.tile_y(col.active_tile_idx);
+                    if tile_idx <= col.active_tile_idx {
+                        tile_y - gaps
+                    } else {
+                        tile_y + col.active_tile_ref().tile_size().h
+                    }
+                } else if tile_idx < col.tiles.len() {
+                    col.tile_y(tile_idx) - gaps
+                } else {
+                    let last_idx = col.tiles.len() - 1;
+                    col.tile_y(last_idx) + col.tiles[last_idx].tile_size().h
+                };
+                let y = y + (gaps - INSERT_HINT_THICKNESS) / 2;
+
+                Some(Rectangle::from_loc_and_size(
+                    (x, y),
+                    (col.visual_width(), INSERT_HINT_THICKNESS),
+                ))
+            }
+            InsertPosition::Floating(_) => None,
+        }
+    }
+
+    /// Inserts the window at the given position and activates it.
+    ///
+    /// The placement provides the column properties when inserting into the tiling layout.
+    pub fn insert_window(
+        &mut self,
+        position: InsertPosition,
+        window: W,
+        placement: WindowPlacement,
+    ) {
+        let (width, is_full_width) = match placement {
+            WindowPlacement::Column {
+                width,
+                is_full_width,
+            } => (width, is_full_width),
+            WindowPlacement::Floating => {
+                let width = ColumnWidth::Fixed(window.size().w);
+                (self.options.default_width.unwrap_or(width), false)
+            }
+        };
+
+        match position {
+            InsertPosition::NewColumn(_) if self.columns.is_empty() => {
+                self.add_window(window, true, width, is_full_width);
+            }
+            InsertPosition::NewColumn(col_idx) => {
+                let col_idx = min(col_idx, self.columns.len());
+                self.insert_window_column(col_idx, window, width, is_full_width);
+                self.activate_column(col_idx);
+                self.floating_is_active = false;
+            }
+            InsertPosition::InColumn(col_idx, tile_idx) => {
+                self.enter_output_for_window(&window);
+
+                let col = &mut self.columns[col_idx];
+                let tile_idx = min(tile_idx, col.tiles.len());
+                col.insert_window(tile_idx, window);
+                col.active_tile_idx = tile_idx;
+
+                self.activate_column(col_idx);
+                self.floating_is_active = false;
+            }
+            InsertPosition::Floating(pos) => {
+                self.add_floating_window(window, true);
+                self.floating.last_mut().unwrap().pos = pos;
+            }
+        }
     }
 
     #[cfg(test)]
@@ -902,20 +1536,45 @@ impl<W: LayoutElement> Workspace<W> {
         assert!(self.view_size.w > 0);
         assert!(self.view_size.h > 0);
 
+        assert_eq!(
+            self.options,
+            self.options_with_overrides(),
+            "workspace options must match the base options with overrides"
+        );
+
         if !self.columns.is_empty() {
             assert!(self.active_column_idx < self.columns.len());
 
             for column in &self.columns {
+                assert_eq!(
+                    column.options, self.options,
+                    "column options must be synchronized with workspace"
+                );
+
                 column.verify_invariants();
             }
         }
+
+        if self.floating_is_active {
+            assert!(!self.floating.is_empty());
+        }
     }
 
     pub fn focus_left(&mut self) {
+        if self.is_floating_focused() {
+            self.focus_floating_towards(Point::from((-1, 0)));
+            return;
+        }
+
         self.activate_column(self.active_column_idx.saturating_sub(1));
     }
 
     pub fn focus_right(&mut self) {
+        if self.is_floating_focused() {
+            self.focus_floating_towards(Point::from((1, 0)));
+            return;
+        }
+
         if self.columns.is_empty() {
             return;
         }
@@ -924,18 +1583,37 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn focus_column_first(&mut self) {
+        if self.is_floating_focused() {
+            return;
+        }
+
         self.activate_column(0);
     }
 
     pub fn focus_column_last(&mut self) {
-        if self.columns.is_empty() {
+        if self.columns.is_empty() || self.is_floating_focused() {
             return;
         }
 
         self.activate_column(self.columns.len() - 1);
     }
 
+    /// Focuses the column at the given 1-based index, or the last column if there are fewer.
+    pub fn focus_column(&mut self, index: usize) {
+        if self.columns.is_empty() || self.is_floating_focused() {
+            return;
+        }
+
+        let idx = min(index.saturating_sub(1), self.columns.len() - 1);
+        self.activate_column(idx);
+    }
+
     pub fn focus_down(&mut self) {
+        if self.is_floating_focused() {
+            self.focus_floating_towards(Point::from((0, 1)));
+            return;
+        }
+
         if self.columns.is_empty() {
             return;
         }
@@ -944,6 +1622,11 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn focus_up(&mut self) {
+        if self.is_floating_focused() {
+            self.focus_floating_towards(Point::from((0, -1)));
+            return;
+        }
+
         if self.columns.is_empty() {
             return;
         }
@@ -969,11 +1652,21 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn move_left(&mut self) {
+        if self.is_floating_focused() {
+            self.move_floating_by(Point::from((-FLOATING_MOVE_AMOUNT, 0)));
+            return;
+        }
+
         let new_idx = self.active_column_idx.saturating_sub(1);
         self.move_column_to(new_idx);
     }
 
     pub fn move_right(&mut self) {
+        if self.is_floating_focused() {
+            self.move_floating_by(Point::from((FLOATING_MOVE_AMOUNT, 0)));
+            return;
+        }
+
         if self.columns.is_empty() {
             return;
         }
@@ -983,19 +1676,38 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn move_column_to_first(&mut self) {
+        if self.is_floating_focused() {
+            return;
+        }
+
         self.move_column_to(0);
     }
 
-    pub fn move_column_to_last(&mut self) {
-        if self.columns.is_empty() {
+    pub fn move_column_to_last(&mut self) {
+        if self.columns.is_empty() || self.is_floating_focused() {
+            return;
+        }
+
+        let new_idx = self.columns.len() - 1;
+        self.move_column_to(new_idx);
+    }
+
+    /// Moves the active column to the given 1-based index, or to the end if there are fewer.
+    pub fn move_column_to_index(&mut self, index: usize) {
+        if self.columns.is_empty() || self.is_floating_focused() {
             return;
         }
 
-        let new_idx = self.columns.len() - 1;
+        let new_idx = min(index.saturating_sub(1), self.columns.len() - 1);
         self.move_column_to(new_idx);
     }
 
     pub fn move_down(&mut self) {
+        if self.is_floating_focused() {
+            self.move_floating_by(Point::from((0, FLOATING_MOVE_AMOUNT)));
+            return;
+        }
+
         if self.columns.is_empty() {
             return;
         }
@@ -1004,6 +1716,11 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn move_up(&mut self) {
+        if self.is_floating_focused() {
+            self.move_floating_by(Point::from((0, -FLOATING_MOVE_AMOUNT)));
+            return;
+        }
+
         if self.columns.is_empty() {
             return;
         }
@@ -1011,72 +1728,200 @@ impl<W: LayoutElement> Workspace<W> {
         self.columns[self.active_column_idx].move_up();
     }
 
-    pub fn consume_or_expel_window_left(&mut self) {
-        if self.columns.is_empty() {
+    pub fn consume_or_expel_window_left(&mut self, window: Option<&W::Id>) {
+        if self.columns.is_empty() || self.floating_target_idx(window).is_some() {
             return;
         }
 
-        let source_column = &self.columns[self.active_column_idx];
+        let (source_col_idx, source_tile_idx) = self.window_position(window);
+        let was_focused = self.is_window_position_focused(source_col_idx, source_tile_idx);
+
+        let source_column = &self.columns[source_col_idx];
         if source_column.tiles.len() == 1 {
-            if self.active_column_idx == 0 {
+            if source_col_idx == 0 {
                 return;
             }
 
             // Move into adjacent column.
-            let target_column_idx = self.active_column_idx - 1;
-            let window = self.remove_window_by_idx(self.active_column_idx, 0);
+            let target_column_idx = source_col_idx - 1;
+            let window = self.remove_window_by_idx(source_col_idx, 0);
             self.enter_output_for_window(&window);
 
             let target_column = &mut self.columns[target_column_idx];
             target_column.add_window(window);
-            target_column.focus_last();
-            self.activate_column(target_column_idx);
+            if was_focused {
+                target_column.focus_last();
+                self.activate_column(target_column_idx);
+            }
         } else {
             // Move out of column.
             let width = source_column.width;
             let is_full_width = source_column.is_full_width;
-            let window =
-                self.remove_window_by_idx(self.active_column_idx, source_column.active_tile_idx);
+            let window = self.remove_window_by_idx(source_col_idx, source_tile_idx);
 
-            self.add_window(window, true, width, is_full_width);
-            // Window was added to the right of current column, so move the new column left.
-            self.move_left();
+            if was_focused {
+                self.add_window(window, true, width, is_full_width);
+                // Window was added to the right of current column, so move the new column left.
+                self.move_left();
+            } else {
+                self.insert_window_column(source_col_idx, window, width, is_full_width);
+            }
         }
     }
 
-    pub fn consume_or_expel_window_right(&mut self) {
-        if self.columns.is_empty() {
+    pub fn consume_or_expel_window_right(&mut self, window: Option<&W::Id>) {
+        if self.columns.is_empty() || self.floating_target_idx(window).is_some() {
             return;
         }
 
-        let source_column = &self.columns[self.active_column_idx];
+        let (source_col_idx, source_tile_idx) = self.window_position(window);
+        let was_focused = self.is_window_position_focused(source_col_idx, source_tile_idx);
+
+        let source_column = &self.columns[source_col_idx];
         if source_column.tiles.len() == 1 {
-            if self.active_column_idx + 1 == self.columns.len() {
+            if source_col_idx + 1 == self.columns.len() {
                 return;
             }
 
-            // Move into adjacent column.
-            let target_column_idx = self.active_column_idx;
-            let window = self.remove_window_by_idx(self.active_column_idx, 0);
+            // Move into adjacent column. Once the source column is removed, the target column
+            // takes its index.
+            let target_column_idx = source_col_idx;
+            let window = self.remove_window_by_idx(source_col_idx, 0);
             self.enter_output_for_window(&window);
 
             let target_column = &mut self.columns[target_column_idx];
             target_column.add_window(window);
-            target_column.focus_last();
-            self.activate_column(target_column_idx);
+            if was_focused {
+                target_column.focus_last();
+                self.activate_column(target_column_idx);
+            }
         } else {
             // Move out of column.
             let width = source_column.width;
             let is_full_width = source_column.is_full_width;
-            let window =
-                self.remove_window_by_idx(self.active_column_idx, source_column.active_tile_idx);
+            let window = self.remove_window_by_idx(source_col_idx, source_tile_idx);
+
+            if was_focused {
+                self.add_window(window, true, width, is_full_width);
+            } else {
+                self.insert_window_column(source_col_idx + 1, window, width, is_full_width);
+            }
+        }
+    }
+
+    pub fn swap_window_left(&mut self) {
+        if self.columns.is_empty() || self.is_floating_focused() {
+            return;
+        }
+
+        if self.active_column_idx == 0 {
+            return;
+        }
+
+        self.swap_window_with_column(self.active_column_idx - 1);
+    }
+
+    pub fn swap_window_right(&mut self) {
+        if self.columns.is_empty() || self.is_floating_focused() {
+            return;
+        }
+
+        if self.active_column_idx + 1 == self.columns.len() {
+            return;
+        }
+
+        self.swap_window_with_column(self.active_column_idx + 1);
+    }
+
+    /// Swaps the active window with the active window of the column at `target_col_idx`.
+    ///
+    /// The columns keep their widths, and the window heights stay in place, so both columns keep
+    /// their shapes.
+    fn swap_window_with_column(&mut self, target_col_idx: usize) {
+        let source_col_idx = self.active_column_idx;
+        if self.columns[source_col_idx].is_fullscreen || self.columns[target_col_idx].is_fullscreen
+        {
+            return;
+        }
+
+        let source_tile_idx = self.columns[source_col_idx].active_tile_idx;
+        let target_tile_idx = self.columns[target_col_idx].active_tile_idx;
+
+        let tile_pos = |ws: &Self, col_idx: usize, tile_idx: usize| {
+            Point::from((ws.column_x(col_idx), ws.columns[col_idx].tile_y(tile_idx)))
+        };
+        let source_pos = tile_pos(self, source_col_idx, source_tile_idx);
+        let target_pos = tile_pos(self, target_col_idx, target_tile_idx);
+
+        let left_col_idx = min(source_col_idx, target_col_idx);
+        let right_col_idx = max(source_col_idx, target_col_idx);
+        let (left, right) = self.columns.split_at_mut(right_col_idx);
+        let (left_col, right_col) = (&mut left[left_col_idx], &mut right[0]);
+        mem::swap(
+            &mut left_col.tiles[left_col.active_tile_idx],
+            &mut right_col.tiles[right_col.active_tile_idx],
+        );
+        left_col.update_tile_sizes();
+        right_col.update_tile_sizes();
+
+        let new_source_pos = tile_pos(self, target_col_idx, target_tile_idx);
+        let new_target_pos = tile_pos(self, source_col_idx, source_tile_idx);
+        self.columns[target_col_idx].tiles[target_tile_idx]
+            .animate_move_from(source_pos - new_source_pos);
+        self.columns[source_col_idx].tiles[source_tile_idx]
+            .animate_move_from(target_pos - new_target_pos);
+
+        // The focus follows the moved window.
+        self.activate_column(target_col_idx);
+    }
+
+    /// Returns the column and tile indices of the window, or of the active window if `None`.
+    ///
+    /// The workspace must not be empty, and the window, if any, must be on this workspace.
+    pub fn window_position(&self, window: Option<&W::Id>) -> (usize, usize) {
+        if let Some(window) = window {
+            self.columns
+                .iter()
+                .enumerate()
+                .find_map(|(col_idx, col)| col.position(window).map(|tile_idx| (col_idx, tile_idx)))
+                .unwrap()
+        } else {
+            let col = &self.columns[self.active_column_idx];
+            (self.active_column_idx, col.active_tile_idx)
+        }
+    }
+
+    fn is_window_position_focused(&self, col_idx: usize, tile_idx: usize) -> bool {
+        self.active_column_idx == col_idx && self.columns[col_idx].active_tile_idx == tile_idx
+    }
+
+    /// Inserts a new column with the window at `idx` without activating it.
+    fn insert_window_column(
+        &mut self,
+        idx: usize,
+        window: W,
+        width: ColumnWidth,
+        is_full_width: bool,
+    ) {
+        self.enter_output_for_window(&window);
+
+        let column = Column::new(
+            window,
+            self.view_size,
+            self.working_area,
+            self.options.clone(),
+            width,
+            is_full_width,
+        );
+        self.columns.insert(idx, column);
 
-            self.add_window(window, true, width, is_full_width);
+        if idx <= self.active_column_idx {
+            self.active_column_idx += 1;
         }
     }
 
     pub fn consume_into_column(&mut self) {
-        if self.columns.len() < 2 {
+        if self.columns.len() < 2 || self.is_floating_focused() {
             return;
         }
 
@@ -1093,7 +1938,7 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn expel_from_column(&mut self) {
-        if self.columns.is_empty() {
+        if self.columns.is_empty() || self.is_floating_focused() {
             return;
         }
 
@@ -1110,6 +1955,14 @@ impl<W: LayoutElement> Workspace<W> {
         self.add_window(window, true, width, is_full_width);
     }
 
+    pub fn toggle_column_tabbed_display(&mut self) {
+        if self.columns.is_empty() || self.is_floating_focused() {
+            return;
+        }
+
+        self.columns[self.active_column_idx].toggle_tabbed_display();
+    }
+
     pub fn center_column(&mut self) {
         let center_x = self.view_pos();
         self.animate_view_offset_to_column_centered(center_x, self.active_column_idx);
@@ -1132,6 +1985,24 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     fn tiles_in_render_order(&self) -> impl Iterator<Item = (&'_ Tile<W>, Point<i32, Logical>)> {
+        // Floating tiles are drawn above the columns, starting from the topmost one.
+        let floating = self
+            .floating
+            .iter()
+            .rev()
+            .map(|floating| (&floating.tile, floating.pos));
+
+        let columns = (!self.columns.is_empty())
+            .then(|| self.column_tiles_in_render_order())
+            .into_iter()
+            .flatten();
+
+        floating.chain(columns)
+    }
+
+    fn column_tiles_in_render_order(
+        &self,
+    ) -> impl Iterator<Item = (&'_ Tile<W>, Point<i32, Logical>)> {
         let view_pos = self.visual_column_x(self.active_column_idx) + self.view_offset;
 
         // Start with the active window since it's drawn on top.
@@ -1162,6 +2033,11 @@ impl<W: LayoutElement> Workspace<W> {
                             return None;
                         }
 
+                        if col.display == ColumnDisplay::Tabbed && tile_idx != col.active_tile_idx {
+                            // Only the active tab is visible.
+                            return None;
+                        }
+
                         let tile_pos = Point::from((x, y));
                         Some((tile, tile_pos))
                     },
@@ -1181,6 +2057,14 @@ impl<W: LayoutElement> Workspace<W> {
     ///
     /// During animations, assumes the final view position.
     pub fn active_tile_visual_rectangle(&self) -> Option<Rectangle<i32, Logical>> {
+        let view = Rectangle::from_loc_and_size((0, 0), self.view_size);
+
+        if self.is_floating_focused() {
+            let floating = self.floating.last()?;
+            let tile_rect = Rectangle::from_loc_and_size(floating.pos, floating.tile.tile_size());
+            return view.intersection(tile_rect);
+        }
+
         let col = self.active_column_ref()?;
         let view_pos = self
             .view_offset_adj
@@ -1191,7 +2075,6 @@ impl<W: LayoutElement> Workspace<W> {
         let tile_size = col.active_tile_ref().tile_size();
         let tile_rect = Rectangle::from_loc_and_size(tile_pos, tile_size);
 
-        let view = Rectangle::from_loc_and_size((0, 0), self.view_size);
         view.intersection(tile_rect)
     }
 
@@ -1199,10 +2082,6 @@ impl<W: LayoutElement> Workspace<W> {
         &self,
         pos: Point<f64, Logical>,
     ) -> Option<(&W, Option<Point<i32, Logical>>)> {
-        if self.columns.is_empty() {
-            return None;
-        }
-
         self.tiles_in_render_order().find_map(|(tile, tile_pos)| {
             let pos_within_tile = pos - tile_pos.to_f64();
 
@@ -1218,7 +2097,7 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn toggle_width(&mut self) {
-        if self.columns.is_empty() {
+        if self.columns.is_empty() || self.is_floating_focused() {
             return;
         }
 
@@ -1226,30 +2105,179 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn toggle_full_width(&mut self) {
-        if self.columns.is_empty() {
+        if self.columns.is_empty() || self.is_floating_focused() {
             return;
         }
 
         self.columns[self.active_column_idx].toggle_full_width();
     }
 
-    pub fn set_column_width(&mut self, change: SizeChange) {
+    pub fn set_column_width(&mut self, window: Option<&W::Id>, change: SizeChange) {
+        if let Some(idx) = self.floating_target_idx(window) {
+            let window = self.floating[idx].tile.window();
+            let size = window.size();
+            let width = resolve_floating_size_change(
+                size.w,
+                change,
+                self.working_area.size.w,
+                window.min_size().w,
+                window.max_size().w,
+            );
+            window.request_size(Size::from((width, size.h)));
+            return;
+        }
+
+        if self.columns.is_empty() {
+            return;
+        }
+
+        let (col_idx, tile_idx) = self.window_position(window);
+        self.columns[col_idx].set_column_width(tile_idx, change);
+    }
+
+    pub fn set_window_height(&mut self, window: Option<&W::Id>, change: SizeChange) {
+        if let Some(idx) = self.floating_target_idx(window) {
+            let window = self.floating[idx].tile.window();
+            let size = window.size();
+            let height = resolve_floating_size_change(
+                size.h,
+                change,
+                self.working_area.size.h,
+                window.min_size().h,
+                window.max_size().h,
+            );
+            window.request_size(Size::from((size.w, height)));
+            return;
+        }
+
+        if self.columns.is_empty() {
+            return;
+        }
+
+        let (col_idx, tile_idx) = self.window_position(window);
+        self.columns[col_idx].set_window_height(tile_idx, change);
+    }
+
+    pub fn toggle_window_height(&mut self, window: Option<&W::Id>) {
+        if let Some(idx) = self.floating_target_idx(window) {
+            let window = self.floating[idx].tile.window();
+            let size = window.size();
+            let available = self.working_area.size.h;
+            let (min_h, max_h) = (window.min_size().h, window.max_size().h);
+            let heights = self
+                .options
+                .preset_window_heights
+                .iter()
+                .map(|preset| {
+                    let change = match *preset {
+                        PresetSize::Proportion(p) => SizeChange::SetProportion(p * 100.),
+                        PresetSize::Fixed(f) => SizeChange::SetFixed(f),
+                    };
+                    resolve_floating_size_change(size.h, change, available, min_h, max_h)
+                })
+                .collect::<Vec<_>>();
+            let height = heights
+                .iter()
+                .find(|h| **h > size.h)
+                .or(heights.first())
+                .copied()
+                .unwrap_or(size.h);
+            window.request_size(Size::from((size.w, height)));
+            return;
+        }
+
         if self.columns.is_empty() {
             return;
         }
 
-        self.columns[self.active_column_idx].set_column_width(change);
+        let (col_idx, tile_idx) = self.window_position(window);
+        self.columns[col_idx].toggle_window_height(tile_idx);
     }
 
-    pub fn set_window_height(&mut self, change: SizeChange) {
+    pub fn reset_window_height(&mut self, window: Option<&W::Id>) {
+        // Floating windows have no automatic height to go back to.
+        if self.floating_target_idx(window).is_some() {
+            return;
+        }
+
         if self.columns.is_empty() {
             return;
         }
 
-        self.columns[self.active_column_idx].set_window_height(change);
+        let (col_idx, tile_idx) = self.window_position(window);
+        self.columns[col_idx].reset_window_height(tile_idx);
+    }
+
+    /// Interactively resizes the window by its edges from `old_size` to `new_size`.
+    ///
+    /// The sizes are window sizes. Returns the new size clamped to the window's size constraints.
+    pub fn resize_window_edges(
+        &mut self,
+        window: &W::Id,
+        edges: ResizeEdge,
+        old_size: Size<i32, Logical>,
+        new_size: Size<i32, Logical>,
+    ) -> Size<i32, Logical> {
+        let Some(win) = self.windows().find(|win| win.id() == window) else {
+            return old_size;
+        };
+
+        let clamp = |size: i32, min_size: i32, max_size: i32| {
+            let mut size = size;
+            if max_size > 0 {
+                size = size.min(max_size);
+            }
+            if min_size > 0 {
+                size = size.max(min_size);
+            }
+            size.max(1)
+        };
+        let (min_size, max_size) = (win.min_size(), win.max_size());
+        let mut size = old_size;
+        if edges.intersects(ResizeEdge::LEFT_RIGHT) {
+            size.w = clamp(new_size.w, min_size.w, max_size.w);
+        }
+        if edges.intersects(ResizeEdge::TOP_BOTTOM) {
+            size.h = clamp(new_size.h, min_size.h, max_size.h);
+        }
+
+        if let Some(idx) = self.floating_idx(window) {
+            let floating = &mut self.floating[idx];
+            floating.tile.window().request_size(size);
+
+            // Keep the opposite edges in place.
+            if edges.contains(ResizeEdge::LEFT) {
+                floating.pos.x -= size.w - old_size.w;
+            }
+            if edges.contains(ResizeEdge::TOP) {
+                floating.pos.y -= size.h - old_size.h;
+            }
+
+            return size;
+        }
+
+        let (col_idx, tile_idx) = self.window_position(Some(window));
+        let col = &mut self.columns[col_idx];
+        if edges.intersects(ResizeEdge::LEFT_RIGHT) {
+            col.set_column_width(tile_idx, SizeChange::SetFixed(size.w));
+        }
+        if edges.intersects(ResizeEdge::TOP_BOTTOM) {
+            col.set_window_height(tile_idx, SizeChange::SetFixed(size.h));
+        }
+
+        size
     }
 
     pub fn set_fullscreen(&mut self, window: &W::Id, is_fullscreen: bool) {
+        if self.is_floating(window) {
+            if !is_fullscreen {
+                return;
+            }
+
+            // Fullscreen windows live in columns.
+            self.toggle_window_floating(Some(window));
+        }
+
         let (mut col_idx, tile_idx) = self
             .columns
             .iter()
@@ -1292,6 +2320,11 @@ impl<W: LayoutElement> Workspace<W> {
     }
 
     pub fn toggle_fullscreen(&mut self, window: &W::Id) {
+        if self.is_floating(window) {
+            self.set_fullscreen(window, true);
+            return;
+        }
+
         let col = self
             .columns
             .iter_mut()
@@ -1303,7 +2336,7 @@ impl<W: LayoutElement> Workspace<W> {
 
     pub fn render_above_top_layer(&self) -> bool {
         // Render above the top layer if we're on a fullscreen window and the view is stationary.
-        if self.columns.is_empty() {
+        if self.columns.is_empty() || self.is_floating_focused() {
             return false;
         }
 
@@ -1319,7 +2352,7 @@ impl<W: LayoutElement> Workspace<W> {
         renderer: &mut R,
         target: RenderTarget,
     ) -> Vec<WorkspaceRenderElement<R>> {
-        if self.columns.is_empty() {
+        if !self.has_windows() {
             return vec![];
         }
 
@@ -1332,12 +2365,17 @@ impl<W: LayoutElement> Workspace<W> {
             .unwrap_or(Scale::from(1.));
 
         let mut rv = vec![];
-        let mut first = true;
 
-        for (tile, tile_pos) in self.tiles_in_render_order() {
-            // For the active tile (which comes first), draw the focus ring.
-            let focus_ring = first;
-            first = false;
+        // The active tile comes first among either the floating or the column tiles.
+        let active_idx = if self.is_floating_focused() {
+            0
+        } else {
+            self.floating.len()
+        };
+
+        for (idx, (tile, tile_pos)) in self.tiles_in_render_order().enumerate() {
+            // For the active tile, draw the focus ring.
+            let focus_ring = idx == active_idx;
 
             rv.extend(
                 tile.render(
@@ -1352,6 +2390,17 @@ impl<W: LayoutElement> Workspace<W> {
             );
         }
 
+        // Draw the tab indicators of the tabbed columns.
+        let mut x = -(self.visual_column_x(self.active_column_idx) + self.view_offset);
+        for col in &self.columns {
+            if col.display == ColumnDisplay::Tabbed && !col.is_fullscreen {
+                let pos = Point::from((x, col.tile_y(col.active_tile_idx)));
+                rv.extend(col.tab_indicator.render(pos, output_scale).map(Into::into));
+            }
+
+            x += col.visual_width() + self.options.gaps;
+        }
+
         rv
     }
 
@@ -1561,11 +2610,13 @@ impl<W: LayoutElement> Workspace<W> {
 
     pub fn refresh(&mut self, is_active: bool) {
         let bounds = self.toplevel_bounds();
+        let is_floating_focused = self.is_floating_focused();
 
         for (col_idx, col) in self.columns.iter_mut().enumerate() {
             for (tile_idx, tile) in col.tiles.iter_mut().enumerate() {
                 let win = tile.window_mut();
                 let active = is_active
+                    && !is_floating_focused
                     && self.active_column_idx == col_idx
                     && col.active_tile_idx == tile_idx;
                 win.set_activated(active);
@@ -1575,6 +2626,16 @@ impl<W: LayoutElement> Workspace<W> {
                 win.refresh();
             }
         }
+
+        let top_idx = self.floating.len().saturating_sub(1);
+        for (idx, floating) in self.floating.iter_mut().enumerate() {
+            let win = floating.tile.window_mut();
+            win.set_activated(is_active && is_floating_focused && idx == top_idx);
+
+            win.set_bounds(bounds);
+            win.send_pending_configure();
+            win.refresh();
+        }
     }
 }
 
@@ -1594,6 +2655,8 @@ impl<W: LayoutElement> Column<W> {
             width,
             is_full_width,
             is_fullscreen: false,
+            display: ColumnDisplay::Normal,
+            tab_indicator: TabIndicator::new(options.focus_ring),
             view_size,
             working_area,
             options,
@@ -1631,6 +2694,22 @@ impl<W: LayoutElement> Column<W> {
             }
         }
 
+        // If preset heights changed, make our heights non-preset.
+        if self.options.preset_window_heights != options.preset_window_heights {
+            for (tile, h) in zip(&self.tiles, &mut self.heights) {
+                if let WindowHeight::Preset(idx) = *h {
+                    let preset = self.options.preset_window_heights[idx];
+                    let tile_height = resolve_preset_height(
+                        &self.options,
+                        self.working_area.size.h,
+                        preset,
+                        tile,
+                    );
+                    *h = WindowHeight::Fixed(tile.window_height_for_tile_height(tile_height));
+                }
+            }
+        }
+
         if self.options.gaps != options.gaps {
             update_sizes = true;
         }
@@ -1645,6 +2724,8 @@ impl<W: LayoutElement> Column<W> {
             tile.update_config(options.clone());
         }
 
+        self.tab_indicator.update_config(options.focus_ring);
+
         self.options = options;
 
         if update_sizes {
@@ -1663,6 +2744,16 @@ impl<W: LayoutElement> Column<W> {
             let is_active = is_active && tile_idx == self.active_tile_idx;
             tile.advance_animations(current_time, is_active);
         }
+
+        if self.display == ColumnDisplay::Tabbed {
+            self.tab_indicator.update(
+                self.tiles.len(),
+                self.active_tile_idx,
+                self.active_tile_ref().tile_size().h,
+                self.options.gaps,
+                is_active,
+            );
+        }
     }
 
     pub fn are_animations_ongoing(&self) -> bool {
@@ -1689,10 +2780,14 @@ impl<W: LayoutElement> Column<W> {
     }
 
     fn add_window(&mut self, window: W) {
+        self.insert_window(self.tiles.len(), window);
+    }
+
+    fn insert_window(&mut self, idx: usize, window: W) {
         let tile = Tile::new(window, self.options.clone());
         self.is_fullscreen = false;
-        self.tiles.push(tile);
-        self.heights.push(WindowHeight::Auto);
+        self.tiles.insert(idx, tile);
+        self.heights.insert(idx, WindowHeight::Auto);
         self.update_tile_sizes();
     }
 
@@ -1750,6 +2845,23 @@ impl<W: LayoutElement> Column<W> {
         let width = width.resolve(&self.options, self.working_area.size.w);
         let width = max(min(width, max_width), min_width);
 
+        if self.display == ColumnDisplay::Tabbed {
+            // Every tab gets the full column height.
+            let full_height = self.working_area.size.h - self.options.gaps * 2;
+            for (tile, (min_size, max_size)) in zip(&mut self.tiles, zip(&min_size, &max_size)) {
+                let mut height = full_height;
+                if max_size.h > 0 {
+                    height = min(height, max_size.h);
+                }
+                if min_size.h > 0 {
+                    height = max(height, min_size.h);
+                }
+
+                tile.request_tile_size(Size::from((width, max(height, 1))));
+            }
+            return;
+        }
+
         // Compute the tile heights. Start by converting window heights to tile heights.
         let mut heights = zip(&self.tiles, &self.heights)
             .map(|(tile, height)| match *height {
@@ -1757,6 +2869,15 @@ impl<W: LayoutElement> Column<W> {
                 WindowHeight::Fixed(height) => {
                     WindowHeight::Fixed(tile.tile_height_for_window_height(height))
                 }
+                WindowHeight::Preset(idx) => {
+                    let preset = self.options.preset_window_heights[idx];
+                    WindowHeight::Fixed(resolve_preset_height(
+                        &self.options,
+                        self.working_area.size.h,
+                        preset,
+                        tile,
+                    ))
+                }
             })
             .collect::<Vec<_>>();
         let mut height_left = self.working_area.size.h - self.options.gaps;
@@ -1870,6 +2991,17 @@ impl<W: LayoutElement> Column<W> {
             .unwrap()
     }
 
+    /// Returns the column width to carry over when a window leaves this column.
+    ///
+    /// Preset indices only make sense with this column's options, so they are resolved to the
+    /// preset itself.
+    fn placement_width(&self) -> ColumnWidth {
+        match self.width {
+            ColumnWidth::Preset(idx) => self.options.preset_widths[idx],
+            width => width,
+        }
+    }
+
     fn visual_width(&self) -> i32 {
         self.tiles
             .iter()
@@ -1912,12 +3044,23 @@ impl<W: LayoutElement> Column<W> {
         self.active_tile_idx = new_idx;
     }
 
+    #[cfg(test)]
+    pub fn heights(&self) -> &[WindowHeight] {
+        &self.heights
+    }
+
     #[cfg(test)]
     fn verify_invariants(&self) {
         assert!(!self.tiles.is_empty(), "columns can't be empty");
         assert!(self.active_tile_idx < self.tiles.len());
         assert_eq!(self.tiles.len(), self.heights.len());
 
+        for h in &self.heights {
+            if let WindowHeight::Preset(idx) = *h {
+                assert!(idx < self.options.preset_window_heights.len());
+            }
+        }
+
         if self.is_fullscreen {
             assert_eq!(self.tiles.len(), 1);
         }
@@ -1956,7 +3099,7 @@ impl<W: LayoutElement> Column<W> {
         self.update_tile_sizes();
     }
 
-    fn set_column_width(&mut self, change: SizeChange) {
+    fn set_column_width(&mut self, tile_idx: usize, change: SizeChange) {
         let width = if self.is_full_width {
             ColumnWidth::Proportion(1.)
         } else {
@@ -1977,9 +3120,9 @@ impl<W: LayoutElement> Column<W> {
         let width = match (current, change) {
             (_, SizeChange::SetFixed(fixed)) => {
                 // As a special case, setting a fixed column width will compute it in such a way
-                // that the active window gets that width. This is the intention behind the ability
+                // that the target window gets that width. This is the intention behind the ability
                 // to set a fixed size.
-                let tile = &self.tiles[self.active_tile_idx];
+                let tile = &self.tiles[tile_idx];
                 ColumnWidth::Fixed(tile.tile_width_for_window_width(fixed).clamp(1, MAX_PX))
             }
             (_, SizeChange::SetProportion(proportion)) => {
@@ -2005,11 +3148,11 @@ impl<W: LayoutElement> Column<W> {
         self.set_width(width);
     }
 
-    fn set_window_height(&mut self, change: SizeChange) {
-        let current = self.heights[self.active_tile_idx];
-        let tile = &self.tiles[self.active_tile_idx];
+    fn set_window_height(&mut self, tile_idx: usize, change: SizeChange) {
+        let current = self.heights[tile_idx];
+        let tile = &self.tiles[tile_idx];
         let current_window_px = match current {
-            WindowHeight::Auto => tile.window_size().h,
+            WindowHeight::Auto | WindowHeight::Preset(_) => tile.window_size().h,
             WindowHeight::Fixed(height) => height,
         };
         let current_tile_px = tile.tile_height_for_window_height(current_window_px);
@@ -2040,7 +3183,7 @@ impl<W: LayoutElement> Column<W> {
         };
 
         // Clamp it against the window height constraints.
-        let win = &self.tiles[self.active_tile_idx].window();
+        let win = &self.tiles[tile_idx].window();
         let min_h = win.min_size().h;
         let max_h = win.max_size().h;
 
@@ -2051,7 +3194,38 @@ impl<W: LayoutElement> Column<W> {
             window_height = window_height.max(min_h);
         }
 
-        self.heights[self.active_tile_idx] = WindowHeight::Fixed(window_height.clamp(1, MAX_PX));
+        self.heights[tile_idx] = WindowHeight::Fixed(window_height.clamp(1, MAX_PX));
+        self.update_tile_sizes();
+    }
+
+    fn toggle_window_height(&mut self, tile_idx: usize) {
+        let idx = match self.heights[tile_idx] {
+            WindowHeight::Preset(idx) => (idx + 1) % self.options.preset_window_heights.len(),
+            _ => {
+                let tile = &self.tiles[tile_idx];
+                let current = tile.tile_size().h;
+                self.options
+                    .preset_window_heights
+                    .iter()
+                    .position(|preset| {
+                        let height = resolve_preset_height(
+                            &self.options,
+                            self.working_area.size.h,
+                            *preset,
+                            tile,
+                        );
+                        height > current
+                    })
+                    .unwrap_or(0)
+            }
+        };
+
+        self.heights[tile_idx] = WindowHeight::Preset(idx);
+        self.update_tile_sizes();
+    }
+
+    fn reset_window_height(&mut self, tile_idx: usize) {
+        self.heights[tile_idx] = WindowHeight::Auto;
         self.update_tile_sizes();
     }
 
@@ -2077,13 +3251,55 @@ impl<W: LayoutElement> Column<W> {
             y = self.working_area.loc.y + self.options.gaps;
         }
 
+        let is_tabbed = self.display == ColumnDisplay::Tabbed;
         self.tiles.iter().map(move |tile| {
             let pos = y;
-            y += tile.tile_size().h + self.options.gaps;
+            // Tabs are all at the top of the column.
+            if !is_tabbed {
+                y += tile.tile_size().h + self.options.gaps;
+            }
             pos
         })
     }
 
+    /// Computes the index to insert a window dropped at the given Y position into this column.
+    ///
+    /// Returns `None` if the window was dropped in the middle of a tile.
+    fn insert_tile_idx(&self, y: f64) -> Option<usize> {
+        if self.is_fullscreen {
+            return None;
+        }
+
+        let is_tabbed = self.display == ColumnDisplay::Tabbed;
+        let mut idx = 0;
+        for (tile_idx, (tile, tile_y)) in zip(&self.tiles, self.tile_ys()).enumerate() {
+            if is_tabbed && tile_idx != self.active_tile_idx {
+                continue;
+            }
+
+            let tile_y = f64::from(tile_y);
+            let h = f64::from(tile.tile_size().h);
+            let edge = h / 4.;
+            if y < tile_y + edge {
+                return Some(tile_idx);
+            }
+            if y < tile_y + h - edge {
+                return None;
+            }
+            idx = tile_idx + 1;
+        }
+
+        Some(idx)
+    }
+
+    fn toggle_tabbed_display(&mut self) {
+        self.display = match self.display {
+            ColumnDisplay::Normal => ColumnDisplay::Tabbed,
+            ColumnDisplay::Tabbed => ColumnDisplay::Normal,
+        };
+        self.update_tile_sizes();
+    }
+
     fn active_tile_ref(&self) -> &Tile<W> {
         &self.tiles[self.active_tile_idx]
     }
@@ -2130,6 +3346,78 @@ fn set_preferred_scale_transform(window: &impl LayoutElement, output: &Output) {
     window.set_preferred_scale_transform(scale, transform);
 }
 
+/// Resolves a height preset to a tile height for `tile` in a column.
+fn resolve_preset_height<W: LayoutElement>(
+    options: &Options,
+    working_height: i32,
+    preset: PresetSize,
+    tile: &Tile<W>,
+) -> i32 {
+    // FIXME: fix overflows then remove limits.
+    const MAX_PX: i32 = 100000;
+
+    match preset {
+        PresetSize::Proportion(proportion) => {
+            let gaps = options.gaps as f64;
+            let height = (working_height as f64 - gaps) * proportion - gaps;
+            (height.round() as i32).clamp(1, MAX_PX)
+        }
+        PresetSize::Fixed(fixed) => tile.tile_height_for_window_height(fixed.clamp(1, MAX_PX)),
+    }
+}
+
+/// Resolves a size change of a floating window along one axis, in window pixels.
+fn resolve_floating_size_change(
+    current: i32,
+    change: SizeChange,
+    available: i32,
+    min_size: i32,
+    max_size: i32,
+) -> i32 {
+    // FIXME: fix overflows then remove limits.
+    const MAX_PX: i32 = 100000;
+
+    let mut size = match change {
+        SizeChange::SetFixed(fixed) => fixed,
+        SizeChange::SetProportion(proportion) => {
+            (available as f64 * proportion / 100.).round() as i32
+        }
+        SizeChange::AdjustFixed(delta) => current.saturating_add(delta),
+        SizeChange::AdjustProportion(delta) => {
+            current.saturating_add((available as f64 * delta / 100.).round() as i32)
+        }
+    };
+
+    if max_size > 0 {
+        size = size.min(max_size);
+    }
+    if min_size > 0 {
+        size = size.max(min_size);
+    }
+
+    size.clamp(1, MAX_PX)
+}
+
+/// Moves a floating tile so that at least a part of it is inside the working area.
+fn clamp_floating_pos(
+    pos: Point<i32, Logical>,
+    size: Size<i32, Logical>,
+    area: Rectangle<i32, Logical>,
+) -> Point<i32, Logical> {
+    let visible_w = max(min(min(size.w, area.size.w), FLOATING_MIN_VISIBLE), 0);
+    let visible_h = max(min(min(size.h, area.size.h), FLOATING_MIN_VISIBLE), 0);
+
+    let x = max(
+        min(pos.x, area.loc.x + area.size.w - visible_w),
+        area.loc.x - size.w + visible_w,
+    );
+    let y = max(
+        min(pos.y, area.loc.y + area.size.h - visible_h),
+        area.loc.y - size.h + visible_h,
+    );
+    Point::from((x, y))
+}
+
 pub fn compute_working_area(output: &Output, struts: Struts) -> Rectangle<i32, Logical> {
     // Start with the layer-shell non-exclusive zone.
     let mut working_area = layer_map_for_output(output).non_exclusive_zone();
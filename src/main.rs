@@ -177,6 +177,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         display,
     )
     .unwrap();
+    state.niri.config_path = path.clone();
 
     // Set WAYLAND_DISPLAY for children.
     let socket_name = &state.niri.socket_name;
@@ -225,7 +226,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         event_loop
             .handle()
             .insert_source(rx, move |event, _, state| match event {
-                calloop::channel::Event::Msg(()) => state.reload_config(path.clone()),
+                calloop::channel::Event::Msg(()) => {
+                    // Errors are already shown by the config error notification.
+                    let _ = state.reload_config(path.clone());
+                }
                 calloop::channel::Event::Closed => (),
             })
             .unwrap();
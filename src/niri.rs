@@ -11,7 +11,7 @@ use std::{env, mem, thread};
 use _server_decoration::server::org_kde_kwin_server_decoration_manager::Mode as KdeDecorationsMode;
 use anyhow::{ensure, Context};
 use calloop::futures::Scheduler;
-use niri_config::{Config, Key, Modifiers, PreviewRender, TrackLayout};
+use niri_config::{Config, Key, Modifiers, PreviewRender, TrackLayout, WorkspaceReference};
 use smithay::backend::allocator::Fourcc;
 use smithay::backend::renderer::element::memory::MemoryRenderBufferRenderElement;
 use smithay::backend::renderer::element::solid::{SolidColorBuffer, SolidColorRenderElement};
@@ -86,6 +86,7 @@ use smithay::wayland::tablet_manager::{TabletManagerState, TabletSeatTrait};
 use smithay::wayland::text_input::TextInputManagerState;
 use smithay::wayland::viewporter::ViewporterState;
 use smithay::wayland::virtual_keyboard::VirtualKeyboardManagerState;
+use smithay::wayland::xdg_activation::XdgActivationState;
 use smithay::wayland::xdg_foreign::XdgForeignState;
 
 use crate::backend::tty::SurfaceDmabufFeedback;
@@ -101,7 +102,9 @@ use crate::input::{
     apply_libinput_settings, mods_with_finger_scroll_binds, mods_with_wheel_binds, TabletData,
 };
 use crate::ipc::server::IpcServer;
-use crate::layout::{Layout, LayoutElement as _, MonitorRenderElement};
+use crate::layout::{
+    InteractiveMoveRenderElement, Layout, LayoutElement as _, MonitorRenderElement,
+};
 use crate::protocols::foreign_toplevel::{self, ForeignToplevelManagerState};
 use crate::protocols::gamma_control::GammaControlManagerState;
 use crate::protocols::screencopy::{Screencopy, ScreencopyManagerState};
@@ -113,11 +116,15 @@ use crate::ui::config_error_notification::ConfigErrorNotification;
 use crate::ui::exit_confirm_dialog::ExitConfirmDialog;
 use crate::ui::hotkey_overlay::HotkeyOverlay;
 use crate::ui::screenshot_ui::{ScreenshotUi, ScreenshotUiRenderElement};
+use crate::ui::window_switcher::{
+    order_by_recent_use, WindowSwitcher, WindowSwitcherRenderElement,
+};
 use crate::utils::spawning::CHILD_ENV;
 use crate::utils::{
     center, center_f64, get_monotonic_time, ipc_transform_to_smithay, logical_output,
     make_screenshot_path, output_size, write_png_rgba8,
 };
+use crate::window::mapped::MappedId;
 use crate::window::{InitialConfigureState, Mapped, ResolvedWindowRules, Unmapped, WindowRef};
 use crate::{animation, niri_render_elements};
 
@@ -131,6 +138,14 @@ const FRAME_CALLBACK_THROTTLE: Option<Duration> = Some(Duration::from_millis(995
 
 pub struct Niri {
     pub config: Rc<RefCell<Config>>,
+    /// Path to the config file, if any, used for reloading it on request.
+    pub config_path: Option<PathBuf>,
+    /// Output sections as written in the config file.
+    ///
+    /// The outputs in `config` additionally have the transient changes applied.
+    pub file_output_config: Vec<niri_config::Output>,
+    /// Output changes made at runtime over IPC, by output name.
+    pub transient_output_config: HashMap<String, Vec<niri_ipc::OutputAction>>,
 
     pub event_loop: LoopHandle<'static, State>,
     pub scheduler: Scheduler<()>,
@@ -172,6 +187,7 @@ pub struct Niri {
     pub screencopy_state: ScreencopyManagerState,
     pub viewporter_state: ViewporterState,
     pub xdg_foreign_state: XdgForeignState,
+    pub activation_state: XdgActivationState,
     pub shm_state: ShmState,
     pub output_manager_state: OutputManagerState,
     pub dmabuf_state: DmabufState,
@@ -209,6 +225,7 @@ pub struct Niri {
     pub pointer_focus: PointerFocus,
     pub tablet_cursor_location: Option<Point<f64, Logical>>,
     pub gesture_swipe_3f_cumulative: Option<(f64, f64)>,
+    pub gesture_pinch_3f_active: bool,
     pub vertical_wheel_tracker: ScrollTracker,
     pub horizontal_wheel_tracker: ScrollTracker,
     pub mods_with_wheel_binds: HashSet<Modifiers>,
@@ -221,6 +238,7 @@ pub struct Niri {
     pub screenshot_ui: ScreenshotUi,
     pub config_error_notification: ConfigErrorNotification,
     pub hotkey_overlay: HotkeyOverlay,
+    pub window_switcher: WindowSwitcher,
     pub exit_confirm_dialog: Option<ExitConfirmDialog>,
 
     #[cfg(feature = "dbus")]
@@ -230,7 +248,6 @@ pub struct Niri {
 
     pub ipc_server: Option<IpcServer>,
     pub ipc_outputs_changed: bool,
-    pub ipc_focused_window: Arc<Mutex<Option<Window>>>,
 
     // Casts are dropped before PipeWire to prevent a double-free (yay).
     pub casts: Vec<Cast>,
@@ -462,6 +479,8 @@ impl State {
         foreign_toplevel::refresh(self);
         self.niri.refresh_window_rules();
         self.refresh_ipc_outputs();
+        self.ipc_refresh_layout();
+        self.ipc_refresh_keyboard_layout_index();
     }
 
     pub fn move_cursor(&mut self, location: Point<f64, Logical>) {
@@ -672,6 +691,11 @@ impl State {
             };
 
             let layout_focus = || {
+                // Windows in the overview are only picked, they don't receive keyboard input.
+                if mon.is_overview_open() {
+                    return Some(KeyboardFocus::Layout { surface: None });
+                }
+
                 self.niri
                     .layout
                     .focus()
@@ -716,8 +740,6 @@ impl State {
                 focus
             );
 
-            let mut newly_focused_window = None;
-
             // Tell the windows their new focus state for window rule purposes.
             if let KeyboardFocus::Layout {
                 surface: Some(surface),
@@ -733,12 +755,9 @@ impl State {
             {
                 if let Some((mapped, _)) = self.niri.layout.find_window_and_output_mut(surface) {
                     mapped.set_is_focused(true);
-                    newly_focused_window = Some(mapped.window.clone());
                 }
             }
 
-            *self.niri.ipc_focused_window.lock().unwrap() = newly_focused_window;
-
             if let Some(grab) = self.niri.popup_grab.as_mut() {
                 if Some(&grab.root) != focus.surface() {
                     trace!(
@@ -799,16 +818,17 @@ impl State {
         }
     }
 
-    pub fn reload_config(&mut self, path: PathBuf) {
+    pub fn reload_config(&mut self, path: PathBuf) -> Result<(), Vec<niri_ipc::ConfigDiagnostic>> {
         let _span = tracy_client::span!("State::reload_config");
 
         let mut config = match Config::load(&path) {
             Ok(config) => config,
             Err(err) => {
+                let diagnostics = niri_config::diagnostics(&*err);
                 warn!("{:?}", err.context("error loading config"));
                 self.niri.config_error_notification.show();
                 self.niri.queue_redraw_all();
-                return;
+                return Err(diagnostics);
             }
         };
 
@@ -862,8 +882,19 @@ impl State {
             libinput_config_changed = true;
         }
 
-        if config.outputs != old_config.outputs {
+        // Compare the outputs as written in the config file, since the current ones also have the
+        // transient changes applied.
+        if config.outputs != self.niri.file_output_config {
             output_config_changed = true;
+
+            retain_transient_output_config(
+                &mut self.niri.transient_output_config,
+                &self.niri.file_output_config,
+                &config.outputs,
+            );
+            self.niri.file_output_config = config.outputs.clone();
+        } else {
+            config.outputs = mem::take(&mut old_config.outputs);
         }
 
         if config.binds != old_config.binds {
@@ -889,6 +920,7 @@ impl State {
             if let Err(err) = keyboard.set_xkb_config(self, xkb.to_xkb_config()) {
                 warn!("error updating xkb config: {err:?}");
             }
+            self.ipc_keyboard_layouts_changed();
         }
 
         if libinput_config_changed {
@@ -899,47 +931,7 @@ impl State {
         }
 
         if output_config_changed {
-            let mut resized_outputs = vec![];
-            for output in self.niri.global_space.outputs() {
-                let name = output.name();
-                let config = self.niri.config.borrow_mut();
-                let config = config.outputs.iter().find(|o| o.name == name);
-
-                let scale = config.map(|c| c.scale).unwrap_or(1.);
-                let scale = scale.clamp(1., 10.).ceil() as i32;
-
-                let mut transform = config
-                    .map(|c| ipc_transform_to_smithay(c.transform))
-                    .unwrap_or(Transform::Normal);
-                // FIXME: fix winit damage on other transforms.
-                if name == "winit" {
-                    transform = Transform::Flipped180;
-                }
-
-                if output.current_scale().integer_scale() != scale
-                    || output.current_transform() != transform
-                {
-                    output.change_current_state(
-                        None,
-                        Some(transform),
-                        Some(output::Scale::Integer(scale)),
-                        None,
-                    );
-                    self.niri.ipc_outputs_changed = true;
-                    resized_outputs.push(output.clone());
-                }
-            }
-            for output in resized_outputs {
-                self.niri.output_resized(&output);
-            }
-
-            self.backend.on_output_config_changed(&mut self.niri);
-
-            self.niri.reposition_outputs(None);
-
-            if let Some(touch) = self.niri.seat.get_touch() {
-                touch.cancel(self);
-            }
+            self.reload_output_config();
         }
 
         if window_rules_changed {
@@ -972,6 +964,74 @@ impl State {
         // clients will use the new xdg-decoration setting.
 
         self.niri.queue_redraw_all();
+
+        Ok(())
+    }
+
+    pub fn reload_output_config(&mut self) {
+        let outputs = merge_transient_output_config(
+            &self.niri.file_output_config,
+            &self.niri.transient_output_config,
+        );
+        self.niri.config.borrow_mut().outputs = outputs;
+
+        let mut resized_outputs = vec![];
+        for output in self.niri.global_space.outputs() {
+            let name = output.name();
+            let config = self.niri.config.borrow_mut();
+            let config = config.outputs.iter().find(|o| o.name == name);
+
+            let scale = config.map(|c| c.scale).unwrap_or(1.);
+            let scale = scale.clamp(1., 10.).ceil() as i32;
+
+            let mut transform = config
+                .map(|c| ipc_transform_to_smithay(c.transform))
+                .unwrap_or(Transform::Normal);
+            // FIXME: fix winit damage on other transforms.
+            if name == "winit" {
+                transform = Transform::Flipped180;
+            }
+
+            if output.current_scale().integer_scale() != scale
+                || output.current_transform() != transform
+            {
+                output.change_current_state(
+                    None,
+                    Some(transform),
+                    Some(output::Scale::Integer(scale)),
+                    None,
+                );
+                self.niri.ipc_outputs_changed = true;
+                resized_outputs.push(output.clone());
+            }
+        }
+        for output in resized_outputs {
+            self.niri.output_resized(&output);
+        }
+
+        self.backend.on_output_config_changed(&mut self.niri);
+
+        self.niri.reposition_outputs(None);
+
+        if let Some(touch) = self.niri.seat.get_touch() {
+            touch.cancel(self);
+        }
+    }
+
+    /// Applies an output configuration change without touching the config file.
+    ///
+    /// The change stays on top of the config file until niri restarts or the section of this
+    /// output in the config file changes.
+    pub fn apply_transient_output_config(&mut self, name: &str, action: niri_ipc::OutputAction) {
+        let actions = self
+            .niri
+            .transient_output_config
+            .entry(String::from(name))
+            .or_default();
+        push_transient_output_action(actions, action);
+
+        self.reload_output_config();
+        self.niri.queue_redraw_all();
     }
 
     pub fn refresh_ipc_outputs(&mut self) {
@@ -982,7 +1042,9 @@ impl State {
 
         let _span = tracy_client::span!("State::refresh_ipc_outputs");
 
-        for (name, ipc_output) in self.backend.ipc_outputs().lock().unwrap().iter_mut() {
+        let ipc_outputs = self.backend.ipc_outputs();
+        let mut ipc_outputs = ipc_outputs.lock().unwrap();
+        for (name, ipc_output) in ipc_outputs.iter_mut() {
             let logical = self
                 .niri
                 .global_space
@@ -992,6 +1054,13 @@ impl State {
             ipc_output.logical = logical;
         }
 
+        if let Some(server) = &self.niri.ipc_server {
+            server.send_event(niri_ipc::Event::OutputsChanged {
+                outputs: ipc_outputs.clone(),
+            });
+        }
+        drop(ipc_outputs);
+
         #[cfg(feature = "dbus")]
         self.niri.on_ipc_outputs_changed();
     }
@@ -1119,6 +1188,7 @@ impl Niri {
         let config_ = config.borrow();
 
         let layout = Layout::new(&config_);
+        let file_output_config = config_.outputs.clone();
 
         let compositor_state = CompositorState::new_v6::<State>(&display_handle);
         let xdg_shell_state = XdgShellState::new_with_capabilities::<State>(
@@ -1195,6 +1265,7 @@ impl Niri {
         });
         let viewporter_state = ViewporterState::new::<State>(&display_handle);
         let xdg_foreign_state = XdgForeignState::new::<State>(&display_handle);
+        let activation_state = XdgActivationState::new::<State>(&display_handle);
 
         let is_tty = matches!(backend, Backend::Tty(_));
         let gamma_control_manager_state =
@@ -1309,6 +1380,9 @@ impl Niri {
         drop(config_);
         Self {
             config,
+            config_path: None,
+            file_output_config,
+            transient_output_config: HashMap::new(),
 
             event_loop,
             scheduler,
@@ -1338,6 +1412,7 @@ impl Niri {
             screencopy_state,
             viewporter_state,
             xdg_foreign_state,
+            activation_state,
             text_input_state,
             input_method_state,
             virtual_keyboard_state,
@@ -1373,6 +1448,7 @@ impl Niri {
             pointer_focus: PointerFocus::default(),
             tablet_cursor_location: None,
             gesture_swipe_3f_cumulative: None,
+            gesture_pinch_3f_active: false,
             vertical_wheel_tracker: ScrollTracker::new(120),
             horizontal_wheel_tracker: ScrollTracker::new(120),
             mods_with_wheel_binds,
@@ -1387,6 +1463,7 @@ impl Niri {
             screenshot_ui,
             config_error_notification,
             hotkey_overlay,
+            window_switcher: WindowSwitcher::new(),
             exit_confirm_dialog,
 
             #[cfg(feature = "dbus")]
@@ -1396,7 +1473,6 @@ impl Niri {
 
             ipc_server,
             ipc_outputs_changed: false,
-            ipc_focused_window: Arc::new(Mutex::new(None)),
 
             pipewire,
             casts: vec![],
@@ -1882,7 +1958,11 @@ impl Niri {
 
     pub fn output_left(&self) -> Option<Output> {
         let active = self.layout.active_output()?;
-        let active_geo = self.global_space.output_geometry(active).unwrap();
+        self.output_left_of(active)
+    }
+
+    pub fn output_left_of(&self, current: &Output) -> Option<Output> {
+        let active_geo = self.global_space.output_geometry(current).unwrap();
         let extended_geo = Rectangle::from_loc_and_size(
             (i32::MIN / 2, active_geo.loc.y),
             (i32::MAX, active_geo.size.h),
@@ -1899,7 +1979,11 @@ impl Niri {
 
     pub fn output_right(&self) -> Option<Output> {
         let active = self.layout.active_output()?;
-        let active_geo = self.global_space.output_geometry(active).unwrap();
+        self.output_right_of(active)
+    }
+
+    pub fn output_right_of(&self, current: &Output) -> Option<Output> {
+        let active_geo = self.global_space.output_geometry(current).unwrap();
         let extended_geo = Rectangle::from_loc_and_size(
             (i32::MIN / 2, active_geo.loc.y),
             (i32::MAX, active_geo.size.h),
@@ -1916,7 +2000,11 @@ impl Niri {
 
     pub fn output_up(&self) -> Option<Output> {
         let active = self.layout.active_output()?;
-        let active_geo = self.global_space.output_geometry(active).unwrap();
+        self.output_up_of(active)
+    }
+
+    pub fn output_up_of(&self, current: &Output) -> Option<Output> {
+        let active_geo = self.global_space.output_geometry(current).unwrap();
         let extended_geo = Rectangle::from_loc_and_size(
             (active_geo.loc.x, i32::MIN / 2),
             (active_geo.size.w, i32::MAX),
@@ -1933,7 +2021,11 @@ impl Niri {
 
     pub fn output_down(&self) -> Option<Output> {
         let active = self.layout.active_output()?;
-        let active_geo = self.global_space.output_geometry(active).unwrap();
+        self.output_down_of(active)
+    }
+
+    pub fn output_down_of(&self, current: &Output) -> Option<Output> {
+        let active_geo = self.global_space.output_geometry(current).unwrap();
         let extended_geo = Rectangle::from_loc_and_size(
             (active_geo.loc.x, i32::MIN / 2),
             (active_geo.size.w, i32::MAX),
@@ -1948,6 +2040,45 @@ impl Niri {
             .cloned()
     }
 
+    /// Resolves a workspace reference to the workspace index and, for named workspaces, to the
+    /// output that the workspace is on.
+    ///
+    /// Indices are relative to the current output, so they come back without an output.
+    pub fn find_output_and_workspace_index(
+        &self,
+        workspace_reference: WorkspaceReference,
+    ) -> Option<(Option<Output>, usize)> {
+        match workspace_reference {
+            WorkspaceReference::Index(index) => Some((None, index.saturating_sub(1) as usize)),
+            WorkspaceReference::Name(name) => {
+                let (mon, index, _) = self.layout.find_workspace_by_name(&name)?;
+                Some((Some(mon?.output.clone()), index))
+            }
+        }
+    }
+
+    /// Finds a mapped window by its IPC id.
+    pub fn find_window_by_id(&self, id: u64) -> Option<(Window, Option<Output>)> {
+        let mut rv = None;
+        self.layout.with_windows(|mapped, output| {
+            if u64::from(mapped.id().get()) == id {
+                rv = Some((mapped.window.clone(), output.cloned()));
+            }
+        });
+        rv
+    }
+
+    /// Returns the ids of the windows on outputs, most recently focused first.
+    pub fn windows_by_recent_use(&self) -> Vec<MappedId> {
+        let mut windows = Vec::new();
+        self.layout.with_windows(|mapped, output| {
+            if output.is_some() {
+                windows.push((mapped.focus_timestamp(), mapped.id()));
+            }
+        });
+        order_by_recent_use(windows)
+    }
+
     pub fn output_for_tablet(&self) -> Option<&Output> {
         let config = self.config.borrow();
         let map_to_output = config.input.tablet.map_to_output.as_ref();
@@ -2371,10 +2502,23 @@ impl Niri {
             elements.push(element.into());
         }
 
+        // Then the window switcher.
+        elements.extend(
+            self.window_switcher
+                .render(renderer, output, &self.layout, target)
+                .into_iter()
+                .map(OutputRenderElements::from),
+        );
+
         // Get monitor elements.
         let mon = self.layout.monitor_for_output(output).unwrap();
         let monitor_elements = mon.render_elements(renderer, target);
 
+        // The interactively moved window goes above the rest of the layout.
+        let move_elements = self
+            .layout
+            .render_interactive_move_for_output(renderer, output, target);
+
         // Get layer-shell elements.
         let layer_map = layer_map_for_output(output);
         let mut extend_from_layer = |elements: &mut Vec<OutputRenderElements<R>>, layer| {
@@ -2404,10 +2548,12 @@ impl Niri {
 
         // Then the regular monitor elements and the top layer in varying order.
         if mon.render_above_top_layer() {
+            elements.extend(move_elements.into_iter().map(OutputRenderElements::from));
             elements.extend(monitor_elements.into_iter().map(OutputRenderElements::from));
             extend_from_layer(&mut elements, Layer::Top);
         } else {
             extend_from_layer(&mut elements, Layer::Top);
+            elements.extend(move_elements.into_iter().map(OutputRenderElements::from));
             elements.extend(monitor_elements.into_iter().map(OutputRenderElements::from));
         }
 
@@ -3550,6 +3696,93 @@ impl Niri {
     }
 }
 
+/// Returns the output sections of the config file with the transient changes applied on top.
+fn merge_transient_output_config(
+    file: &[niri_config::Output],
+    transient: &HashMap<String, Vec<niri_ipc::OutputAction>>,
+) -> Vec<niri_config::Output> {
+    let mut outputs = file.to_vec();
+
+    for (name, actions) in transient {
+        let idx = match outputs.iter().position(|o| &o.name == name) {
+            Some(idx) => idx,
+            None => {
+                outputs.push(niri_config::Output {
+                    name: name.clone(),
+                    ..Default::default()
+                });
+                outputs.len() - 1
+            }
+        };
+
+        for action in actions {
+            apply_output_action(&mut outputs[idx], action.clone());
+        }
+    }
+
+    outputs
+}
+
+fn apply_output_action(config: &mut niri_config::Output, action: niri_ipc::OutputAction) {
+    match action {
+        niri_ipc::OutputAction::Off => config.off = true,
+        niri_ipc::OutputAction::On => config.off = false,
+        niri_ipc::OutputAction::Mode { mode } => {
+            config.mode = match mode {
+                niri_ipc::ModeToSet::Automatic => None,
+                niri_ipc::ModeToSet::Specific(mode) => Some(niri_config::Mode {
+                    width: mode.width,
+                    height: mode.height,
+                    refresh: mode.refresh,
+                }),
+            }
+        }
+        niri_ipc::OutputAction::Scale { scale } => config.scale = scale,
+        niri_ipc::OutputAction::Transform { transform } => config.transform = transform,
+        niri_ipc::OutputAction::Position { position } => {
+            config.position = match position {
+                niri_ipc::PositionToSet::Automatic => None,
+                niri_ipc::PositionToSet::Specific(position) => Some(niri_config::Position {
+                    x: position.x,
+                    y: position.y,
+                }),
+            }
+        }
+    }
+}
+
+/// Adds a transient output change, replacing an older change of the same setting.
+fn push_transient_output_action(
+    actions: &mut Vec<niri_ipc::OutputAction>,
+    action: niri_ipc::OutputAction,
+) {
+    actions.retain(|existing| !is_same_output_setting(existing, &action));
+    actions.push(action);
+}
+
+/// Drops the transient changes of the outputs whose sections in the config file changed.
+fn retain_transient_output_config(
+    transient: &mut HashMap<String, Vec<niri_ipc::OutputAction>>,
+    old_file: &[niri_config::Output],
+    new_file: &[niri_config::Output],
+) {
+    transient.retain(|name, _| {
+        let old = old_file.iter().find(|o| &o.name == name);
+        let new = new_file.iter().find(|o| &o.name == name);
+        old == new
+    });
+}
+
+/// Returns whether both actions change the same output setting.
+fn is_same_output_setting(a: &niri_ipc::OutputAction, b: &niri_ipc::OutputAction) -> bool {
+    use niri_ipc::OutputAction::{Off, On};
+
+    match (a, b) {
+        (On | Off, On | Off) => true,
+        _ => mem::discriminant(a) == mem::discriminant(b),
+    }
+}
+
 pub struct ClientState {
     pub compositor_state: CompositorClientState,
     pub can_view_decoration_globals: bool,
@@ -3565,11 +3798,128 @@ impl ClientData for ClientState {
 niri_render_elements! {
     OutputRenderElements<R> => {
         Monitor = MonitorRenderElement<R>,
+        InteractiveMove = InteractiveMoveRenderElement<R>,
         Wayland = WaylandSurfaceRenderElement<R>,
         NamedPointer = MemoryRenderBufferRenderElement<R>,
         SolidColor = SolidColorRenderElement,
         ScreenshotUi = ScreenshotUiRenderElement,
+        WindowSwitcher = WindowSwitcherRenderElement<R>,
         // Used for the CPU-rendered panels.
         RelocatedMemoryBuffer = RelocateRenderElement<MemoryRenderBufferRenderElement<R>>,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use niri_ipc::{ConfiguredPosition, OutputAction, PositionToSet, Transform};
+
+    use super::*;
+
+    fn output(name: &str, scale: f64) -> niri_config::Output {
+        niri_config::Output {
+            name: String::from(name),
+            scale,
+            ..Default::default()
+        }
+    }
+
+    fn transient(changes: &[(&str, OutputAction)]) -> HashMap<String, Vec<OutputAction>> {
+        let mut transient = HashMap::<_, Vec<_>>::new();
+        for (name, action) in changes {
+            let actions = transient.entry(String::from(*name)).or_default();
+            push_transient_output_action(actions, action.clone());
+        }
+        transient
+    }
+
+    #[test]
+    fn transient_change_overrides_config_file() {
+        let file = [output("eDP-1", 2.), output("HDMI-A-1", 1.5)];
+        let transient = transient(&[("eDP-1", OutputAction::Scale { scale: 1. })]);
+
+        let merged = merge_transient_output_config(&file, &transient);
+        assert_eq!(merged, [output("eDP-1", 1.), output("HDMI-A-1", 1.5)]);
+    }
+
+    #[test]
+    fn transient_change_adds_missing_output() {
+        let file = [output("eDP-1", 2.)];
+        let transient = transient(&[("HDMI-A-1", OutputAction::Off)]);
+
+        let merged = merge_transient_output_config(&file, &transient);
+        assert_eq!(
+            merged,
+            [
+                output("eDP-1", 2.),
+                niri_config::Output {
+                    name: String::from("HDMI-A-1"),
+                    off: true,
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn transient_on_and_off_replace_each_other() {
+        let transient = transient(&[("eDP-1", OutputAction::Off), ("eDP-1", OutputAction::On)]);
+        assert_eq!(transient["eDP-1"], [OutputAction::On]);
+
+        let mut file = [output("eDP-1", 1.)];
+        file[0].off = true;
+        let merged = merge_transient_output_config(&file, &transient);
+        assert!(!merged[0].off);
+    }
+
+    #[test]
+    fn transient_change_replaces_same_setting() {
+        let position = PositionToSet::Specific(ConfiguredPosition { x: 10, y: 20 });
+        let transient = transient(&[
+            ("eDP-1", OutputAction::Scale { scale: 1.5 }),
+            ("eDP-1", OutputAction::Position { position }),
+            (
+                "eDP-1",
+                OutputAction::Transform {
+                    transform: Transform::_90,
+                },
+            ),
+            ("eDP-1", OutputAction::Scale { scale: 3. }),
+        ]);
+        assert_eq!(
+            transient["eDP-1"],
+            [
+                OutputAction::Position { position },
+                OutputAction::Transform {
+                    transform: Transform::_90
+                },
+                OutputAction::Scale { scale: 3. },
+            ]
+        );
+
+        let merged = merge_transient_output_config(&[output("eDP-1", 1.)], &transient);
+        assert_eq!(merged[0].scale, 3.);
+        assert_eq!(merged[0].transform, Transform::_90);
+        assert_eq!(
+            merged[0].position,
+            Some(niri_config::Position { x: 10, y: 20 })
+        );
+    }
+
+    #[test]
+    fn config_reload_keeps_transient_changes() {
+        let old_file = [output("eDP-1", 2.), output("HDMI-A-1", 1.)];
+        let mut transient = transient(&[
+            ("eDP-1", OutputAction::Scale { scale: 1. }),
+            ("HDMI-A-1", OutputAction::Off),
+        ]);
+
+        // Only the HDMI-A-1 section changed, so only its transient changes are dropped.
+        let new_file = [output("eDP-1", 2.), output("HDMI-A-1", 2.)];
+        retain_transient_output_config(&mut transient, &old_file, &new_file);
+        assert!(transient.contains_key("eDP-1"));
+        assert!(!transient.contains_key("HDMI-A-1"));
+
+        let merged = merge_transient_output_config(&new_file, &transient);
+        assert_eq!(merged, [output("eDP-1", 1.), output("HDMI-A-1", 2.)]);
+    }
+}
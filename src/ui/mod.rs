@@ -2,3 +2,4 @@ pub mod config_error_notification;
 pub mod exit_confirm_dialog;
 pub mod hotkey_overlay;
 pub mod screenshot_ui;
+pub mod window_switcher;
@@ -0,0 +1,320 @@
+use std::cell::RefCell;
+use std::cmp::{max, min};
+use std::mem;
+use std::time::Duration;
+
+use niri_config::Modifiers;
+use smithay::backend::renderer::element::solid::{SolidColorBuffer, SolidColorRenderElement};
+use smithay::backend::renderer::element::utils::{
+    CropRenderElement, Relocate, RelocateRenderElement, RescaleRenderElement,
+};
+use smithay::backend::renderer::element::Kind;
+use smithay::output::Output;
+use smithay::utils::{Logical, Point, Rectangle, Scale, Size};
+
+use crate::layout::{Layout, LayoutElement as _, LayoutElementRenderElement};
+use crate::niri_render_elements;
+use crate::render_helpers::renderer::NiriRenderer;
+use crate::render_helpers::RenderTarget;
+use crate::utils::output_size;
+use crate::window::mapped::MappedId;
+use crate::window::Mapped;
+
+/// Size of the box that each window thumbnail is fit into.
+const THUMBNAIL_WIDTH: i32 = 256;
+const THUMBNAIL_HEIGHT: i32 = 160;
+
+const PADDING: i32 = 16;
+const MARGIN: i32 = PADDING * 2;
+const BACKGROUND_COLOR: [f32; 4] = [0.1, 0.1, 0.1, 0.9];
+const SELECTION_COLOR: [f32; 4] = [0.5, 0.5, 0.5, 0.9];
+
+niri_render_elements! {
+    WindowSwitcherRenderElement<R> => {
+        Thumbnail = RelocateRenderElement<RescaleRenderElement<
+            CropRenderElement<LayoutElementRenderElement<R>>
+        >>,
+        SolidColor = SolidColorRenderElement,
+    }
+}
+
+/// Switcher between windows in the order of recent use.
+///
+/// It stays open while the modifiers that opened it are held, and focuses the selected window
+/// once they are released.
+pub struct WindowSwitcher {
+    is_open: bool,
+    /// Windows to pick from, most recently used first.
+    windows: Vec<MappedId>,
+    selected_idx: usize,
+    /// Modifiers held when the switcher was opened.
+    mods: Modifiers,
+    background: RefCell<SolidColorBuffer>,
+    selection: RefCell<SolidColorBuffer>,
+}
+
+impl WindowSwitcher {
+    pub fn new() -> Self {
+        Self {
+            is_open: false,
+            windows: vec![],
+            selected_idx: 0,
+            mods: Modifiers::empty(),
+            background: RefCell::new(SolidColorBuffer::default()),
+            selection: RefCell::new(SolidColorBuffer::default()),
+        }
+    }
+
+    /// Opens the switcher with the windows to pick from and the modifiers currently held.
+    pub fn open(&mut self, windows: Vec<MappedId>, selected_idx: usize, mods: Modifiers) -> bool {
+        if self.is_open || windows.is_empty() {
+            return false;
+        }
+
+        self.is_open = true;
+        self.selected_idx = min(selected_idx, windows.len() - 1);
+        self.windows = windows;
+        self.mods = mods;
+        true
+    }
+
+    /// Closes the switcher, returning the selected window.
+    pub fn close(&mut self) -> Option<MappedId> {
+        if !self.is_open {
+            return None;
+        }
+
+        self.is_open = false;
+        let windows = mem::take(&mut self.windows);
+        windows.get(self.selected_idx).copied()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn select_next(&mut self) {
+        if self.windows.is_empty() {
+            return;
+        }
+
+        self.selected_idx = (self.selected_idx + 1) % self.windows.len();
+    }
+
+    pub fn select_previous(&mut self) {
+        if self.windows.is_empty() {
+            return;
+        }
+
+        self.selected_idx = (self.selected_idx + self.windows.len() - 1) % self.windows.len();
+    }
+
+    /// Returns whether the selection should be committed with these modifiers held.
+    ///
+    /// This happens once all modifiers that opened the switcher are released. When it was opened
+    /// without any modifiers, it waits for an explicit confirmation instead.
+    pub fn should_confirm(&self, mods: Modifiers) -> bool {
+        self.is_open && !self.mods.is_empty() && !mods.intersects(self.mods)
+    }
+
+    pub fn render<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        output: &Output,
+        layout: &Layout<Mapped>,
+        target: RenderTarget,
+    ) -> Vec<WindowSwitcherRenderElement<R>> {
+        if !self.is_open || layout.active_output() != Some(output) {
+            return vec![];
+        }
+
+        let scale = Scale::from(output.current_scale().fractional_scale());
+        let output_size = output_size(output);
+        let count = self.windows.len() as i32;
+
+        // Shrink the thumbnails if they don't fit on the output.
+        let available = output_size.w - MARGIN * 2 - PADDING * (count + 1);
+        let thumb_w = max(min(THUMBNAIL_WIDTH, available / count), 1);
+        let thumb_h = max(thumb_w * THUMBNAIL_HEIGHT / THUMBNAIL_WIDTH, 1);
+
+        let panel_size: Size<i32, Logical> = Size::from((
+            thumb_w * count + PADDING * (count + 1),
+            thumb_h + PADDING * 2,
+        ));
+        let panel_loc: Point<i32, Logical> = Point::from((
+            (output_size.w - panel_size.w) / 2,
+            (output_size.h - panel_size.h) / 2,
+        ));
+        let thumb_loc = |idx: usize| {
+            panel_loc + Point::from((PADDING + (thumb_w + PADDING) * idx as i32, PADDING))
+        };
+
+        let mut rv = vec![];
+
+        layout.with_windows(|mapped, _| {
+            let Some(idx) = self.windows.iter().position(|id| *id == mapped.id()) else {
+                return;
+            };
+
+            let size = mapped.size();
+            if size.w <= 0 || size.h <= 0 {
+                return;
+            }
+
+            // Fit the window into its box, but never scale it up.
+            let zoom = f64::min(
+                f64::min(
+                    f64::from(thumb_w) / f64::from(size.w),
+                    f64::from(thumb_h) / f64::from(size.h),
+                ),
+                1.,
+            );
+            let zoomed = size.to_f64().upscale(zoom);
+            let loc = thumb_loc(idx).to_f64()
+                + Point::from((
+                    (f64::from(thumb_w) - zoomed.w) / 2.,
+                    (f64::from(thumb_h) - zoomed.h) / 2.,
+                ));
+
+            // Cut off the popups and the parts of the window outside its geometry.
+            let crop = Rectangle::from_loc_and_size((0, 0), size.to_physical_precise_round(scale));
+
+            let elements = mapped.render(renderer, Point::from((0, 0)), scale, 1., target);
+            rv.extend(elements.into_iter().filter_map(|elem| {
+                let elem = CropRenderElement::from_element(elem, scale, crop)?;
+                let elem = RescaleRenderElement::from_element(elem, Point::from((0, 0)), zoom);
+                let elem = RelocateRenderElement::from_element(
+                    elem,
+                    loc.to_physical_precise_round(scale),
+                    Relocate::Relative,
+                );
+                Some(WindowSwitcherRenderElement::from(elem))
+            }));
+        });
+
+        let mut selection = self.selection.borrow_mut();
+        selection.update((thumb_w + PADDING, thumb_h + PADDING), SELECTION_COLOR);
+        let selection_loc = thumb_loc(self.selected_idx) - Point::from((PADDING / 2, PADDING / 2));
+        rv.push(
+            SolidColorRenderElement::from_buffer(
+                &selection,
+                selection_loc.to_physical_precise_round(scale),
+                scale,
+                1.,
+                Kind::Unspecified,
+            )
+            .into(),
+        );
+
+        let mut background = self.background.borrow_mut();
+        background.update(panel_size, BACKGROUND_COLOR);
+        rv.push(
+            SolidColorRenderElement::from_buffer(
+                &background,
+                panel_loc.to_physical_precise_round(scale),
+                scale,
+                1.,
+                Kind::Unspecified,
+            )
+            .into(),
+        );
+
+        rv
+    }
+}
+
+impl Default for WindowSwitcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Orders windows by their last focus time, most recently focused first.
+///
+/// Windows that were never focused come last, in their original order.
+pub fn order_by_recent_use<T>(mut windows: Vec<(Option<Duration>, T)>) -> Vec<T> {
+    windows.sort_by(|(a, _), (b, _)| b.cmp(a));
+    windows.into_iter().map(|(_, window)| window).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(count: usize) -> Vec<MappedId> {
+        (0..count).map(|_| MappedId::next()).collect()
+    }
+
+    #[test]
+    fn selection_wraps_around() {
+        let windows = ids(3);
+        let mut switcher = WindowSwitcher::new();
+        assert!(switcher.open(windows.clone(), 0, Modifiers::ALT));
+
+        switcher.select_previous();
+        assert_eq!(switcher.selected_idx, 2);
+        switcher.select_next();
+        assert_eq!(switcher.selected_idx, 0);
+        switcher.select_next();
+        switcher.select_next();
+        switcher.select_next();
+        assert_eq!(switcher.selected_idx, 0);
+
+        switcher.select_previous();
+        assert_eq!(switcher.close(), Some(windows[2]));
+    }
+
+    #[test]
+    fn open_without_windows() {
+        let mut switcher = WindowSwitcher::new();
+        assert!(!switcher.open(vec![], 0, Modifiers::ALT));
+        assert!(!switcher.is_open());
+        assert_eq!(switcher.close(), None);
+    }
+
+    #[test]
+    fn open_clamps_selection() {
+        let windows = ids(2);
+        let mut switcher = WindowSwitcher::new();
+        assert!(switcher.open(windows.clone(), 5, Modifiers::ALT));
+        assert_eq!(switcher.selected_idx, 1);
+
+        // Opening again while open does nothing.
+        assert!(!switcher.open(ids(3), 0, Modifiers::ALT));
+        assert_eq!(switcher.close(), Some(windows[1]));
+        assert!(!switcher.is_open());
+    }
+
+    #[test]
+    fn confirm_on_modifier_release() {
+        let mut switcher = WindowSwitcher::new();
+        assert!(!switcher.should_confirm(Modifiers::empty()));
+
+        switcher.open(ids(2), 0, Modifiers::ALT | Modifiers::SHIFT);
+        assert!(!switcher.should_confirm(Modifiers::ALT | Modifiers::SHIFT));
+        assert!(!switcher.should_confirm(Modifiers::ALT));
+        assert!(!switcher.should_confirm(Modifiers::SHIFT | Modifiers::CTRL));
+        assert!(switcher.should_confirm(Modifiers::CTRL));
+        assert!(switcher.should_confirm(Modifiers::empty()));
+    }
+
+    #[test]
+    fn no_confirm_when_opened_without_modifiers() {
+        let mut switcher = WindowSwitcher::new();
+        switcher.open(ids(2), 0, Modifiers::empty());
+        assert!(!switcher.should_confirm(Modifiers::empty()));
+        assert!(!switcher.should_confirm(Modifiers::ALT));
+    }
+
+    #[test]
+    fn recent_use_order() {
+        let windows = vec![
+            (None, 1),
+            (Some(Duration::from_secs(1)), 2),
+            (None, 3),
+            (Some(Duration::from_secs(3)), 4),
+            (Some(Duration::from_secs(2)), 5),
+        ];
+        assert_eq!(order_by_recent_use(windows), [4, 5, 2, 1, 3]);
+    }
+}
@@ -7,11 +7,13 @@ use std::sync::atomic::AtomicBool;
 use std::time::Duration;
 
 use anyhow::{ensure, Context};
+use bitflags::bitflags;
 use directories::UserDirs;
 use git_version::git_version;
 use niri_config::Config;
 use smithay::output::Output;
 use smithay::reexports::rustix::time::{clock_gettime, ClockId};
+use smithay::reexports::wayland_protocols::xdg::shell::server::xdg_toplevel;
 use smithay::utils::{Logical, Point, Rectangle, Size, Transform};
 
 pub mod id;
@@ -20,6 +22,63 @@ pub mod watcher;
 
 pub static IS_SYSTEMD_SERVICE: AtomicBool = AtomicBool::new(false);
 
+bitflags! {
+    /// Edges of a window being interactively resized.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ResizeEdge: u32 {
+        const TOP = 0b0001;
+        const BOTTOM = 0b0010;
+        const LEFT = 0b0100;
+        const RIGHT = 0b1000;
+
+        const TOP_LEFT = Self::TOP.bits() | Self::LEFT.bits();
+        const BOTTOM_LEFT = Self::BOTTOM.bits() | Self::LEFT.bits();
+
+        const TOP_RIGHT = Self::TOP.bits() | Self::RIGHT.bits();
+        const BOTTOM_RIGHT = Self::BOTTOM.bits() | Self::RIGHT.bits();
+
+        const LEFT_RIGHT = Self::LEFT.bits() | Self::RIGHT.bits();
+        const TOP_BOTTOM = Self::TOP.bits() | Self::BOTTOM.bits();
+    }
+}
+
+impl From<xdg_toplevel::ResizeEdge> for ResizeEdge {
+    fn from(edge: xdg_toplevel::ResizeEdge) -> Self {
+        // The protocol values match our bits.
+        Self::from_bits_truncate(u32::from(edge))
+    }
+}
+
+impl ResizeEdge {
+    /// Picks the edges to resize for a pointer at the given position within the rectangle.
+    ///
+    /// The rectangle is split into thirds along each axis; the middle resizes the bottom right.
+    pub fn for_pos_in_rect(pos: Point<f64, Logical>, rect: Rectangle<i32, Logical>) -> Self {
+        let rect = rect.to_f64();
+        let pos = pos - rect.loc;
+
+        let mut edges = ResizeEdge::empty();
+
+        if pos.x < rect.size.w / 3. {
+            edges |= ResizeEdge::LEFT;
+        } else if pos.x > rect.size.w * 2. / 3. {
+            edges |= ResizeEdge::RIGHT;
+        }
+
+        if pos.y < rect.size.h / 3. {
+            edges |= ResizeEdge::TOP;
+        } else if pos.y > rect.size.h * 2. / 3. {
+            edges |= ResizeEdge::BOTTOM;
+        }
+
+        if edges.is_empty() {
+            edges = ResizeEdge::BOTTOM_RIGHT;
+        }
+
+        edges
+    }
+}
+
 pub fn version() -> String {
     format!(
         "{} ({})",
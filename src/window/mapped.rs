@@ -1,5 +1,6 @@
 use std::cell::RefCell;
 use std::cmp::{max, min};
+use std::time::Duration;
 
 use niri_config::{BlockOutFrom, WindowRule};
 use smithay::backend::renderer::element::solid::{SolidColorBuffer, SolidColorRenderElement};
@@ -19,11 +20,16 @@ use crate::layout::{LayoutElement, LayoutElementRenderElement};
 use crate::niri::WindowOffscreenId;
 use crate::render_helpers::renderer::NiriRenderer;
 use crate::render_helpers::RenderTarget;
+use crate::utils::get_monotonic_time;
+use crate::utils::id::IdCounter;
 
 #[derive(Debug)]
 pub struct Mapped {
     pub window: Window,
 
+    /// Unique ID of this `Mapped`.
+    id: MappedId,
+
     /// Up-to-date rules.
     rules: ResolvedWindowRules,
 
@@ -36,17 +42,45 @@ pub struct Mapped {
     /// Whether this window has the keyboard focus.
     is_focused: bool,
 
+    /// When this window last received the keyboard focus.
+    ///
+    /// Used to order windows by recent use.
+    focus_timestamp: Option<Duration>,
+
+    /// Whether this window requested attention without being allowed to take the focus.
+    ///
+    /// Reset when the window receives the keyboard focus.
+    is_urgent: bool,
+
     /// Buffer to draw instead of the window when it should be blocked out.
     block_out_buffer: RefCell<SolidColorBuffer>,
 }
 
+static MAPPED_ID_COUNTER: IdCounter = IdCounter::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MappedId(u32);
+
+impl MappedId {
+    pub fn next() -> MappedId {
+        MappedId(MAPPED_ID_COUNTER.next())
+    }
+
+    pub fn get(self) -> u32 {
+        self.0
+    }
+}
+
 impl Mapped {
     pub fn new(window: Window, rules: ResolvedWindowRules) -> Self {
         Self {
             window,
+            id: MappedId::next(),
             rules,
             need_to_recompute_rules: false,
             is_focused: false,
+            focus_timestamp: None,
+            is_urgent: false,
             block_out_buffer: RefCell::new(SolidColorBuffer::new((0, 0), [0., 0., 0., 1.])),
         }
     }
@@ -55,6 +89,10 @@ impl Mapped {
         self.window.toplevel().expect("no X11 support")
     }
 
+    pub fn id(&self) -> MappedId {
+        self.id
+    }
+
     /// Recomputes the resolved window rules and returns whether they changed.
     pub fn recompute_window_rules(&mut self, rules: &[WindowRule]) -> bool {
         self.need_to_recompute_rules = false;
@@ -87,6 +125,24 @@ impl Mapped {
 
         self.is_focused = is_focused;
         self.need_to_recompute_rules = true;
+
+        if is_focused {
+            self.is_urgent = false;
+            self.focus_timestamp = Some(get_monotonic_time());
+        }
+    }
+
+    pub fn focus_timestamp(&self) -> Option<Duration> {
+        self.focus_timestamp
+    }
+
+    pub fn is_urgent(&self) -> bool {
+        self.is_urgent
+    }
+
+    pub fn set_urgent(&mut self, is_urgent: bool) {
+        // A focused window already has the user's attention.
+        self.is_urgent = is_urgent && !self.is_focused;
     }
 }
 
@@ -1,4 +1,4 @@
-use niri_config::{BlockOutFrom, Match, WindowRule};
+use niri_config::{BlockOutFrom, Match, WindowRule, WorkspaceReference};
 use smithay::reexports::wayland_protocols::xdg::shell::server::xdg_toplevel;
 use smithay::wayland::compositor::with_states;
 use smithay::wayland::shell::xdg::{
@@ -33,12 +33,18 @@ pub struct ResolvedWindowRules {
     /// Output to open this window on.
     pub open_on_output: Option<String>,
 
+    /// Workspace to open this window on.
+    pub open_on_workspace: Option<WorkspaceReference>,
+
     /// Whether the window should open full-width.
     pub open_maximized: Option<bool>,
 
     /// Whether the window should open fullscreen.
     pub open_fullscreen: Option<bool>,
 
+    /// Whether the window should open in the floating layout.
+    pub open_floating: Option<bool>,
+
     /// Extra bound on the minimum window width.
     pub min_width: Option<u16>,
     /// Extra bound on the minimum window height.
@@ -81,8 +87,10 @@ impl ResolvedWindowRules {
         Self {
             default_width: None,
             open_on_output: None,
+            open_on_workspace: None,
             open_maximized: None,
             open_fullscreen: None,
+            open_floating: None,
             min_width: None,
             min_height: None,
             max_width: None,
@@ -113,6 +121,7 @@ impl ResolvedWindowRules {
             }
 
             let mut open_on_output = None;
+            let mut open_on_workspace = None;
 
             for rule in rules {
                 let matches = |m| window_matches(window, &role, m);
@@ -137,6 +146,10 @@ impl ResolvedWindowRules {
                     open_on_output = Some(x);
                 }
 
+                if let Some(x) = rule.open_on_workspace.as_ref() {
+                    open_on_workspace = Some(x);
+                }
+
                 if let Some(x) = rule.open_maximized {
                     resolved.open_maximized = Some(x);
                 }
@@ -145,6 +158,10 @@ impl ResolvedWindowRules {
                     resolved.open_fullscreen = Some(x);
                 }
 
+                if let Some(x) = rule.open_floating {
+                    resolved.open_floating = Some(x);
+                }
+
                 if let Some(x) = rule.min_width {
                     resolved.min_width = Some(x);
                 }
@@ -170,6 +187,7 @@ impl ResolvedWindowRules {
             }
 
             resolved.open_on_output = open_on_output.map(|x| x.to_owned());
+            resolved.open_on_workspace = open_on_workspace.cloned();
         });
 
         resolved